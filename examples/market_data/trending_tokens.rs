@@ -12,7 +12,7 @@ use std::env;
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load credentials and authenticate
     dotenvy::dotenv().ok();
-    let mut market_client = authenticate().await?;
+    let market_client = authenticate().await?;
 
     println!("Trending Tokens Analysis");
     println!("Analyzing market trends across multiple timeframes\n");
@@ -41,10 +41,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     );
                     
                     println!("   Price: ${:.8}", token.price_usd);
-                    println!("   Change 24h: {:.2}%", token.price_change_24h);
-                    println!("   Volume: ${:.2}", token.volume_24h);
-                    println!("   Market Cap: ${:.2}", token.market_cap);
-                    println!("   Holders: {}", token.holders);
+                    println!("   Market Cap Change: {:.2}%", token.market_cap_change_percent);
+                    println!("   Volume: {:.2} SOL", token.volume_sol);
+                    println!("   Market Cap: {:.2} SOL", token.market_cap_sol);
+                    if let Some(holders) = token.holders {
+                        println!("   Holders: {}", holders);
+                    }
+                    println!("   Top 10 Holders: {:.1}%", token.top_10_holders_percent);
                     
                     // Risk assessment
                     assess_token_risk(token);
@@ -96,23 +99,23 @@ fn assess_token_risk(token: &axiomtrade_rs::models::market::TrendingToken) {
     let mut risk_factors = Vec::new();
     
     // Volume/Market Cap ratio
-    let vol_to_mcap = token.volume_24h / token.market_cap;
+    let vol_to_mcap = token.volume_sol / token.market_cap_sol;
     if vol_to_mcap > 1.0 {
         risk_factors.push("High volume/mcap ratio");
     }
     
-    // Extreme price movements
-    if token.price_change_24h.abs() > 100.0 {
+    // Extreme market cap movements
+    if token.market_cap_change_percent.abs() > 100.0 {
         risk_factors.push("Extreme price volatility");
     }
     
     // Very small market cap
-    if token.market_cap < 100000.0 {
+    if token.market_cap_sol < 500.0 {
         risk_factors.push("Very small market cap");
     }
     
     // Low holder count
-    if token.holders < 50 {
+    if token.holders.is_some_and(|holders| holders < 50) {
         risk_factors.push("Low holder count");
     }
     
@@ -125,22 +128,22 @@ fn assess_token_risk(token: &axiomtrade_rs::models::market::TrendingToken) {
 
 fn analyze_market_trends(tokens: &[axiomtrade_rs::models::market::TrendingToken], timeframe: &str) {
     let total_tokens = tokens.len();
-    let gainers = tokens.iter().filter(|t| t.price_change_24h > 0.0).count();
+    let gainers = tokens.iter().filter(|t| t.market_cap_change_percent > 0.0).count();
     let losers = total_tokens - gainers;
     
     let avg_change: f64 = tokens.iter()
-        .map(|t| t.price_change_24h)
+        .map(|t| t.market_cap_change_percent)
         .sum::<f64>() / total_tokens as f64;
     
     let total_volume: f64 = tokens.iter()
-        .map(|t| t.volume_24h)
+        .map(|t| t.volume_sol)
         .sum();
     
     println!("Market Summary for {}:", timeframe);
     println!("  Gainers: {} ({:.1}%)", gainers, (gainers as f64 / total_tokens as f64) * 100.0);
     println!("  Losers: {} ({:.1}%)", losers, (losers as f64 / total_tokens as f64) * 100.0);
     println!("  Average change: {:.2}%", avg_change);
-    println!("  Total volume: ${:.2}", total_volume);
+    println!("  Total volume: {:.2} SOL", total_volume);
     
     // Market sentiment
     if avg_change > 5.0 {
@@ -165,12 +168,12 @@ fn find_consistent_trends<'a>(
             .find(|lt| lt.symbol == st_token.symbol) {
             
             // Check if trending in same direction
-            let st_positive = st_token.price_change_24h > 0.0;
-            let lt_positive = lt_token.price_change_24h > 0.0;
+            let st_positive = st_token.market_cap_change_percent > 0.0;
+            let lt_positive = lt_token.market_cap_change_percent > 0.0;
             
             if st_positive == lt_positive && 
-               st_token.price_change_24h.abs() > 10.0 &&
-               lt_token.price_change_24h.abs() > 10.0 {
+               st_token.market_cap_change_percent.abs() > 10.0 &&
+               lt_token.market_cap_change_percent.abs() > 10.0 {
                 consistent.push(st_token);
             }
         }
@@ -184,17 +187,17 @@ async fn analyze_opportunities(tokens: &[axiomtrade_rs::models::market::Trending
     let opportunities: Vec<_> = tokens.iter()
         .filter(|token| {
             // Reasonable market cap
-            let reasonable_mcap = token.market_cap > 1000000.0 && token.market_cap < 100000000.0;
+            let reasonable_mcap = token.market_cap_sol > 5_000.0 && token.market_cap_sol < 500_000.0;
             
             // Moderate positive momentum
-            let good_momentum = token.price_change_24h > 5.0 && 
-                               token.price_change_24h < 50.0;
+            let good_momentum = token.market_cap_change_percent > 5.0 && 
+                               token.market_cap_change_percent < 50.0;
             
             // High volume
-            let high_volume = token.volume_24h > 100000.0;
+            let high_volume = token.volume_sol > 500.0;
             
             // Good holder count
-            let good_holders = token.holders > 100;
+            let good_holders = token.holders.is_none_or(|holders| holders > 100);
             
             reasonable_mcap && good_momentum && high_volume && good_holders
         })
@@ -207,17 +210,17 @@ async fn analyze_opportunities(tokens: &[axiomtrade_rs::models::market::Trending
     
     println!("Potential opportunities identified:");
     for (i, token) in opportunities.iter().enumerate() {
-        println!("{}. {} - {:.2}% gain, ${:.2} volume", 
+        println!("{}. {} - {:.2}% market cap gain, {:.2} SOL volume", 
             i + 1,
             token.symbol,
-            token.price_change_24h,
-            token.volume_24h
+            token.market_cap_change_percent,
+            token.volume_sol
         );
         
         // Simple scoring
-        let holders_score = (token.holders as f64 / 500.0).min(5.0);
-        let momentum_score = (token.price_change_24h / 10.0).min(5.0);
-        let volume_score = (token.volume_24h / 500000.0).min(5.0);
+        let holders_score = (token.holders.unwrap_or(0) as f64 / 500.0).min(5.0);
+        let momentum_score = (token.market_cap_change_percent / 10.0).min(5.0);
+        let volume_score = (token.volume_sol / 2_500.0).min(5.0);
         
         let total_score = (holders_score + momentum_score + volume_score) / 3.0;
         
@@ -5,9 +5,11 @@
 
 use axiomtrade_rs::api::portfolio::PortfolioClient;
 use axiomtrade_rs::auth::AuthClient;
+use axiomtrade_rs::pricing::ReferencePrices;
 use dotenvy::dotenv;
 use std::env;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,6 +27,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Get batch balances for the wallets
     let batch_response = client.get_batch_balance(&demo_wallets).await?;
+    let sol_usd = client.sol_usd().await?;
     
     if batch_response.balances.is_empty() {
         println!("No wallet balances found");
@@ -44,7 +47,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         
         // Add SOL balance as a "token"
         if wallet_balance.sol_balance > 0.0 {
-            let sol_value_usd = wallet_balance.sol_balance * 100.0; // Rough SOL price estimate
+            let sol_value_usd = wallet_balance.sol_balance * sol_usd;
             total_positions += 1;
             total_value += sol_value_usd;
             
@@ -265,8 +268,9 @@ async fn authenticate() -> Result<PortfolioClient, Box<dyn std::error::Error>> {
     let mut auth_client = AuthClient::new()?;
     auth_client.login(&email, &password, None).await?;
     
-    // Create portfolio client (uses same auth internally)
-    let portfolio_client = PortfolioClient::new()?;
+    // Create portfolio client (uses same auth internally), pricing SOL for USD conversions
+    let portfolio_client = PortfolioClient::new()?
+        .with_reference_prices(Arc::new(ReferencePrices::with_default_sources()?));
     
     Ok(portfolio_client)
 }
\ No newline at end of file
@@ -3,7 +3,7 @@
 /// This example demonstrates basic buy and sell operations using the
 /// Axiom Trade API with proper error handling and transaction verification.
 
-use axiomtrade_rs::{AuthClient, Result, AxiomError};
+use axiomtrade_rs::{AuthClient, Result, AxiomError, Lamports, TokenAmount};
 use axiomtrade_rs::api::trading::TradingClient;
 use std::env;
 
@@ -54,7 +54,7 @@ async fn main() -> Result<()> {
     
     let sol_mint = "So11111111111111111111111111111111111111112"; // Native SOL
     
-    match trading_client.get_quote(sol_mint, token_mint, amount_sol, Some(1.0)).await {
+    match trading_client.get_quote(sol_mint, token_mint, sol_amount(amount_sol)?, Some(1.0)).await {
         Ok(quote) => {
             println!("Current USDC swap quote:");
             println!("  Input: {} SOL", quote.in_amount);
@@ -132,7 +132,7 @@ async fn simulate_buy_trade(
     // Step 3: Get price quote
     println!("  ✓ Getting price quote");
     let sol_mint = "So11111111111111111111111111111111111111112";
-    match client.get_quote(sol_mint, token_mint, amount_sol, Some(1.0)).await {
+    match client.get_quote(sol_mint, token_mint, sol_amount(amount_sol)?, Some(1.0)).await {
         Ok(quote) => {
             println!("    Expected output: {} tokens", quote.out_amount);
             println!("    Price impact: {:.2}%", quote.price_impact);
@@ -163,7 +163,10 @@ async fn simulate_sell_trade(
     // Step 2: Get price quote
     println!("  ✓ Getting price quote");
     let sol_mint = "So11111111111111111111111111111111111111112";
-    match client.get_quote(token_mint, sol_mint, amount_tokens, Some(1.0)).await {
+    // USDC has 6 decimals
+    let usdc_amount = TokenAmount::from_ui_amount(amount_tokens, 6)
+        .map_err(|e| AxiomError::Api { message: e.to_string() })?;
+    match client.get_quote(token_mint, sol_mint, usdc_amount, Some(1.0)).await {
         Ok(quote) => {
             println!("    Expected output: {} SOL", quote.out_amount);
             println!("    Price impact: {:.2}%", quote.price_impact);
@@ -195,6 +198,13 @@ fn validate_amount(amount: f64, unit: &str) -> Result<()> {
     Ok(())
 }
 
+/// Converts a SOL amount to an exact wrapped SOL token amount
+fn sol_amount(amount_sol: f64) -> Result<TokenAmount> {
+    Lamports::from_sol(amount_sol)
+        .map(Lamports::to_token_amount)
+        .map_err(|e| AxiomError::Api { message: e.to_string() })
+}
+
 fn validate_token_mint(mint: &str) -> Result<()> {
     if mint.is_empty() {
         return Err(AxiomError::Api {
@@ -33,6 +33,9 @@ impl MessageHandler for BasicMessageHandler {
             WebSocketMessage::BalanceUpdate(balance) => {
                 println!("💰 Balance Update: {} SOL", balance.sol_balance);
             }
+            WebSocketMessage::NewPair(pair) => {
+                println!("🆕 New Pair: {} ({}) - {:.2} SOL liquidity", pair.token_ticker, pair.protocol, pair.initial_liquidity_sol);
+            }
             WebSocketMessage::Connected { session_id } => {
                 println!("🔗 Connected with session: {}", session_id);
             }
@@ -157,13 +157,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Check connection health
         if !ws_client.is_connected().await {
             println!("🔄 Connection lost, reconnecting...");
+            // Rejoins new_pairs and every token room
             ws_client.reconnect().await?;
-            
-            // Re-subscribe after reconnection
-            ws_client.subscribe_new_tokens().await.ok();
-            for (_symbol, mint) in &tokens_to_watch {
-                ws_client.subscribe_token_price(mint).await.ok();
-            }
         }
 
         // Show periodic summary
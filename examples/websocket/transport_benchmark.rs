@@ -0,0 +1,162 @@
+//! WebSocket Transport Benchmark
+//!
+//! Compares the tungstenite and fastwebsockets backends against a local echo
+//! server. Each round sends a batch of token price frames, waits for the echoes
+//! to be buffered, then times every frame from read through JSON parsing and
+//! room decoding until the message handler returns.
+//!
+//! Run with `cargo run --release --example transport_benchmark [frames_per_round] [rounds]`
+
+use axiomtrade_rs::websocket::{
+    decode_room_message, InboundFrame, MessageHandler, TransportKind, WebSocketMessage,
+};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+const MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+/// Counts price ticks so the decode work cannot be optimised away
+#[derive(Default)]
+struct CountingHandler {
+    ticks: AtomicU64,
+}
+
+#[async_trait]
+impl MessageHandler for CountingHandler {
+    async fn handle_message(&self, message: WebSocketMessage) {
+        if let WebSocketMessage::TokenPrice(tick) = message
+            && tick.price_usd.is_some()
+        {
+            self.ticks.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    async fn on_connected(&self, _session_id: String) {}
+    async fn on_disconnected(&self, _reason: String) {}
+    async fn on_error(&self, _error: String) {}
+}
+
+/// Starts an echo server on a free local port
+///
+/// # Returns
+///
+/// String - The server's ws:// URL
+async fn start_echo_server() -> Result<String, Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}/", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((tcp, _)) = listener.accept().await {
+            let _ = tcp.set_nodelay(true);
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(tcp).await else {
+                    return;
+                };
+                while let Some(Ok(message)) = ws.next().await {
+                    if message.is_text() && ws.send(message).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    Ok(url)
+}
+
+fn upgrade_request(url: &str) -> Result<http::Request<()>, http::Error> {
+    let host = url.trim_start_matches("ws://").trim_end_matches('/');
+    http::Request::builder()
+        .uri(url)
+        .header("Host", host)
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key())
+        .body(())
+}
+
+fn price_frame(sequence: usize) -> String {
+    format!(
+        r#"{{"room":"{}","content":{{"price":{},"priceSol":0.00000135,"marketCapSol":135.2,"volumeSol":52.7,"sequence":{}}}}}"#,
+        MINT,
+        0.000231 + sequence as f64 * 1e-9,
+        sequence
+    )
+}
+
+/// Times parse-to-handler for every echoed frame
+///
+/// # Returns
+///
+/// Vec<Duration> - One sample per frame
+async fn run_backend(
+    kind: TransportKind,
+    url: &str,
+    frames_per_round: usize,
+    rounds: usize,
+) -> Result<Vec<Duration>, Box<dyn std::error::Error>> {
+    let (mut reader, mut writer) = kind.connect(upgrade_request(url)?).await?;
+    let handler = CountingHandler::default();
+    let mut samples = Vec::with_capacity(frames_per_round * rounds);
+
+    for round in 0..rounds {
+        for i in 0..frames_per_round {
+            writer.send(Message::Text(price_frame(round * frames_per_round + i))).await?;
+        }
+        // Let the echoes land in the socket buffer so reads measure parsing, not the network
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        for _ in 0..frames_per_round {
+            let started = Instant::now();
+            let InboundFrame::Text(text) = reader.read_frame().await? else {
+                return Err("expected a text frame".into());
+            };
+            let value: serde_json::Value = serde_json::from_str(text)?;
+            if let (Some(room), Some(content)) = (value["room"].as_str(), value.get("content")) {
+                handler.handle_message(decode_room_message(room, content)?).await;
+            }
+            samples.push(started.elapsed());
+        }
+    }
+
+    writer.close().await;
+    assert_eq!(handler.ticks.load(Ordering::Relaxed) as usize, frames_per_round * rounds);
+    Ok(samples)
+}
+
+fn report(kind: TransportKind, mut samples: Vec<Duration>) {
+    samples.sort();
+    let percentile = |p: f64| samples[((samples.len() - 1) as f64 * p) as usize];
+    let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+    println!(
+        "{:<16} frames {:>7}  mean {:>9.2?}  p50 {:>9.2?}  p99 {:>9.2?}  max {:>9.2?}",
+        format!("{:?}", kind),
+        samples.len(),
+        mean,
+        percentile(0.50),
+        percentile(0.99),
+        percentile(1.0),
+    );
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let frames_per_round = args.next().map_or(Ok(500), |a| a.parse())?;
+    let rounds = args.next().map_or(Ok(20), |a| a.parse())?;
+
+    let url = start_echo_server().await?;
+    println!("Echo server at {}, {} rounds of {} frames\n", url, rounds, frames_per_round);
+
+    for kind in [TransportKind::Tungstenite, TransportKind::FastWebSockets] {
+        // One warm-up round so allocation and connection setup are not measured
+        run_backend(kind, &url, frames_per_round, 1).await?;
+        let samples = run_backend(kind, &url, frames_per_round, rounds).await?;
+        report(kind, samples);
+    }
+
+    Ok(())
+}
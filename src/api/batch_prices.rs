@@ -0,0 +1,338 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::join_all;
+
+use crate::api::market_data::{MarketDataClient, MarketDataError};
+use crate::auth::AuthError;
+use crate::models::market::PriceData;
+use crate::models::solana::Pubkey;
+use crate::utils::cache::ResponseCache;
+use crate::utils::rate_limiter::RateLimiter;
+
+/// Mints per `/batch-prices` request by default
+pub const DEFAULT_BATCH_CHUNK_SIZE: usize = 50;
+
+/// Per-mint results of a batch price request
+#[derive(Debug, Default)]
+pub struct BatchPrices {
+    /// One entry per unique requested mint, keyed as passed in
+    pub prices: HashMap<String, Result<PriceData, MarketDataError>>,
+}
+
+impl BatchPrices {
+    pub fn get(&self, mint: &str) -> Option<&Result<PriceData, MarketDataError>> {
+        self.prices.get(mint)
+    }
+
+    /// Prices that were fetched successfully
+    pub fn succeeded(&self) -> HashMap<&str, &PriceData> {
+        self.prices
+            .iter()
+            .filter_map(|(mint, result)| result.as_ref().ok().map(|price| (mint.as_str(), price)))
+            .collect()
+    }
+
+    /// Mints that failed, with their errors
+    pub fn failed(&self) -> HashMap<&str, &MarketDataError> {
+        self.prices
+            .iter()
+            .filter_map(|(mint, result)| result.as_ref().err().map(|error| (mint.as_str(), error)))
+            .collect()
+    }
+
+    /// Returns true if every mint has a price
+    pub fn is_complete(&self) -> bool {
+        self.prices.values().all(Result::is_ok)
+    }
+
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+}
+
+/// Deduplicated, validated mints split into request-sized chunks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct BatchPlan {
+    pub chunks: Vec<Vec<String>>,
+    /// Invalid mints with the parse error message
+    pub invalid: Vec<(String, String)>,
+}
+
+impl BatchPlan {
+    pub fn new(token_mints: &[String], chunk_size: usize) -> Self {
+        let mut seen = HashSet::new();
+        let mut valid = Vec::new();
+        let mut invalid = Vec::new();
+
+        for mint in token_mints {
+            if !seen.insert(mint.as_str()) {
+                continue;
+            }
+            match mint.parse::<Pubkey>() {
+                Ok(_) => valid.push(mint.clone()),
+                Err(e) => invalid.push((mint.clone(), e.to_string())),
+            }
+        }
+
+        Self {
+            chunks: valid.chunks(chunk_size.max(1)).map(<[String]>::to_vec).collect(),
+            invalid,
+        }
+    }
+
+    pub fn invalid_results(&self) -> HashMap<String, Result<PriceData, MarketDataError>> {
+        self.invalid
+            .iter()
+            .map(|(mint, error)| {
+                let result = Err(MarketDataError::InvalidTokenMint(format!("{}: {}", error, mint)));
+                (mint.clone(), result)
+            })
+            .collect()
+    }
+}
+
+/// Whether an error means retrying mints one by one is pointless
+pub(crate) fn stops_fallback(error: &MarketDataError) -> bool {
+    matches!(error, MarketDataError::AuthError(_) | MarketDataError::RateLimited(_))
+}
+
+/// Copies a batch error for each mint it applies to, keeping the variant so callers can tell
+/// a rate limit or auth failure apart. Errors that cannot be copied become `ApiError`.
+fn copy_error(error: &MarketDataError) -> MarketDataError {
+    match error {
+        MarketDataError::AuthError(e) => MarketDataError::AuthError(match e {
+            AuthError::InvalidCredentials => AuthError::InvalidCredentials,
+            AuthError::OtpRequired => AuthError::OtpRequired,
+            AuthError::InvalidOtp => AuthError::InvalidOtp,
+            AuthError::TokenExpired => AuthError::TokenExpired,
+            AuthError::TokenNotFound => AuthError::TokenNotFound,
+            AuthError::Unauthorized => AuthError::Unauthorized,
+            AuthError::NotAuthenticated => AuthError::NotAuthenticated,
+            other => AuthError::ApiError { message: other.to_string() },
+        }),
+        MarketDataError::InvalidTokenMint(m) => MarketDataError::InvalidTokenMint(m.clone()),
+        MarketDataError::TokenNotFound(m) => MarketDataError::TokenNotFound(m.clone()),
+        MarketDataError::ApiError(m) => MarketDataError::ApiError(m.clone()),
+        MarketDataError::ParsingError(m) => MarketDataError::ParsingError(m.clone()),
+        MarketDataError::RateLimited(m) => MarketDataError::RateLimited(m.clone()),
+        MarketDataError::InvalidConfig(m) => MarketDataError::InvalidConfig(m.clone()),
+        MarketDataError::NetworkError(e) => {
+            MarketDataError::ApiError(format!("Batch request failed: {}", e))
+        }
+    }
+}
+
+/// Maps a batch response back onto the requested mints.
+///
+/// A failed request fails every mint in the chunk with a copy of its error; mints missing
+/// from a successful response get `TokenNotFound`.
+pub(crate) fn merge_chunk(
+    chunk: &[String],
+    batch: Result<Vec<PriceData>, MarketDataError>,
+) -> Vec<(String, Result<PriceData, MarketDataError>)> {
+    match batch {
+        Ok(prices) => {
+            let mut by_mint: HashMap<Pubkey, PriceData> =
+                prices.into_iter().map(|price| (price.mint_address, price)).collect();
+            chunk
+                .iter()
+                .map(|mint| {
+                    let result = mint
+                        .parse::<Pubkey>()
+                        .ok()
+                        .and_then(|key| by_mint.remove(&key))
+                        .ok_or_else(|| MarketDataError::TokenNotFound(mint.clone()));
+                    (mint.clone(), result)
+                })
+                .collect()
+        }
+        Err(e) => chunk.iter().map(|mint| (mint.clone(), Err(copy_error(&e)))).collect(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchPriceConfig {
+    /// Maximum mints per batch request
+    pub chunk_size: usize,
+    /// Batch and fallback requests allowed per `rate_window`, shared by all workers
+    pub max_requests: usize,
+    pub rate_window: Duration,
+    /// Retry failed and omitted mints with `get_token_price`, unless the batch failed on
+    /// authentication or a rate limit
+    pub fallback_to_single: bool,
+}
+
+impl Default for BatchPriceConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_BATCH_CHUNK_SIZE,
+            max_requests: 10,
+            rate_window: Duration::from_secs(1),
+            fallback_to_single: true,
+        }
+    }
+}
+
+/// Fetches large mint lists by running batch chunks concurrently.
+///
+/// Each worker client handles one chunk at a time, so concurrency equals the number of
+/// workers. All workers share one rate limiter and, when built with `new`, one response cache.
+pub struct BatchPriceFetcher {
+    workers: Vec<MarketDataClient>,
+    limiter: RateLimiter,
+    config: BatchPriceConfig,
+}
+
+impl BatchPriceFetcher {
+    /// Creates a fetcher with `concurrency` default clients sharing one cache
+    ///
+    /// # Arguments
+    ///
+    /// * `concurrency` - usize - Number of chunks in flight at once
+    /// * `config` - BatchPriceConfig - Chunking, rate limit and fallback settings
+    ///
+    /// # Returns
+    ///
+    /// Result<BatchPriceFetcher, MarketDataError> - A new fetcher instance
+    pub fn new(concurrency: usize, config: BatchPriceConfig) -> Result<Self, MarketDataError> {
+        let cache = Arc::new(ResponseCache::default());
+        let workers = (0..concurrency.max(1))
+            .map(|_| Ok(MarketDataClient::new()?.with_cache(Arc::clone(&cache))))
+            .collect::<Result<Vec<_>, MarketDataError>>()?;
+        Self::with_clients(workers, config)
+    }
+
+    /// Creates a fetcher over existing clients
+    ///
+    /// # Arguments
+    ///
+    /// * `workers` - Vec<MarketDataClient> - One client per concurrent chunk; must not be empty
+    /// * `config` - BatchPriceConfig - Chunking, rate limit and fallback settings
+    ///
+    /// # Returns
+    ///
+    /// Result<BatchPriceFetcher, MarketDataError> - A new fetcher instance, or `InvalidConfig`
+    /// if `workers` is empty
+    pub fn with_clients(
+        workers: Vec<MarketDataClient>,
+        config: BatchPriceConfig,
+    ) -> Result<Self, MarketDataError> {
+        if workers.is_empty() {
+            return Err(MarketDataError::InvalidConfig(
+                "BatchPriceFetcher needs at least one client".to_string(),
+            ));
+        }
+        let limiter = RateLimiter::new(config.max_requests.max(1), config.rate_window);
+        Ok(Self {
+            workers,
+            limiter,
+            config,
+        })
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Gets prices for any number of mints
+    ///
+    /// # Arguments
+    ///
+    /// * `token_mints` - &[String] - Token mint addresses; duplicates are requested once
+    ///
+    /// # Returns
+    ///
+    /// BatchPrices - A result for every unique mint
+    pub async fn fetch(&mut self, token_mints: &[String]) -> BatchPrices {
+        let plan = BatchPlan::new(token_mints, self.config.chunk_size);
+        let mut prices = plan.invalid_results();
+
+        // Deal chunks round-robin so each worker processes its share sequentially
+        let concurrency = self.workers.len();
+        let mut assignments: Vec<Vec<&[String]>> = vec![Vec::new(); concurrency];
+        for (i, chunk) in plan.chunks.iter().enumerate() {
+            assignments[i % concurrency].push(chunk);
+        }
+
+        let limiter = &self.limiter;
+        let fallback = self.config.fallback_to_single;
+        let runs = self.workers.iter_mut().zip(assignments).map(|(worker, chunks)| async move {
+            let mut results = Vec::new();
+            for chunk in chunks {
+                limiter.wait_if_needed().await;
+                results.extend(worker.fetch_price_chunk(chunk, fallback, Some(limiter)).await);
+            }
+            results
+        });
+
+        for results in join_all(runs).await {
+            prices.extend(results);
+        }
+        BatchPrices { prices }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const BONK: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    fn price(mint: &str) -> PriceData {
+        PriceData {
+            mint_address: mint.parse().unwrap(),
+            price_usd: 1.0,
+            price_sol: 0.01,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_plan_dedupes_validates_and_chunks() {
+        let mints: Vec<String> = [SOL, "not-a-mint", USDC, SOL, BONK].iter().map(|m| m.to_string()).collect();
+        let plan = BatchPlan::new(&mints, 2);
+
+        assert_eq!(plan.chunks, vec![vec![SOL.to_string(), USDC.to_string()], vec![BONK.to_string()]]);
+        assert_eq!(plan.invalid.len(), 1);
+        assert!(matches!(
+            plan.invalid_results().get("not-a-mint"),
+            Some(Err(MarketDataError::InvalidTokenMint(_)))
+        ));
+    }
+
+    #[test]
+    fn test_merge_chunk_reports_omitted_and_failed() {
+        let chunk = vec![SOL.to_string(), USDC.to_string()];
+
+        let merged: HashMap<_, _> = merge_chunk(&chunk, Ok(vec![price(SOL)])).into_iter().collect();
+        assert!(merged[SOL].is_ok());
+        assert!(matches!(merged[USDC], Err(MarketDataError::TokenNotFound(_))));
+
+        let failed = merge_chunk(&chunk, Err(MarketDataError::ApiError("boom".to_string())));
+        assert_eq!(failed.len(), 2);
+        assert!(failed.iter().all(|(_, r)| matches!(r, Err(MarketDataError::ApiError(m)) if m.contains("boom"))));
+
+        let limited = merge_chunk(&chunk, Err(MarketDataError::RateLimited("slow down".to_string())));
+        assert!(limited.iter().all(|(_, r)| matches!(r, Err(MarketDataError::RateLimited(_)))));
+        let unauthorized = merge_chunk(&chunk, Err(MarketDataError::AuthError(AuthError::Unauthorized)));
+        assert!(unauthorized.iter().all(|(_, r)| r.as_ref().is_err_and(stops_fallback)));
+
+        assert!(stops_fallback(&MarketDataError::RateLimited("slow down".to_string())));
+        assert!(stops_fallback(&MarketDataError::AuthError(AuthError::Unauthorized)));
+        assert!(!stops_fallback(&MarketDataError::TokenNotFound(SOL.to_string())));
+    }
+
+    #[test]
+    fn test_fetcher_needs_a_client() {
+        let result = BatchPriceFetcher::with_clients(Vec::new(), BatchPriceConfig::default());
+        assert!(matches!(result, Err(MarketDataError::InvalidConfig(_))));
+    }
+}
@@ -1,11 +1,19 @@
 use crate::auth::{AuthClient, AuthError};
+use crate::models::solana::Pubkey;
 use crate::models::market::{
-    ChartTimeframe, MarketStats, PriceData, PriceFeed, TimePeriod, TokenAnalysis, TokenChart,
-    TokenInfo, TokenSearch, TokenSearchResult, TrendingToken,
+    ChartCurrency, ChartTimeframe, MarketStats, PriceData, PriceFeed, TimePeriod, TokenAnalysis, TokenChart,
+    TokenInfo, TokenProfile, TokenSearch, TokenSearchResult, TrendingToken,
 };
+use crate::models::screener::ScreenerToken;
+use crate::api::batch_prices::{merge_chunk, stops_fallback, BatchPlan, BatchPrices};
+use crate::utils::cache::{CacheStats, ResponseCache};
+use crate::utils::rate_limiter::RateLimiter;
 use reqwest::StatusCode;
 use serde_json;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::RwLock;
 
 #[derive(Error, Debug)]
 pub enum MarketDataError {
@@ -26,11 +34,108 @@ pub enum MarketDataError {
 
     #[error("Parsing error: {0}")]
     ParsingError(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+/// Market data endpoints whose responses are cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CachedEndpoint {
+    TokenInfo,
+    TokenAnalysis,
+    TokenProfile,
+    Price,
+    Trending,
+}
+
+impl CachedEndpoint {
+    /// Gets the cache namespace for this endpoint.
+    ///
+    /// # Returns
+    ///
+    /// &'static str - The namespace name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CachedEndpoint::TokenInfo => "token_info",
+            CachedEndpoint::TokenAnalysis => "token_analysis",
+            CachedEndpoint::TokenProfile => "token_profile",
+            CachedEndpoint::Price => "price",
+            CachedEndpoint::Trending => "trending",
+        }
+    }
+}
+
+/// Per-endpoint cache TTLs. A zero TTL disables caching for that endpoint.
+#[derive(Debug, Clone)]
+pub struct MarketDataCacheConfig {
+    pub token_info_ttl: Duration,
+    pub token_analysis_ttl: Duration,
+    pub token_profile_ttl: Duration,
+    pub price_ttl: Duration,
+    pub trending_ttl: Duration,
+}
+
+impl Default for MarketDataCacheConfig {
+    fn default() -> Self {
+        Self {
+            token_info_ttl: Duration::from_secs(300),
+            token_analysis_ttl: Duration::from_secs(60),
+            token_profile_ttl: Duration::from_secs(60),
+            price_ttl: Duration::from_secs(2),
+            trending_ttl: Duration::from_secs(15),
+        }
+    }
+}
+
+impl MarketDataCacheConfig {
+    /// Creates a configuration that disables caching for every endpoint.
+    ///
+    /// # Returns
+    ///
+    /// MarketDataCacheConfig - Configuration with all TTLs set to zero.
+    pub fn disabled() -> Self {
+        Self {
+            token_info_ttl: Duration::ZERO,
+            token_analysis_ttl: Duration::ZERO,
+            token_profile_ttl: Duration::ZERO,
+            price_ttl: Duration::ZERO,
+            trending_ttl: Duration::ZERO,
+        }
+    }
+
+    /// Gets the TTL for an endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - CachedEndpoint - The cached endpoint.
+    ///
+    /// # Returns
+    ///
+    /// Duration - How long responses stay fresh.
+    pub fn ttl(&self, endpoint: CachedEndpoint) -> Duration {
+        match endpoint {
+            CachedEndpoint::TokenInfo => self.token_info_ttl,
+            CachedEndpoint::TokenAnalysis => self.token_analysis_ttl,
+            CachedEndpoint::TokenProfile => self.token_profile_ttl,
+            CachedEndpoint::Price => self.price_ttl,
+            CachedEndpoint::Trending => self.trending_ttl,
+        }
+    }
 }
 
+/// Client for the market data endpoints.
+///
+/// Methods take `&self`, so concurrent callers of one client share in-flight requests for
+/// cached endpoints.
 pub struct MarketDataClient {
-    auth_client: AuthClient,
+    auth_client: Arc<RwLock<AuthClient>>,
     base_url: String,
+    cache: Arc<ResponseCache>,
+    cache_config: MarketDataCacheConfig,
 }
 
 impl MarketDataClient {
@@ -41,8 +146,10 @@ impl MarketDataClient {
     /// Result<MarketDataClient, MarketDataError> - A new market data client instance.
     pub fn new() -> Result<Self, MarketDataError> {
         Ok(Self {
-            auth_client: AuthClient::new()?,
+            auth_client: Arc::new(RwLock::new(AuthClient::new()?)),
             base_url: "https://api6.axiom.trade".to_string(),
+            cache: Arc::new(ResponseCache::default()),
+            cache_config: MarketDataCacheConfig::default(),
         })
     }
 
@@ -57,13 +164,114 @@ impl MarketDataClient {
     /// Result<MarketDataClient, MarketDataError> - A new market data client instance.
     pub fn with_base_url(base_url: String) -> Result<Self, MarketDataError> {
         Ok(Self {
-            auth_client: AuthClient::new()?,
+            auth_client: Arc::new(RwLock::new(AuthClient::new()?)),
             base_url,
+            cache: Arc::new(ResponseCache::default()),
+            cache_config: MarketDataCacheConfig::default(),
         })
     }
 
+    /// Uses a shared response cache.
+    ///
+    /// Clients sharing a cache also share in-flight requests, so concurrent callers for the
+    /// same token trigger a single HTTP request even across clients.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - Arc<ResponseCache> - The cache to share.
+    ///
+    /// # Returns
+    ///
+    /// MarketDataClient - The client using the shared cache.
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Sets the per-endpoint cache TTLs.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - MarketDataCacheConfig - The TTL configuration.
+    ///
+    /// # Returns
+    ///
+    /// MarketDataClient - The client using the new TTLs.
+    pub fn with_cache_config(mut self, config: MarketDataCacheConfig) -> Self {
+        self.cache_config = config;
+        self
+    }
+
+    /// Gets the response cache, e.g. to share it with another client.
+    ///
+    /// # Returns
+    ///
+    /// Arc<ResponseCache> - The client's cache.
+    pub fn cache(&self) -> Arc<ResponseCache> {
+        self.cache.clone()
+    }
+
+    /// Gets cache statistics across all cached endpoints.
+    ///
+    /// # Returns
+    ///
+    /// CacheStats - Hits, misses, coalesced requests and entry count.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Gets cache statistics for one endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - CachedEndpoint - The cached endpoint.
+    ///
+    /// # Returns
+    ///
+    /// CacheStats - Counters for the endpoint.
+    pub fn endpoint_cache_stats(&self, endpoint: CachedEndpoint) -> CacheStats {
+        self.cache.namespace_stats(endpoint.as_str())
+    }
+
+    /// Drops every cached response for a token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - &str - The token mint, pair address or symbol used as the cache key.
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed.
+    pub fn invalidate_token(&self, token: &str) -> usize {
+        self.cache.invalidate_key(token)
+    }
+
+    /// Drops every cached response for an endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - CachedEndpoint - The cached endpoint.
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed.
+    pub fn invalidate_endpoint(&self, endpoint: CachedEndpoint) -> usize {
+        self.cache.invalidate_namespace(endpoint.as_str())
+    }
+
+    /// Drops every cached response.
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed.
+    pub fn clear_cache(&self) -> usize {
+        self.cache.clear()
+    }
+
     /// Gets trending meme tokens.
     ///
+    /// Responses are cached for `trending_ttl` and concurrent requests are coalesced.
+    ///
     /// # Arguments
     ///
     /// * `time_period` - TimePeriod - The time period for trending tokens.
@@ -72,53 +280,54 @@ impl MarketDataClient {
     ///
     /// Result<Vec<TrendingToken>, MarketDataError> - List of trending tokens.
     pub async fn get_trending_tokens(
-        &mut self,
+        &self,
         time_period: TimePeriod,
     ) -> Result<Vec<TrendingToken>, MarketDataError> {
-        let period_str = match time_period {
-            TimePeriod::OneHour => "1h",
-            TimePeriod::TwentyFourHours => "24h",
-            TimePeriod::SevenDays => "7d",
-            TimePeriod::ThirtyDays => "30d",
-        };
+        let tokens = self.get_trending_screener_tokens(time_period).await?;
+        Ok(tokens.iter().map(TrendingToken::from).collect())
+    }
 
-        let url = format!("{}/meme-trending?timePeriod={}", self.base_url, period_str);
+    /// Gets trending tokens with every field the endpoint returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_period` - TimePeriod - The time period for trending tokens.
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<ScreenerToken>, MarketDataError> - Trending tokens in server order, ranked from 1.
+    pub async fn get_trending_screener_tokens(
+        &self,
+        time_period: TimePeriod,
+    ) -> Result<Vec<ScreenerToken>, MarketDataError> {
+        let cache = self.cache.clone();
+        let ttl = self.cache_config.ttl(CachedEndpoint::Trending);
+        cache
+            .get_or_fetch(
+                CachedEndpoint::Trending.as_str(),
+                time_period.as_str(),
+                ttl,
+                self.fetch_trending_tokens(time_period),
+            )
+            .await
+    }
+
+    async fn fetch_trending_tokens(
+        &self,
+        time_period: TimePeriod,
+    ) -> Result<Vec<ScreenerToken>, MarketDataError> {
+        let url = format!("{}/meme-trending?timePeriod={}", self.base_url, time_period.as_str());
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
         match response.status() {
             StatusCode::OK => {
                 let json_value = response.json::<serde_json::Value>().await?;
-
-                if let Some(array) = json_value.as_array() {
-                    let mut tokens = Vec::new();
-                    for item in array {
-                        let token = TrendingToken {
-                            mint_address: item["tokenAddress"].as_str().unwrap_or("").to_string(),
-                            symbol: item["tokenTicker"].as_str().unwrap_or("").to_string(),
-                            name: item["tokenName"].as_str().unwrap_or("").to_string(),
-                            price_usd: item["priceUsd"].as_f64().unwrap_or(0.0),
-                            price_change_24h: item["marketCapPercentChange"].as_f64().unwrap_or(0.0),
-                            price_change_7d: 0.0,
-                            volume_24h: item["volumeSol"].as_f64().unwrap_or(0.0),
-                            market_cap: item["marketCapSol"].as_f64().unwrap_or(0.0),
-                            holders: item["top10Holders"].as_f64().unwrap_or(0.0) as u64,
-                            rank: 0,
-                            logo_uri: item["tokenImage"].as_str().map(|s| s.to_string()),
-                        };
-                        tokens.push(token);
-                    }
-
-                    for (i, token) in tokens.iter_mut().enumerate() {
-                        token.rank = (i + 1) as u32;
-                    }
-
-                    Ok(tokens)
-                } else {
-                    Err(MarketDataError::ParsingError("Expected array response".to_string()))
-                }
+                parse_screener_tokens(json_value)
             }
             StatusCode::UNAUTHORIZED => Err(MarketDataError::AuthError(AuthError::Unauthorized)),
             status => {
@@ -141,7 +350,7 @@ impl MarketDataClient {
     ///
     /// Result<TokenInfo, MarketDataError> - Detailed token information.
     pub async fn get_token_info(
-        &mut self,
+        &self,
         token_symbol: &str,
     ) -> Result<TokenInfo, MarketDataError> {
         if token_symbol.is_empty() {
@@ -153,6 +362,8 @@ impl MarketDataClient {
         let url = format!("{}/token-analysis?tokenTicker={}", self.base_url, token_symbol);
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -177,6 +388,8 @@ impl MarketDataClient {
 
     /// Gets token creator analysis and related tokens.
     ///
+    /// Responses are cached for `token_analysis_ttl` and concurrent requests are coalesced.
+    ///
     /// # Arguments
     ///
     /// * `token_symbol` - &str - The token symbol (e.g., "BONK", "SOL", "USDC").
@@ -185,7 +398,23 @@ impl MarketDataClient {
     ///
     /// Result<TokenAnalysis, MarketDataError> - Creator analysis and related tokens.
     pub async fn get_token_analysis(
-        &mut self,
+        &self,
+        token_symbol: &str,
+    ) -> Result<TokenAnalysis, MarketDataError> {
+        let cache = self.cache.clone();
+        let ttl = self.cache_config.ttl(CachedEndpoint::TokenAnalysis);
+        cache
+            .get_or_fetch(
+                CachedEndpoint::TokenAnalysis.as_str(),
+                token_symbol,
+                ttl,
+                self.fetch_token_analysis(token_symbol),
+            )
+            .await
+    }
+
+    async fn fetch_token_analysis(
+        &self,
         token_symbol: &str,
     ) -> Result<TokenAnalysis, MarketDataError> {
         if token_symbol.is_empty() {
@@ -197,6 +426,8 @@ impl MarketDataClient {
         let url = format!("{}/token-analysis?tokenTicker={}", self.base_url, token_symbol);
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -221,6 +452,8 @@ impl MarketDataClient {
 
     /// Gets quick token information by address (mint or pair address).
     ///
+    /// Responses are cached for `token_info_ttl` and concurrent requests are coalesced.
+    ///
     /// # Arguments
     ///
     /// * `address` - &str - The token mint address or pair address.
@@ -229,14 +462,31 @@ impl MarketDataClient {
     ///
     /// Result<TokenInfo, MarketDataError> - Quick token information.
     pub async fn get_token_info_by_address(
-        &mut self,
+        &self,
         address: &str,
     ) -> Result<TokenInfo, MarketDataError> {
         self.validate_token_mint(address)?;
+        let cache = self.cache.clone();
+        let ttl = self.cache_config.ttl(CachedEndpoint::TokenInfo);
+        cache
+            .get_or_fetch(
+                CachedEndpoint::TokenInfo.as_str(),
+                address,
+                ttl,
+                self.fetch_token_info_by_address(address),
+            )
+            .await
+    }
 
+    async fn fetch_token_info_by_address(
+        &self,
+        address: &str,
+    ) -> Result<TokenInfo, MarketDataError> {
         let url = format!("{}/clipboard-pair-info?address={}", self.base_url, address);
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -259,8 +509,55 @@ impl MarketDataClient {
         }
     }
 
+    /// Gets token metadata and creator analysis for a mint or pair address.
+    ///
+    /// Combines the `/clipboard-pair-info` lookup by address with `/token-analysis` for the
+    /// resolved ticker, going through the caches of both. A token the analysis endpoint does
+    /// not know yields a profile without creator data rather than an error.
+    ///
+    /// Responses are cached for `token_profile_ttl` and concurrent requests are coalesced.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &str - The token mint address or pair address.
+    ///
+    /// # Returns
+    ///
+    /// Result<TokenProfile, MarketDataError> - Combined token metadata and creator analysis.
+    pub async fn get_token_profile(
+        &self,
+        address: &str,
+    ) -> Result<TokenProfile, MarketDataError> {
+        self.validate_token_mint(address)?;
+        let cache = self.cache.clone();
+        let ttl = self.cache_config.ttl(CachedEndpoint::TokenProfile);
+        cache
+            .get_or_fetch(
+                CachedEndpoint::TokenProfile.as_str(),
+                address,
+                ttl,
+                self.fetch_token_profile(address),
+            )
+            .await
+    }
+
+    async fn fetch_token_profile(
+        &self,
+        address: &str,
+    ) -> Result<TokenProfile, MarketDataError> {
+        let info = self.get_token_info_by_address(address).await?;
+        let analysis = match self.get_token_analysis(&info.symbol).await {
+            Ok(analysis) => Some(analysis),
+            Err(MarketDataError::TokenNotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        Ok(TokenProfile::from_parts(info, analysis))
+    }
+
     /// Gets current price data for a token.
     ///
+    /// Responses are cached for `price_ttl` and concurrent requests are coalesced.
+    ///
     /// # Arguments
     ///
     /// * `token_mint` - &str - The token mint address.
@@ -269,14 +566,31 @@ impl MarketDataClient {
     ///
     /// Result<PriceData, MarketDataError> - Current price data.
     pub async fn get_token_price(
-        &mut self,
+        &self,
         token_mint: &str,
     ) -> Result<PriceData, MarketDataError> {
         self.validate_token_mint(token_mint)?;
+        let cache = self.cache.clone();
+        let ttl = self.cache_config.ttl(CachedEndpoint::Price);
+        cache
+            .get_or_fetch(
+                CachedEndpoint::Price.as_str(),
+                token_mint,
+                ttl,
+                self.fetch_token_price(token_mint),
+            )
+            .await
+    }
 
+    async fn fetch_token_price(
+        &self,
+        token_mint: &str,
+    ) -> Result<PriceData, MarketDataError> {
         let url = format!("{}/price/{}", self.base_url, token_mint);
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -289,6 +603,7 @@ impl MarketDataClient {
                 Err(MarketDataError::TokenNotFound(token_mint.to_string()))
             }
             StatusCode::UNAUTHORIZED => Err(MarketDataError::AuthError(AuthError::Unauthorized)),
+            StatusCode::TOO_MANY_REQUESTS => Err(MarketDataError::RateLimited(response.text().await?)),
             status => {
                 let error_text = response.text().await?;
                 Err(MarketDataError::ApiError(format!(
@@ -310,22 +625,22 @@ impl MarketDataClient {
     ///
     /// Result<PriceFeed, MarketDataError> - Historical price feed.
     pub async fn get_price_feed(
-        &mut self,
+        &self,
         token_mint: &str,
         time_period: TimePeriod,
     ) -> Result<PriceFeed, MarketDataError> {
         self.validate_token_mint(token_mint)?;
 
-        let period_str = match time_period {
-            TimePeriod::OneHour => "1h",
-            TimePeriod::TwentyFourHours => "24h",
-            TimePeriod::SevenDays => "7d",
-            TimePeriod::ThirtyDays => "30d",
-        };
-
-        let url = format!("{}/price-feed/{}?period={}", self.base_url, token_mint, period_str);
+        let url = format!(
+            "{}/price-feed/{}?period={}",
+            self.base_url,
+            token_mint,
+            time_period.as_str()
+        );
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -360,30 +675,47 @@ impl MarketDataClient {
     ///
     /// Result<TokenChart, MarketDataError> - Chart data with candles.
     pub async fn get_token_chart(
-        &mut self,
+        &self,
         token_mint: &str,
         timeframe: ChartTimeframe,
         limit: Option<usize>,
     ) -> Result<TokenChart, MarketDataError> {
-        self.validate_token_mint(token_mint)?;
+        self.get_token_chart_before(token_mint, timeframe, None, None, limit).await
+    }
 
-        let timeframe_str = match timeframe {
-            ChartTimeframe::OneMinute => "1m",
-            ChartTimeframe::FiveMinutes => "5m",
-            ChartTimeframe::FifteenMinutes => "15m",
-            ChartTimeframe::OneHour => "1h",
-            ChartTimeframe::FourHours => "4h",
-            ChartTimeframe::OneDay => "1d",
-            ChartTimeframe::OneWeek => "1w",
-        };
+    /// Gets chart data ending before a point in time, for paging backwards through history.
+    ///
+    /// The `to` and `currency` query parameters are not part of the documented endpoint, so
+    /// callers should check that returned pages actually move backwards and that
+    /// `TokenChart::currency` does not contradict the requested currency.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_mint` - &str - The token mint address.
+    /// * `timeframe` - ChartTimeframe - The chart timeframe.
+    /// * `currency` - Option<ChartCurrency> - Quote currency to request; None for the server default.
+    /// * `before` - Option<i64> - Only return candles that start before this Unix timestamp (seconds); None for the latest candles.
+    /// * `limit` - Option<usize> - Maximum number of candles to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// Result<TokenChart, MarketDataError> - Chart data with candles.
+    pub async fn get_token_chart_before(
+        &self,
+        token_mint: &str,
+        timeframe: ChartTimeframe,
+        currency: Option<ChartCurrency>,
+        before: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<TokenChart, MarketDataError> {
+        self.validate_token_mint(token_mint)?;
 
-        let mut url = format!("{}/chart/{}?timeframe={}", self.base_url, token_mint, timeframe_str);
-        if let Some(limit) = limit {
-            url = format!("{}&limit={}", url, limit);
-        }
+        let url = chart_url(&self.base_url, token_mint, timeframe, currency, before, limit);
 
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -411,10 +743,12 @@ impl MarketDataClient {
     /// # Returns
     ///
     /// Result<MarketStats, MarketDataError> - Market statistics.
-    pub async fn get_market_stats(&mut self) -> Result<MarketStats, MarketDataError> {
+    pub async fn get_market_stats(&self) -> Result<MarketStats, MarketDataError> {
         let url = format!("{}/meme-trending?timePeriod=24h", self.base_url);
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -467,7 +801,7 @@ impl MarketDataClient {
     ///
     /// Result<TokenSearch, MarketDataError> - Search results.
     pub async fn search_tokens(
-        &mut self,
+        &self,
         query: &str,
         limit: Option<usize>,
     ) -> Result<TokenSearch, MarketDataError> {
@@ -488,6 +822,8 @@ impl MarketDataClient {
 
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::GET, &url, None)
             .await?;
 
@@ -510,6 +846,59 @@ impl MarketDataClient {
         }
     }
 
+    /// Searches tokens, returning every field the endpoint provides.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - &str - The search query.
+    /// * `limit` - Option<usize> - Maximum number of results.
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<ScreenerToken>, MarketDataError> - Matches in server order, ranked from 1.
+    pub async fn search_screener_tokens(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<ScreenerToken>, MarketDataError> {
+        if query.is_empty() {
+            return Err(MarketDataError::ApiError(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+
+        let mut url = format!(
+            "{}/search-v3?searchQuery={}",
+            self.base_url,
+            urlencoding::encode(query)
+        );
+        if let Some(limit) = limit {
+            url = format!("{}&limit={}", url, limit);
+        }
+
+        let response = self
+            .auth_client
+            .write()
+            .await
+            .make_authenticated_request(reqwest::Method::GET, &url, None)
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let json_value = response.json::<serde_json::Value>().await?;
+                parse_screener_tokens(json_value)
+            }
+            StatusCode::UNAUTHORIZED => Err(MarketDataError::AuthError(AuthError::Unauthorized)),
+            status => {
+                let error_text = response.text().await?;
+                Err(MarketDataError::ApiError(format!(
+                    "Search failed: {} - {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
     /// Gets batch price data for multiple tokens.
     ///
     /// # Arguments
@@ -520,13 +909,88 @@ impl MarketDataClient {
     ///
     /// Result<Vec<PriceData>, MarketDataError> - Price data for all requested tokens.
     pub async fn get_batch_prices(
-        &mut self,
+        &self,
         token_mints: &[String],
     ) -> Result<Vec<PriceData>, MarketDataError> {
         for mint in token_mints {
             self.validate_token_mint(mint)?;
         }
+        self.fetch_batch_prices(token_mints).await
+    }
+
+    /// Gets prices for any number of mints without failing the whole request.
+    ///
+    /// Mints are deduplicated and validated, then requested in chunks of `chunk_size`.
+    /// Invalid mints, failed chunks and mints the batch endpoint omits get their own error
+    /// entry. With `fallback` set, failed and omitted mints are retried one by one with
+    /// `get_token_price`. Use `BatchPriceFetcher` to run chunks concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_mints` - &[String] - Token mint addresses.
+    /// * `chunk_size` - usize - Maximum mints per batch request.
+    /// * `fallback` - bool - Whether to retry missing mints individually.
+    ///
+    /// # Returns
+    ///
+    /// BatchPrices - A result for every requested mint.
+    pub async fn get_batch_prices_partial(
+        &self,
+        token_mints: &[String],
+        chunk_size: usize,
+        fallback: bool,
+    ) -> BatchPrices {
+        let plan = BatchPlan::new(token_mints, chunk_size);
+        let mut prices = plan.invalid_results();
+        for chunk in &plan.chunks {
+            prices.extend(self.fetch_price_chunk(chunk, fallback, None).await);
+        }
+        BatchPrices { prices }
+    }
+
+    /// Fetches one chunk of already validated mints, falling back per token if enabled.
+    ///
+    /// The fallback is skipped, or stopped part way, after an authentication or rate limit
+    /// error, since retrying one mint at a time cannot succeed and only adds load.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - &[String] - Valid, unique mint addresses.
+    /// * `fallback` - bool - Whether to retry missing mints individually.
+    /// * `limiter` - Option<&RateLimiter> - Limiter to wait on before each fallback request.
+    ///
+    /// # Returns
+    ///
+    /// Vec<(String, Result<PriceData, MarketDataError>)> - A result for every mint in the chunk.
+    pub(crate) async fn fetch_price_chunk(
+        &self,
+        chunk: &[String],
+        fallback: bool,
+        limiter: Option<&RateLimiter>,
+    ) -> Vec<(String, Result<PriceData, MarketDataError>)> {
+        let batch = self.fetch_batch_prices(chunk).await;
+        let fallback = fallback && !batch.as_ref().is_err_and(stops_fallback);
+        let mut results = merge_chunk(chunk, batch);
+        if fallback {
+            for (mint, result) in results.iter_mut() {
+                if result.is_err() {
+                    if let Some(limiter) = limiter {
+                        limiter.wait_if_needed().await;
+                    }
+                    *result = self.get_token_price(mint).await;
+                    if result.as_ref().is_err_and(stops_fallback) {
+                        break;
+                    }
+                }
+            }
+        }
+        results
+    }
 
+    async fn fetch_batch_prices(
+        &self,
+        token_mints: &[String],
+    ) -> Result<Vec<PriceData>, MarketDataError> {
         let url = format!("{}/batch-prices", self.base_url);
         let payload = serde_json::json!({
             "mints": token_mints
@@ -534,6 +998,8 @@ impl MarketDataClient {
 
         let response = self
             .auth_client
+            .write()
+            .await
             .make_authenticated_request(reqwest::Method::POST, &url, Some(payload))
             .await?;
 
@@ -543,6 +1009,7 @@ impl MarketDataClient {
                 Ok(prices)
             }
             StatusCode::UNAUTHORIZED => Err(MarketDataError::AuthError(AuthError::Unauthorized)),
+            StatusCode::TOO_MANY_REQUESTS => Err(MarketDataError::RateLimited(response.text().await?)),
             StatusCode::BAD_REQUEST => {
                 let error_text = response.text().await?;
                 Err(MarketDataError::ApiError(format!("Bad request: {}", error_text)))
@@ -565,28 +1032,86 @@ impl MarketDataClient {
     ///
     /// # Returns
     ///
-    /// Result<(), MarketDataError> - Ok if valid, error otherwise.
-    fn validate_token_mint(&self, mint: &str) -> Result<(), MarketDataError> {
-        if mint.is_empty() {
-            return Err(MarketDataError::InvalidTokenMint(
-                "Token mint cannot be empty".to_string(),
-            ));
-        }
+    /// Result<Pubkey, MarketDataError> - The parsed mint if valid, error otherwise.
+    fn validate_token_mint(&self, mint: &str) -> Result<Pubkey, MarketDataError> {
+        mint.parse::<Pubkey>()
+            .map_err(|e| MarketDataError::InvalidTokenMint(format!("{}: {}", e, mint)))
+    }
+}
 
-        if mint.len() < 32 || mint.len() > 44 {
-            return Err(MarketDataError::InvalidTokenMint(format!(
-                "Invalid mint address length: {}",
-                mint
-            )));
-        }
+/// Parses a trending or search listing and ranks it in server order.
+///
+/// # Arguments
+///
+/// * `json_value` - serde_json::Value - The response body.
+///
+/// # Returns
+///
+/// Result<Vec<ScreenerToken>, MarketDataError> - The parsed tokens, without rows that could not
+/// be parsed; ranks keep each row's position in the response.
+fn parse_screener_tokens(json_value: serde_json::Value) -> Result<Vec<ScreenerToken>, MarketDataError> {
+    let serde_json::Value::Array(rows) = json_value else {
+        return Err(MarketDataError::ParsingError("Expected array response".to_string()));
+    };
+    // A row that still fails to parse is skipped rather than failing the whole listing
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, row)| {
+            let mut token = serde_json::from_value::<ScreenerToken>(row).ok()?;
+            token.rank = (i + 1) as u32;
+            Some(token)
+        })
+        .collect())
+}
 
-        if !mint.chars().all(|c| c.is_ascii_alphanumeric()) {
-            return Err(MarketDataError::InvalidTokenMint(format!(
-                "Invalid characters in mint address: {}",
-                mint
-            )));
-        }
+/// Builds a `/chart` request URL
+fn chart_url(
+    base_url: &str,
+    token_mint: &str,
+    timeframe: ChartTimeframe,
+    currency: Option<ChartCurrency>,
+    before: Option<i64>,
+    limit: Option<usize>,
+) -> String {
+    let mut url = format!("{}/chart/{}?timeframe={}", base_url, token_mint, timeframe.as_str());
+    if let Some(currency) = currency {
+        url = format!("{}&currency={}", url, currency.as_str());
+    }
+    if let Some(before) = before {
+        url = format!("{}&to={}", url, before);
+    }
+    if let Some(limit) = limit {
+        url = format!("{}&limit={}", url, limit);
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
-        Ok(())
+    #[test]
+    fn test_chart_url_requests_currency() {
+        let url = chart_url("https://api", "Mint", ChartTimeframe::OneMinute, Some(ChartCurrency::Sol), Some(60), Some(10));
+        assert_eq!(url, "https://api/chart/Mint?timeframe=1m&currency=SOL&to=60&limit=10");
+        let url = chart_url("https://api", "Mint", ChartTimeframe::OneHour, None, None, None);
+        assert_eq!(url, "https://api/chart/Mint?timeframe=1h");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_screener_listing_skips_bad_rows() {
+        let tokens = parse_screener_tokens(json!([
+            { "tokenAddress": "a", "createdAt": null, "protocol": null, "buyCount": 3.0 },
+            { "tokenAddress": "b", "supply": [1] },
+            { "tokenAddress": "c", "marketCapSol": "42.5" }
+        ]))
+        .unwrap();
+
+        let ranked: Vec<_> = tokens.iter().map(|t| (t.mint_address.as_str(), t.rank)).collect();
+        assert_eq!(ranked, vec![("a", 1), ("c", 3)]);
+        assert_eq!(tokens[1].market_cap_sol, 42.5);
+        assert!(parse_screener_tokens(json!({})).is_err());
+    }
+}
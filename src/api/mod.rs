@@ -8,4 +8,8 @@ pub mod social;
 pub mod notifications;
 pub mod hyperliquid;
 pub mod infrastructure;
-pub mod turnkey;
\ No newline at end of file
+pub mod turnkey;
+pub mod token_accounts;
+pub mod position_analytics;
+pub mod screener;
+pub mod batch_prices;
\ No newline at end of file
@@ -1,12 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 use reqwest::StatusCode;
 use serde_json::Value;
 use thiserror::Error;
 
 use crate::auth::{AuthClient, AuthError};
+use crate::models::solana::Pubkey;
 use crate::models::portfolio::{BatchBalanceRequest, BatchBalanceResponse, TokenBalance, WalletBalance};
 use crate::models::portfolio_v5::PortfolioV5Response;
+use crate::pricing::{PricingError, ReferenceAsset, ReferencePrices};
+use crate::wallets::{WalletBook, WalletBookError, WalletRollup};
 
 #[derive(Error, Debug)]
 pub enum PortfolioError {
@@ -24,10 +28,17 @@ pub enum PortfolioError {
 
     #[error("Parsing error: {0}")]
     ParsingError(String),
+
+    #[error("Wallet book error: {0}")]
+    WalletBook(#[from] WalletBookError),
+
+    #[error("Pricing error: {0}")]
+    Pricing(#[from] PricingError),
 }
 
 pub struct PortfolioClient {
     auth_client: AuthClient,
+    reference_prices: Option<Arc<ReferencePrices>>,
 }
 
 impl PortfolioClient {
@@ -41,9 +52,46 @@ impl PortfolioClient {
     pub fn new() -> Result<Self, PortfolioError> {
         Ok(Self {
             auth_client: AuthClient::new()?,
+            reference_prices: None,
         })
     }
 
+    ///
+    /// Uses a shared reference price service for SOL to USD conversions.
+    ///
+    /// Without one, or while it has no quote younger than its `max_age`, wallets the balance
+    /// endpoint returns without a USD value are left at zero and listed in
+    /// `BatchBalanceResponse::unpriced`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prices` - Arc<ReferencePrices> - The reference price service
+    ///
+    /// # Returns
+    ///
+    /// PortfolioClient - The client using the given service
+    ///
+    pub fn with_reference_prices(mut self, prices: Arc<ReferencePrices>) -> Self {
+        self.reference_prices = Some(prices);
+        self
+    }
+
+    ///
+    /// Gets the SOL/USD reference price, refreshing it once older than the service's `max_age`.
+    ///
+    /// # Returns
+    ///
+    /// Result<f64, PortfolioError> - USD per SOL, or a pricing error if no service was set
+    /// with `with_reference_prices` or no fresh quote could be fetched
+    ///
+    pub async fn sol_usd(&self) -> Result<f64, PortfolioError> {
+        let prices = self
+            .reference_prices
+            .as_ref()
+            .ok_or(PortfolioError::Pricing(PricingError::NotConfigured))?;
+        Ok(prices.price(ReferenceAsset::Sol).await?)
+    }
+
     ///
     /// Gets the balance for a single wallet address.
     ///
@@ -82,13 +130,12 @@ impl PortfolioClient {
         &mut self,
         wallet_addresses: &[String],
     ) -> Result<BatchBalanceResponse, PortfolioError> {
-        for address in wallet_addresses {
-            self.validate_wallet_address(address)?;
-        }
+        let public_keys = wallet_addresses
+            .iter()
+            .map(|address| self.validate_wallet_address(address))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let request = BatchBalanceRequest {
-            public_keys: wallet_addresses.to_vec(),
-        };
+        let request = BatchBalanceRequest { public_keys };
 
         self.auth_client.ensure_valid_authentication().await?;
         
@@ -109,7 +156,14 @@ impl PortfolioClient {
         match response.status() {
             StatusCode::OK => {
                 let data = response.json::<Value>().await?;
-                self.parse_batch_balance_response(data)
+                let mut batch = Self::parse_batch_balance_response(data)?;
+                // Without a SOL/USD price the wallets stay listed in `unpriced`
+                if !batch.unpriced.is_empty()
+                    && let Ok(sol_usd) = self.sol_usd().await
+                {
+                    Self::apply_sol_usd(&mut batch, sol_usd);
+                }
+                Ok(batch)
             }
             StatusCode::UNAUTHORIZED => Err(PortfolioError::AuthError(AuthError::Unauthorized)),
             StatusCode::BAD_REQUEST => {
@@ -188,32 +242,115 @@ impl PortfolioClient {
     }
 
     ///
-    /// Validates a Solana wallet address format.
+    /// Gets balances for every wallet in a wallet book group.
     ///
     /// # Arguments
     ///
-    /// * `address` - &str - The wallet address to validate
+    /// * `book` - &WalletBook - The wallet book to resolve the group from
+    /// * `group` - &str - Group name, `tag:<tag>`, wallet label or address
     ///
     /// # Returns
     ///
-    /// Result<(), PortfolioError> - Ok if valid, error otherwise
+    /// Result<BatchBalanceResponse, PortfolioError> - The batch balance response
     ///
-    fn validate_wallet_address(&self, address: &str) -> Result<(), PortfolioError> {
-        if address.len() < 32 || address.len() > 44 {
-            return Err(PortfolioError::InvalidWalletAddress(format!(
-                "Invalid address length: {}",
-                address
-            )));
-        }
+    pub async fn get_group_balances(
+        &mut self,
+        book: &WalletBook,
+        group: &str,
+    ) -> Result<BatchBalanceResponse, PortfolioError> {
+        let addresses = book.resolve_strings(group)?;
+        self.get_batch_balance(&addresses).await
+    }
+
+    ///
+    /// Gets the portfolio summary for every wallet in a wallet book group.
+    ///
+    /// # Arguments
+    ///
+    /// * `book` - &WalletBook - The wallet book to resolve the group from
+    /// * `group` - &str - Group name, `tag:<tag>`, wallet label or address
+    ///
+    /// # Returns
+    ///
+    /// Result<PortfolioV5Response, PortfolioError> - The portfolio summary
+    ///
+    pub async fn get_group_portfolio_summary(
+        &mut self,
+        book: &WalletBook,
+        group: &str,
+    ) -> Result<PortfolioV5Response, PortfolioError> {
+        let addresses = book.resolve_strings(group)?;
+        self.get_portfolio_summary(&addresses).await
+    }
+
+    ///
+    /// Fetches balances for every wallet in the book and rolls them up per group.
+    ///
+    /// # Arguments
+    ///
+    /// * `book` - &WalletBook - The wallet book
+    ///
+    /// # Returns
+    ///
+    /// Result<BTreeMap<String, WalletRollup>, PortfolioError> - Totals keyed by group name
+    ///
+    pub async fn get_group_totals(
+        &mut self,
+        book: &WalletBook,
+    ) -> Result<BTreeMap<String, WalletRollup>, PortfolioError> {
+        let balances = self.fetch_book_balances(book).await?;
+        Ok(book.group_totals(&balances))
+    }
+
+    ///
+    /// Fetches balances for every wallet in the book and rolls them up per tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `book` - &WalletBook - The wallet book
+    ///
+    /// # Returns
+    ///
+    /// Result<BTreeMap<String, WalletRollup>, PortfolioError> - Totals keyed by tag
+    ///
+    pub async fn get_tag_totals(
+        &mut self,
+        book: &WalletBook,
+    ) -> Result<BTreeMap<String, WalletRollup>, PortfolioError> {
+        let balances = self.fetch_book_balances(book).await?;
+        Ok(book.tag_totals(&balances))
+    }
 
-        if !address.chars().all(|c| c.is_ascii_alphanumeric()) {
-            return Err(PortfolioError::InvalidWalletAddress(format!(
-                "Invalid characters in address: {}",
-                address
-            )));
+    async fn fetch_book_balances(
+        &mut self,
+        book: &WalletBook,
+    ) -> Result<BatchBalanceResponse, PortfolioError> {
+        let addresses: Vec<String> = book.wallets().map(|w| w.address.to_string()).collect();
+        if addresses.is_empty() {
+            return Ok(BatchBalanceResponse {
+                balances: HashMap::new(),
+                timestamp: chrono::Utc::now().timestamp(),
+                unpriced: Vec::new(),
+            });
         }
+        self.get_batch_balance(&addresses).await
+    }
 
-        Ok(())
+    ///
+    /// Validates a Solana wallet address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &str - The wallet address to validate
+    ///
+    /// # Returns
+    ///
+    /// Result<Pubkey, PortfolioError> - The parsed address if valid, error otherwise
+    ///
+    fn validate_wallet_address(&self, address: &str) -> Result<Pubkey, PortfolioError> {
+        address
+            .parse::<Pubkey>()
+            .map_err(|e| PortfolioError::InvalidWalletAddress(format!("{}: {}", e, address)))
     }
 
     ///
@@ -225,13 +362,13 @@ impl PortfolioClient {
     ///
     /// # Returns
     ///
-    /// Result<BatchBalanceResponse, PortfolioError> - The parsed batch balance response
+    /// Result<BatchBalanceResponse, PortfolioError> - The parsed batch balance response, listing
+    /// wallets whose USD value was not reported in `unpriced`, or a parsing error if a token
+    /// balance is malformed
     ///
-    fn parse_batch_balance_response(
-        &self,
-        data: Value,
-    ) -> Result<BatchBalanceResponse, PortfolioError> {
+    fn parse_batch_balance_response(data: Value) -> Result<BatchBalanceResponse, PortfolioError> {
         let mut balances = HashMap::new();
+        let mut unpriced = Vec::new();
 
         if let Some(balances_obj) = data.as_object() {
             for (wallet_address, balance_data) in balances_obj {
@@ -245,18 +382,24 @@ impl PortfolioClient {
                     let mut token_balances = HashMap::new();
                     if let Some(tokens) = balance_data["tokens"].as_array() {
                         for token in tokens {
-                            if let Ok(token_balance) =
-                                serde_json::from_value::<TokenBalance>(token.clone())
-                            {
-                                token_balances
-                                    .insert(token_balance.mint_address.clone(), token_balance);
-                            }
+                            let token_balance = serde_json::from_value::<TokenBalance>(token.clone())
+                                .map_err(|e| {
+                                    PortfolioError::ParsingError(format!(
+                                        "Invalid token balance for wallet {}: {}",
+                                        wallet_address, e
+                                    ))
+                                })?;
+                            token_balances.insert(token_balance.mint_address.clone(), token_balance);
                         }
                     }
 
-                    let total_value_usd = balance_data["total_value_usd"]
-                        .as_f64()
-                        .unwrap_or(sol_balance * 100.0);
+                    let total_value_usd = match balance_data["total_value_usd"].as_f64() {
+                        Some(value) => value,
+                        None => {
+                            unpriced.push(wallet_address.clone());
+                            0.0
+                        }
+                    };
 
                     balances.insert(
                         wallet_address.clone(),
@@ -273,6 +416,99 @@ impl PortfolioClient {
         Ok(BatchBalanceResponse {
             balances,
             timestamp: chrono::Utc::now().timestamp(),
+            unpriced,
         })
     }
+
+    ///
+    /// Estimates the USD value of unpriced wallets from their SOL balance.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - &mut BatchBalanceResponse - The response whose `unpriced` wallets are valued
+    /// * `sol_usd` - f64 - USD per SOL
+    ///
+    fn apply_sol_usd(batch: &mut BatchBalanceResponse, sol_usd: f64) {
+        for address in batch.unpriced.drain(..) {
+            if let Some(balance) = batch.balances.get_mut(&address) {
+                balance.total_value_usd = balance.sol_balance * sol_usd;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{PriceSource, ReferencePriceConfig};
+    use serde_json::json;
+
+    #[test]
+    fn test_malformed_token_balance_is_an_error() {
+        let data = json!({
+            "wallet": {
+                "sol_balance": 1.5,
+                "tokens": [{ "mint_address": "mint", "amount": "not a number" }]
+            }
+        });
+
+        match PortfolioClient::parse_batch_balance_response(data) {
+            Err(PortfolioError::ParsingError(msg)) => assert!(msg.contains("wallet")),
+            other => panic!("expected a parsing error, got {:?}", other.map(|b| b.balances.len())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wallets_stay_unpriced_without_sol_usd() {
+        let data = json!({
+            "priced": { "sol_balance": 1.0, "token_balances": {}, "total_value_usd": 150.0 },
+            "unpriced": { "sol_balance": 2.0, "tokens": [] }
+        });
+        let mut batch = PortfolioClient::parse_batch_balance_response(data).unwrap();
+        assert_eq!(batch.unpriced, vec!["unpriced".to_string()]);
+
+        let client = PortfolioClient::new().unwrap();
+        assert!(matches!(
+            client.sol_usd().await,
+            Err(PortfolioError::Pricing(PricingError::NotConfigured))
+        ));
+        assert_eq!(batch.balances["unpriced"].total_value_usd, 0.0);
+
+        PortfolioClient::apply_sol_usd(&mut batch, 150.0);
+        assert!(batch.unpriced.is_empty());
+        assert_eq!(batch.balances["unpriced"].total_value_usd, 300.0);
+        assert_eq!(batch.balances["priced"].total_value_usd, 150.0);
+    }
+
+    /// Answers once, then fails like a feed that went down
+    struct FlakySource(std::sync::atomic::AtomicBool);
+
+    #[async_trait::async_trait]
+    impl PriceSource for FlakySource {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn fetch(&self, _asset: ReferenceAsset) -> Result<Option<f64>, PricingError> {
+            if self.0.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                Err(PricingError::Source("flaky".to_string(), "down".to_string()))
+            } else {
+                Ok(Some(150.0))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stale_sol_usd_is_not_used() {
+        let config = ReferencePriceConfig {
+            max_age: std::time::Duration::ZERO,
+            ..Default::default()
+        };
+        let prices = ReferencePrices::new(config).with_source(FlakySource(Default::default()));
+        let client = PortfolioClient::new().unwrap().with_reference_prices(Arc::new(prices));
+
+        assert_eq!(client.sol_usd().await.unwrap(), 150.0);
+        // The only quote is now older than max_age and the refresh fails
+        assert!(matches!(client.sol_usd().await, Err(PortfolioError::Pricing(_))));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::api::market_data::{MarketDataClient, MarketDataError};
+use crate::models::market::{Candle, ChartCurrency, ChartTimeframe, TokenChart};
+use crate::models::position_analytics::{
+    PositionAnalytics, PositionAnalyticsError, StrategyAnalytics, TradeRecord,
+};
+
+/// Upper bound on candles requested per chart page
+const MAX_CANDLES: i64 = 1_500;
+
+/// Upper bound on chart pages fetched to reach a trade's entry
+const MAX_PAGES: usize = 20;
+
+#[derive(Error, Debug)]
+pub enum AnalyticsError {
+    #[error("Market data error: {0}")]
+    MarketData(#[from] MarketDataError),
+
+    #[error("Invalid trade: {0}")]
+    InvalidTrade(#[from] PositionAnalyticsError),
+
+    #[error("Candle history for {token_mint} stops at {earliest}, before reaching the entry at {entry_time}; use a coarser timeframe")]
+    TruncatedWindow {
+        token_mint: String,
+        entry_time: i64,
+        earliest: i64,
+    },
+
+    #[error("Candles for {token_mint} are quoted in {currency:?}, but fills are priced in SOL")]
+    CandleCurrency {
+        token_mint: String,
+        currency: ChartCurrency,
+    },
+}
+
+/// Per-trade analytics together with per-strategy aggregates
+#[derive(Debug, Clone)]
+pub struct AnalyticsReport {
+    pub positions: Vec<PositionAnalytics>,
+    pub by_strategy: BTreeMap<String, StrategyAnalytics>,
+}
+
+pub struct PositionAnalyticsClient {
+    market_data: MarketDataClient,
+}
+
+impl PositionAnalyticsClient {
+    ///
+    /// Creates a new position analytics client.
+    ///
+    /// # Returns
+    ///
+    /// Result<PositionAnalyticsClient, AnalyticsError> - A new position analytics client instance
+    ///
+    pub fn new() -> Result<Self, AnalyticsError> {
+        Ok(Self {
+            market_data: MarketDataClient::new()?,
+        })
+    }
+
+    ///
+    /// Creates a position analytics client on top of an existing market data client.
+    ///
+    /// # Arguments
+    ///
+    /// * `market_data` - MarketDataClient - Client used to fetch candles
+    ///
+    /// # Returns
+    ///
+    /// PositionAnalyticsClient - A new position analytics client instance
+    ///
+    pub fn with_market_data(market_data: MarketDataClient) -> Self {
+        Self { market_data }
+    }
+
+    ///
+    /// Fetches candles covering a trade and computes its analytics.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - &TradeRecord - The trade's entry and exit fills
+    /// * `timeframe` - ChartTimeframe - Candle resolution for excursion tracking
+    ///
+    /// # Returns
+    ///
+    /// Result<PositionAnalytics, AnalyticsError> - The trade's analytics
+    ///
+    pub async fn analyze_trade(
+        &mut self,
+        trade: &TradeRecord,
+        timeframe: ChartTimeframe,
+    ) -> Result<PositionAnalytics, AnalyticsError> {
+        let entry_time = trade
+            .entry_time()
+            .ok_or_else(|| PositionAnalyticsError::NoEntry(trade.token_mint.clone()))?;
+        let now = chrono::Utc::now().timestamp();
+
+        // The chart endpoint returns the most recent candles, so page back until the entry is covered
+        let candle_secs = timeframe.as_secs();
+        let mut candles: Vec<Candle> = Vec::new();
+        let mut before = None;
+        for _ in 0..MAX_PAGES {
+            let limit = ((before.unwrap_or(now) - entry_time) / candle_secs + 2).clamp(1, MAX_CANDLES);
+            let chart = self
+                .market_data
+                .get_token_chart_before(
+                    &trade.token_mint,
+                    timeframe,
+                    Some(ChartCurrency::Sol),
+                    before,
+                    Some(limit as usize),
+                )
+                .await?;
+            let page = sol_candles(&trade.token_mint, chart)?;
+
+            let full_page = page.len() as i64 >= limit;
+            match merge_page(&mut candles, page, before, entry_time) {
+                PageOutcome::Older(oldest) if full_page => before = Some(oldest),
+                // A short or empty page means the token has no older history
+                PageOutcome::Covered | PageOutcome::Older(_) | PageOutcome::Exhausted => {
+                    return Ok(PositionAnalytics::compute(trade, &candles, candle_secs, now)?);
+                }
+                PageOutcome::Stalled => break,
+            }
+        }
+
+        Err(AnalyticsError::TruncatedWindow {
+            token_mint: trade.token_mint.clone(),
+            entry_time,
+            earliest: candles.first().map_or(now, |c| c.timestamp),
+        })
+    }
+
+    ///
+    /// Analyzes several trades and aggregates the results per strategy tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `trades` - &[TradeRecord] - Trades to analyze
+    /// * `timeframe` - ChartTimeframe - Candle resolution for excursion tracking
+    ///
+    /// # Returns
+    ///
+    /// Result<AnalyticsReport, AnalyticsError> - Per-trade and per-strategy analytics
+    ///
+    pub async fn analyze_trades(
+        &mut self,
+        trades: &[TradeRecord],
+        timeframe: ChartTimeframe,
+    ) -> Result<AnalyticsReport, AnalyticsError> {
+        let mut positions = Vec::with_capacity(trades.len());
+        for trade in trades {
+            positions.push(self.analyze_trade(trade, timeframe).await?);
+        }
+
+        let by_strategy = StrategyAnalytics::by_strategy(&positions);
+        Ok(AnalyticsReport {
+            positions,
+            by_strategy,
+        })
+    }
+}
+
+/// Takes the candles of a chart requested in SOL, rejecting a chart reported in another currency
+///
+/// Fill prices are in SOL, so excursions against USD candles would be off by the SOL price.
+/// A chart that does not report its currency is trusted to honour the request.
+fn sol_candles(token_mint: &str, chart: TokenChart) -> Result<Vec<Candle>, AnalyticsError> {
+    match chart.currency {
+        Some(ChartCurrency::Sol) | None => Ok(chart.candles),
+        Some(currency) => Err(AnalyticsError::CandleCurrency {
+            token_mint: token_mint.to_string(),
+            currency,
+        }),
+    }
+}
+
+/// Result of merging one chart page into the candles collected so far
+#[derive(Debug, PartialEq)]
+enum PageOutcome {
+    /// The candles now reach back to the entry
+    Covered,
+    /// The page added candles back to this start time
+    Older(i64),
+    /// The page was empty
+    Exhausted,
+    /// The page had no candles older than the cursor, so the cursor was not honoured
+    Stalled,
+}
+
+/// Merges a page of candles into `candles`, keeping them sorted and unique by start time
+///
+/// # Arguments
+///
+/// * `candles` - &mut Vec<Candle> - Candles collected so far
+/// * `page` - Vec<Candle> - The newly fetched page
+/// * `before` - Option<i64> - Cursor the page was requested with
+/// * `entry_time` - i64 - The trade's entry time
+///
+/// # Returns
+///
+/// PageOutcome - Whether the entry is covered or paging should continue
+fn merge_page(candles: &mut Vec<Candle>, page: Vec<Candle>, before: Option<i64>, entry_time: i64) -> PageOutcome {
+    if page.is_empty() {
+        return PageOutcome::Exhausted;
+    }
+
+    let mut older: Vec<Candle> = page
+        .into_iter()
+        .filter(|c| before.is_none_or(|b| c.timestamp < b))
+        .collect();
+    let Some(oldest) = older.iter().map(|c| c.timestamp).min() else {
+        return PageOutcome::Stalled;
+    };
+
+    older.append(candles);
+    older.sort_by_key(|c| c.timestamp);
+    older.dedup_by_key(|c| c.timestamp);
+    *candles = older;
+
+    if oldest <= entry_time {
+        PageOutcome::Covered
+    } else {
+        PageOutcome::Older(oldest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: i64) -> Candle {
+        Candle { timestamp, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume: 0.0 }
+    }
+
+    #[test]
+    fn test_merge_page_pages_back_and_detects_ignored_cursor() {
+        let mut candles = Vec::new();
+        let page = vec![candle(300), candle(360), candle(420)];
+        assert_eq!(merge_page(&mut candles, page, None, 100), PageOutcome::Older(300));
+
+        let page = vec![candle(180), candle(240), candle(300)];
+        assert_eq!(merge_page(&mut candles, page, Some(300), 100), PageOutcome::Older(180));
+
+        // A server that ignores the cursor returns the latest candles again
+        let page = vec![candle(300), candle(360), candle(420)];
+        assert_eq!(merge_page(&mut candles, page, Some(180), 100), PageOutcome::Stalled);
+
+        let page = vec![candle(60), candle(120)];
+        assert_eq!(merge_page(&mut candles, page, Some(180), 100), PageOutcome::Covered);
+        let times: Vec<i64> = candles.iter().map(|c| c.timestamp).collect();
+        assert_eq!(times, vec![60, 120, 180, 240, 300, 360, 420]);
+    }
+
+    #[test]
+    fn test_usd_candles_are_rejected() {
+        let chart = |currency| TokenChart {
+            mint_address: "Mint".to_string(),
+            timeframe: ChartTimeframe::OneMinute,
+            currency,
+            candles: vec![candle(60)],
+        };
+
+        assert_eq!(sol_candles("Mint", chart(Some(ChartCurrency::Sol))).unwrap(), vec![candle(60)]);
+        assert!(sol_candles("Mint", chart(None)).is_ok());
+        assert!(matches!(
+            sol_candles("Mint", chart(Some(ChartCurrency::Usd))),
+            Err(AnalyticsError::CandleCurrency { currency: ChartCurrency::Usd, .. })
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use chrono::Utc;
+use futures_util::Stream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::api::market_data::{MarketDataClient, MarketDataError};
+use crate::models::market::TimePeriod;
+use crate::models::screener::{diff_listings, ScreenerEvent, ScreenerQuery, ScreenerSource, ScreenerToken};
+
+/// Token screener over the trending and search endpoints.
+///
+/// The endpoints only accept a time period or a search query, so `ScreenerFilter` criteria and
+/// sorting are applied locally to the full typed listing.
+pub struct Screener {
+    client: MarketDataClient,
+}
+
+impl Screener {
+    /// Creates a screener with a default market data client
+    ///
+    /// # Returns
+    ///
+    /// Result<Screener, MarketDataError> - A new screener instance
+    pub fn new() -> Result<Self, MarketDataError> {
+        Ok(Self::with_client(MarketDataClient::new()?))
+    }
+
+    /// Creates a screener over an existing client, sharing its cache
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - MarketDataClient - Client used to fetch listings
+    ///
+    /// # Returns
+    ///
+    /// Screener - A new screener instance
+    pub fn with_client(client: MarketDataClient) -> Self {
+        Self { client }
+    }
+
+    /// Gets the trending listing with every field the endpoint returns
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - TimePeriod - Trending window
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<ScreenerToken>, MarketDataError> - Tokens in server order
+    pub async fn trending(&mut self, period: TimePeriod) -> Result<Vec<ScreenerToken>, MarketDataError> {
+        self.client.get_trending_screener_tokens(period).await
+    }
+
+    /// Searches tokens, returning every field the endpoint returns
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - &str - Search term
+    /// * `limit` - Option<usize> - Maximum results requested from the server
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<ScreenerToken>, MarketDataError> - Matches in server order
+    pub async fn search(&mut self, query: &str, limit: Option<usize>) -> Result<Vec<ScreenerToken>, MarketDataError> {
+        self.client.search_screener_tokens(query, limit).await
+    }
+
+    /// Runs a screen: fetches the source, then filters, sorts and truncates locally
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - &ScreenerQuery - The screen to run
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<ScreenerToken>, MarketDataError> - The ranked result
+    pub async fn screen(&mut self, query: &ScreenerQuery) -> Result<Vec<ScreenerToken>, MarketDataError> {
+        let tokens = match &query.source {
+            ScreenerSource::Trending(period) => self.trending(*period).await?,
+            ScreenerSource::Search { query, limit } => self.search(query, *limit).await?,
+        };
+        Ok(query.apply(tokens, Utc::now()))
+    }
+
+    /// Re-runs a screen periodically and emits listing changes.
+    ///
+    /// The first refresh emits an `Entered` event for every token. Failed refreshes emit
+    /// `Error` and keep the previous listing, so a transient failure does not look like every
+    /// token leaving.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - ScreenerQuery - The screen to run
+    /// * `every` - Duration - Refresh interval
+    /// * `buffer` - usize - Capacity of the event channel
+    ///
+    /// # Returns
+    ///
+    /// ScreenerWatch - Stream of events; dropping it stops refreshing
+    pub fn watch(mut self, query: ScreenerQuery, every: Duration, buffer: usize) -> ScreenerWatch {
+        let (tx, rx) = mpsc::channel(buffer.max(1));
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut listing: Vec<ScreenerToken> = Vec::new();
+
+            loop {
+                ticker.tick().await;
+                let events = match self.screen(&query).await {
+                    Ok(current) => {
+                        let mut events = diff_listings(&listing, &current);
+                        events.push(ScreenerEvent::Refreshed { tokens: current.clone() });
+                        listing = current;
+                        events
+                    }
+                    Err(e) => vec![ScreenerEvent::Error(e.to_string())],
+                };
+                for event in events {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        ScreenerWatch { rx, task }
+    }
+}
+
+/// Event stream from `Screener::watch`
+pub struct ScreenerWatch {
+    rx: mpsc::Receiver<ScreenerEvent>,
+    task: JoinHandle<()>,
+}
+
+impl ScreenerWatch {
+    /// Waits for the next event
+    ///
+    /// # Returns
+    ///
+    /// Option<ScreenerEvent> - The next event, or None if the watch stopped
+    pub async fn recv(&mut self) -> Option<ScreenerEvent> {
+        self.rx.recv().await
+    }
+}
+
+impl Stream for ScreenerWatch {
+    type Item = ScreenerEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<ScreenerEvent>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for ScreenerWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
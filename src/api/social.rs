@@ -1,6 +1,7 @@
 use crate::auth::AuthClient;
 use crate::errors::{AxiomError, Result};
 use crate::models::social::*;
+use crate::wallets::WalletBook;
 use serde_json::json;
 
 pub struct SocialClient {
@@ -76,6 +77,25 @@ impl SocialClient {
         }
     }
 
+    /// Get transactions for the wallets in a wallet book group.
+    ///
+    /// Args:
+    ///     book: &WalletBook - The wallet book to resolve the group from.
+    ///     group: &str - Group name, `tag:<tag>`, wallet label or address.
+    ///     request: TrackedWalletRequest - Filter criteria; `wallet_addresses` is replaced.
+    ///
+    /// Returns:
+    ///     Vec<TrackedTransaction>: List of transactions from the group's wallets.
+    pub async fn get_group_wallet_transactions(
+        &mut self,
+        book: &WalletBook,
+        group: &str,
+        mut request: TrackedWalletRequest,
+    ) -> Result<Vec<TrackedTransaction>> {
+        request.wallet_addresses = Some(book.resolve_strings(group)?);
+        self.get_tracked_wallet_transactions(request).await
+    }
+
     /// Get user's watchlist.
     ///
     /// Returns:
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use reqwest::StatusCode;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::auth::{AuthClient, AuthError};
+use crate::models::solana::{Lamports, Pubkey};
+use crate::models::token_accounts::{CleanupOptions, CleanupPlan, TokenAccount, WalletTokenAccounts};
+
+#[derive(Error, Debug)]
+pub enum TokenAccountsError {
+    #[error("Authentication error: {0}")]
+    AuthError(#[from] AuthError),
+
+    #[error("Network error: {0}")]
+    NetworkError(#[from] reqwest::Error),
+
+    #[error("Invalid wallet address: {0}")]
+    InvalidWalletAddress(String),
+
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    #[error("Parsing error: {0}")]
+    ParsingError(String),
+}
+
+pub struct TokenAccountsClient {
+    auth_client: AuthClient,
+}
+
+impl TokenAccountsClient {
+    ///
+    /// Creates a new token accounts client.
+    ///
+    /// # Returns
+    ///
+    /// Result<TokenAccountsClient, TokenAccountsError> - A new token accounts client instance
+    ///
+    pub fn new() -> Result<Self, TokenAccountsError> {
+        Ok(Self {
+            auth_client: AuthClient::new()?,
+        })
+    }
+
+    ///
+    /// Lists all SPL token accounts (Token and Token-2022) owned by a wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet_address` - &str - The Solana wallet address
+    ///
+    /// # Returns
+    ///
+    /// Result<WalletTokenAccounts, TokenAccountsError> - The wallet's token accounts
+    ///
+    pub async fn get_token_accounts(
+        &mut self,
+        wallet_address: &str,
+    ) -> Result<WalletTokenAccounts, TokenAccountsError> {
+        let owner = self.validate_wallet_address(wallet_address)?;
+        let mut accounts = self
+            .get_batch_token_accounts(&[wallet_address.to_string()])
+            .await?;
+
+        Ok(accounts.remove(&owner).unwrap_or(WalletTokenAccounts {
+            owner,
+            accounts: Vec::new(),
+        }))
+    }
+
+    ///
+    /// Lists SPL token accounts for multiple wallets in one request.
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet_addresses` - &[String] - Array of Solana wallet addresses
+    ///
+    /// # Returns
+    ///
+    /// Result<HashMap<Pubkey, WalletTokenAccounts>, TokenAccountsError> - Token accounts keyed by owner
+    ///
+    pub async fn get_batch_token_accounts(
+        &mut self,
+        wallet_addresses: &[String],
+    ) -> Result<HashMap<Pubkey, WalletTokenAccounts>, TokenAccountsError> {
+        let public_keys = wallet_addresses
+            .iter()
+            .map(|address| self.validate_wallet_address(address))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.auth_client.ensure_valid_authentication().await?;
+
+        let url = "https://axiom.trade/api/batched-wallet-token-accounts";
+        let request_body = serde_json::json!({ "publicKeys": public_keys });
+        let response = self
+            .auth_client
+            .make_authenticated_request(reqwest::Method::POST, url, Some(request_body))
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let data = response.json::<Value>().await?;
+                self.parse_token_accounts_response(data)
+            }
+            StatusCode::UNAUTHORIZED => Err(TokenAccountsError::AuthError(AuthError::Unauthorized)),
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(TokenAccountsError::ApiError(format!("Bad request: {}", error_text)))
+            }
+            status => {
+                let error_text = response.text().await?;
+                Err(TokenAccountsError::ApiError(format!(
+                    "Failed to get token accounts: {} - {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    ///
+    /// Fetches a wallet's token accounts and builds a batched close/burn plan.
+    ///
+    /// The plan only describes instructions; signing and sending is left to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet_address` - &str - The Solana wallet address
+    /// * `options` - &CleanupOptions - Dust threshold, exclusions and batch size
+    ///
+    /// # Returns
+    ///
+    /// Result<CleanupPlan, TokenAccountsError> - The cleanup plan
+    ///
+    pub async fn build_cleanup_plan(
+        &mut self,
+        wallet_address: &str,
+        options: &CleanupOptions,
+    ) -> Result<CleanupPlan, TokenAccountsError> {
+        let wallet = self.get_token_accounts(wallet_address).await?;
+        Ok(CleanupPlan::build(wallet.owner, &wallet.accounts, options))
+    }
+
+    ///
+    /// Estimates the SOL rent a cleanup would reclaim without building instructions.
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet_address` - &str - The Solana wallet address
+    /// * `options` - &CleanupOptions - Dust threshold and exclusions
+    ///
+    /// # Returns
+    ///
+    /// Result<Lamports, TokenAccountsError> - Rent that would be reclaimed
+    ///
+    pub async fn estimate_reclaimable_rent(
+        &mut self,
+        wallet_address: &str,
+        options: &CleanupOptions,
+    ) -> Result<Lamports, TokenAccountsError> {
+        Ok(self
+            .build_cleanup_plan(wallet_address, options)
+            .await?
+            .total_rent_reclaimed)
+    }
+
+    ///
+    /// Validates a Solana wallet address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &str - The wallet address to validate
+    ///
+    /// # Returns
+    ///
+    /// Result<Pubkey, TokenAccountsError> - The parsed address if valid, error otherwise
+    ///
+    fn validate_wallet_address(&self, address: &str) -> Result<Pubkey, TokenAccountsError> {
+        address
+            .parse::<Pubkey>()
+            .map_err(|e| TokenAccountsError::InvalidWalletAddress(format!("{}: {}", e, address)))
+    }
+
+    ///
+    /// Parses the batched token accounts response.
+    ///
+    /// The endpoint returns an object keyed by wallet address whose values are either an
+    /// array of accounts or an object with a `tokenAccounts` array.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Value - The JSON response from the API
+    ///
+    /// # Returns
+    ///
+    /// Result<HashMap<Pubkey, WalletTokenAccounts>, TokenAccountsError> - Token accounts keyed by owner
+    ///
+    fn parse_token_accounts_response(
+        &self,
+        data: Value,
+    ) -> Result<HashMap<Pubkey, WalletTokenAccounts>, TokenAccountsError> {
+        let wallets = data.as_object().ok_or_else(|| {
+            TokenAccountsError::ParsingError("Expected object keyed by wallet".to_string())
+        })?;
+
+        let mut result = HashMap::new();
+        for (wallet_address, value) in wallets {
+            let owner = self.validate_wallet_address(wallet_address)?;
+            let entries = value
+                .as_array()
+                .or_else(|| value["tokenAccounts"].as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            let accounts = entries
+                .into_iter()
+                .map(serde_json::from_value::<TokenAccount>)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    TokenAccountsError::ParsingError(format!(
+                        "Invalid token account for {}: {}",
+                        wallet_address, e
+                    ))
+                })?;
+
+            result.insert(owner, WalletTokenAccounts { owner, accounts });
+        }
+
+        Ok(result)
+    }
+}
@@ -1,4 +1,7 @@
 use crate::auth::{AuthClient, AuthError};
+use crate::models::solana::{Lamports, Pubkey, TokenAmount};
+use crate::risk::TokenRiskReport;
+use crate::wallets::{WalletBook, WalletBookError};
 use crate::models::trading::{
     BuyOrderRequest,
     OrderResponse,
@@ -38,6 +41,45 @@ pub enum TradingError {
 
     #[error("Parsing error: {0}")]
     ParsingError(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Wallet book error: {0}")]
+    WalletBook(#[from] WalletBookError),
+
+    #[error("Rejected by risk check: {0}")]
+    RiskRejected(String),
+}
+
+/// Per-wallet results of an order placed for a wallet book group
+#[derive(Debug, Default)]
+pub struct GroupOrders {
+    /// One entry per resolved wallet, in the group's order
+    pub orders: Vec<(Pubkey, Result<OrderResponse, TradingError>)>,
+}
+
+impl GroupOrders {
+    /// Orders that were accepted, by wallet
+    pub fn succeeded(&self) -> Vec<(&Pubkey, &OrderResponse)> {
+        self.orders
+            .iter()
+            .filter_map(|(wallet, result)| result.as_ref().ok().map(|order| (wallet, order)))
+            .collect()
+    }
+
+    /// Wallets whose order failed, with their errors
+    pub fn failed(&self) -> Vec<(&Pubkey, &TradingError)> {
+        self.orders
+            .iter()
+            .filter_map(|(wallet, result)| result.as_ref().err().map(|error| (wallet, error)))
+            .collect()
+    }
+
+    /// Returns true if every wallet's order was accepted
+    pub fn is_complete(&self) -> bool {
+        self.orders.iter().all(|(_, result)| result.is_ok())
+    }
 }
 
 pub struct TradingClient {
@@ -96,14 +138,94 @@ impl TradingClient {
         amount_sol: f64,
         slippage_percent: Option<f64>,
     ) -> Result<OrderResponse, TradingError> {
+        self.submit_buy(token_mint, amount_sol, slippage_percent, None).await
+    }
+
+    ///
+    /// Buys a token with SOL only if its risk report passed.
+    ///
+    /// # Arguments
+    /// * report: &TokenRiskReport - Risk report for the token being bought.
+    /// * token_mint: &str - The token mint address.
+    /// * amount_sol: f64 - Amount of SOL to spend.
+    /// * slippage_percent: Option<f64> - Slippage tolerance percentage.
+    ///
+    /// # Returns
+    /// * Result<OrderResponse, TradingError> - The order response, or RiskRejected.
+    ///
+    pub async fn buy_token_checked(
+        &mut self,
+        report: &TokenRiskReport,
+        token_mint: &str,
+        amount_sol: f64,
+        slippage_percent: Option<f64>,
+    ) -> Result<OrderResponse, TradingError> {
+        if report.mint_address != token_mint {
+            return Err(TradingError::RiskRejected(format!(
+                "report is for {}, not {}",
+                report.mint_address, token_mint
+            )));
+        }
+        if !report.passed {
+            return Err(TradingError::RiskRejected(report.summary()));
+        }
+        self.buy_token(token_mint, amount_sol, slippage_percent).await
+    }
+
+    ///
+    /// Buys a token with SOL from every wallet in a wallet book group.
+    ///
+    /// Each wallet gets its own order, so one wallet failing does not stop the others.
+    ///
+    /// # Arguments
+    /// * book: &WalletBook - The wallet book to resolve the group from.
+    /// * group: &str - Group name, `tag:<tag>`, wallet label or address.
+    /// * token_mint: &str - The token mint address.
+    /// * amount_sol: f64 - Amount of SOL to spend per wallet.
+    /// * slippage_percent: Option<f64> - Slippage tolerance percentage.
+    ///
+    /// # Returns
+    /// * Result<GroupOrders, TradingError> - The result of every wallet's order, or an error if
+    ///   the group, mint or amount is invalid.
+    ///
+    pub async fn buy_token_for_group(
+        &mut self,
+        book: &WalletBook,
+        group: &str,
+        token_mint: &str,
+        amount_sol: f64,
+        slippage_percent: Option<f64>,
+    ) -> Result<GroupOrders, TradingError> {
+        let wallets = book.resolve(group)?;
         self.validate_token_mint(token_mint)?;
         self.validate_amount(amount_sol, "SOL")?;
 
+        let mut results = GroupOrders::default();
+        for wallet in wallets {
+            let result = self.submit_buy(token_mint, amount_sol, slippage_percent, Some(wallet)).await;
+            results.orders.push((wallet, result));
+        }
+        Ok(results)
+    }
+
+    async fn submit_buy(
+        &mut self,
+        token_mint: &str,
+        amount_sol: f64,
+        slippage_percent: Option<f64>,
+        wallet_address: Option<Pubkey>,
+    ) -> Result<OrderResponse, TradingError> {
+        let token_mint = self.validate_token_mint(token_mint)?;
+        self.validate_amount(amount_sol, "SOL")?;
+        let amount_sol = Lamports::from_sol(amount_sol)
+            .map_err(|e| TradingError::InvalidAmount(e.to_string()))?;
+
         let request = BuyOrderRequest {
-            token_mint: token_mint.to_string(),
+            token_mint,
             amount_sol,
             slippage_percent: slippage_percent.unwrap_or(self.default_slippage),
             priority_fee: None,
+            wallet_address,
         };
 
         let url = format!("{}/batched-send-tx-v2", self.base_url);
@@ -126,7 +248,7 @@ impl TradingClient {
     ///
     /// # Arguments
     /// * token_mint: &str - The token mint address.
-    /// * amount_tokens: f64 - Amount of tokens to sell.
+    /// * amount_tokens: TokenAmount - Amount of tokens to sell, in the mint's decimals.
     /// * slippage_percent: Option<f64> - Slippage tolerance percentage.
     ///
     /// # Returns
@@ -135,17 +257,64 @@ impl TradingClient {
     pub async fn sell_token(
         &mut self,
         token_mint: &str,
-        amount_tokens: f64,
+        amount_tokens: TokenAmount,
         slippage_percent: Option<f64>,
     ) -> Result<OrderResponse, TradingError> {
+        self.submit_sell(token_mint, amount_tokens, slippage_percent, None).await
+    }
+
+    ///
+    /// Sells a token for SOL from every wallet in a wallet book group.
+    ///
+    /// Each wallet gets its own order, so one wallet failing does not stop the others.
+    ///
+    /// # Arguments
+    /// * book: &WalletBook - The wallet book to resolve the group from.
+    /// * group: &str - Group name, `tag:<tag>`, wallet label or address.
+    /// * token_mint: &str - The token mint address.
+    /// * amount_tokens: TokenAmount - Amount of tokens to sell per wallet, in the mint's decimals.
+    /// * slippage_percent: Option<f64> - Slippage tolerance percentage.
+    ///
+    /// # Returns
+    /// * Result<GroupOrders, TradingError> - The result of every wallet's order, or an error if
+    ///   the group, mint or amount is invalid.
+    ///
+    pub async fn sell_token_for_group(
+        &mut self,
+        book: &WalletBook,
+        group: &str,
+        token_mint: &str,
+        amount_tokens: TokenAmount,
+        slippage_percent: Option<f64>,
+    ) -> Result<GroupOrders, TradingError> {
+        let wallets = book.resolve(group)?;
         self.validate_token_mint(token_mint)?;
-        self.validate_amount(amount_tokens, "tokens")?;
+        self.validate_token_amount(amount_tokens)?;
+
+        let mut results = GroupOrders::default();
+        for wallet in wallets {
+            let result = self.submit_sell(token_mint, amount_tokens, slippage_percent, Some(wallet)).await;
+            results.orders.push((wallet, result));
+        }
+        Ok(results)
+    }
+
+    async fn submit_sell(
+        &mut self,
+        token_mint: &str,
+        amount_tokens: TokenAmount,
+        slippage_percent: Option<f64>,
+        wallet_address: Option<Pubkey>,
+    ) -> Result<OrderResponse, TradingError> {
+        let token_mint = self.validate_token_mint(token_mint)?;
+        self.validate_token_amount(amount_tokens)?;
 
         let request = SellOrderRequest {
-            token_mint: token_mint.to_string(),
+            token_mint,
             amount_tokens,
             slippage_percent: slippage_percent.unwrap_or(self.default_slippage),
             priority_fee: None,
+            wallet_address,
         };
 
         let url = format!("{}/batched-send-tx-v2", self.base_url);
@@ -169,7 +338,7 @@ impl TradingClient {
     /// # Arguments
     /// * from_mint: &str - The source token mint address.
     /// * to_mint: &str - The destination token mint address.
-    /// * amount: f64 - Amount of source tokens to swap.
+    /// * amount: TokenAmount - Amount of source tokens to swap, in the source mint's decimals.
     /// * slippage_percent: Option<f64> - Slippage tolerance percentage.
     ///
     /// # Returns
@@ -179,12 +348,12 @@ impl TradingClient {
         &mut self,
         from_mint: &str,
         to_mint: &str,
-        amount: f64,
+        amount: TokenAmount,
         slippage_percent: Option<f64>,
     ) -> Result<OrderResponse, TradingError> {
-        self.validate_token_mint(from_mint)?;
-        self.validate_token_mint(to_mint)?;
-        self.validate_amount(amount, "tokens")?;
+        let from_mint = self.validate_token_mint(from_mint)?;
+        let to_mint = self.validate_token_mint(to_mint)?;
+        self.validate_token_amount(amount)?;
 
         if from_mint == to_mint {
             return Err(TradingError::ApiError(
@@ -193,8 +362,8 @@ impl TradingClient {
         }
 
         let request = SwapOrderRequest {
-            from_mint: from_mint.to_string(),
-            to_mint: to_mint.to_string(),
+            from_mint,
+            to_mint,
             amount,
             slippage_percent: slippage_percent.unwrap_or(self.default_slippage),
             priority_fee: None,
@@ -221,7 +390,7 @@ impl TradingClient {
     /// # Arguments
     /// * input_mint: &str - The input token mint address.
     /// * output_mint: &str - The output token mint address.
-    /// * amount: f64 - Amount of input tokens.
+    /// * amount: TokenAmount - Amount of input tokens, in the input mint's decimals.
     /// * slippage_percent: Option<f64> - Slippage tolerance percentage.
     ///
     /// # Returns
@@ -231,16 +400,16 @@ impl TradingClient {
         &mut self,
         input_mint: &str,
         output_mint: &str,
-        amount: f64,
+        amount: TokenAmount,
         slippage_percent: Option<f64>,
     ) -> Result<QuoteResponse, TradingError> {
-        self.validate_token_mint(input_mint)?;
-        self.validate_token_mint(output_mint)?;
-        self.validate_amount(amount, "tokens")?;
+        let input_mint = self.validate_token_mint(input_mint)?;
+        let output_mint = self.validate_token_mint(output_mint)?;
+        self.validate_token_amount(amount)?;
 
         let request = QuoteRequest {
-            input_mint: input_mint.to_string(),
-            output_mint: output_mint.to_string(),
+            input_mint,
+            output_mint,
             amount,
             slippage_percent: slippage_percent.unwrap_or(self.default_slippage),
         };
@@ -336,7 +505,7 @@ impl TradingClient {
             max_sol_amount: 100.0,
             max_slippage_percent: 50.0,
             default_slippage_percent: 5.0,
-            priority_fee_lamports: 5000,
+            priority_fee_lamports: Lamports(5000),
         })
     }
 
@@ -397,30 +566,11 @@ impl TradingClient {
     /// * mint: &str - The token mint address.
     ///
     /// # Returns
-    /// * Result<(), TradingError> - Ok if valid, error otherwise.
+    /// * Result<Pubkey, TradingError> - The parsed mint if valid, error otherwise.
     ///
-    fn validate_token_mint(&self, mint: &str) -> Result<(), TradingError> {
-        if mint.is_empty() {
-            return Err(TradingError::InvalidTokenMint(
-                "Token mint cannot be empty".to_string(),
-            ));
-        }
-
-        if mint.len() < 32 || mint.len() > 44 {
-            return Err(TradingError::InvalidTokenMint(format!(
-                "Invalid mint address length: {}",
-                mint
-            )));
-        }
-
-        if !mint.chars().all(|c| c.is_ascii_alphanumeric()) {
-            return Err(TradingError::InvalidTokenMint(format!(
-                "Invalid characters in mint address: {}",
-                mint
-            )));
-        }
-
-        Ok(())
+    fn validate_token_mint(&self, mint: &str) -> Result<Pubkey, TradingError> {
+        mint.parse::<Pubkey>()
+            .map_err(|e| TradingError::InvalidTokenMint(format!("{}: {}", e, mint)))
     }
 
     ///
@@ -435,14 +585,14 @@ impl TradingClient {
     ///
     fn validate_amount(&self, amount: f64, unit: &str) -> Result<(), TradingError> {
         if amount <= 0.0 {
-            return Err(TradingError::ApiError(format!(
+            return Err(TradingError::InvalidAmount(format!(
                 "Amount must be positive, got {} {}",
                 amount, unit
             )));
         }
 
         if amount.is_nan() || amount.is_infinite() {
-            return Err(TradingError::ApiError(format!(
+            return Err(TradingError::InvalidAmount(format!(
                 "Invalid amount: {} {}",
                 amount, unit
             )));
@@ -450,4 +600,24 @@ impl TradingClient {
 
         Ok(())
     }
+
+    ///
+    /// Validates an exact token amount.
+    ///
+    /// # Arguments
+    /// * amount: TokenAmount - The amount to validate.
+    ///
+    /// # Returns
+    /// * Result<(), TradingError> - Ok if non-zero, error otherwise.
+    ///
+    fn validate_token_amount(&self, amount: TokenAmount) -> Result<(), TradingError> {
+        if amount.is_zero() {
+            return Err(TradingError::InvalidAmount(format!(
+                "Amount must be positive, got {} tokens",
+                amount
+            )));
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
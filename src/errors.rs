@@ -48,6 +48,9 @@ pub enum AxiomError {
     #[error("Notifications error: {0}")]
     Notifications(String),
     
+    #[error("Wallet book error: {0}")]
+    WalletBook(#[from] crate::wallets::WalletBookError),
+    
     #[error("Cryptographic error: {message}")]
     Crypto { message: String },
     
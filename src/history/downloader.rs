@@ -0,0 +1,369 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use crate::api::market_data::MarketDataClient;
+use crate::history::store::{HistoryStore, SeriesState};
+use crate::history::HistoryError;
+use crate::models::market::{Candle, ChartTimeframe, TimePeriod};
+use crate::utils::rate_limiter::RateLimiter;
+
+#[derive(Debug, Clone)]
+pub struct DownloadConfig {
+    /// Candles requested per page
+    pub page_size: usize,
+    /// Pages fetched per series per run; later runs resume where this one stopped
+    pub max_pages: usize,
+    /// Stop paging once history reaches this Unix timestamp (seconds)
+    pub stop_before: Option<i64>,
+    /// Requests allowed per `rate_window`
+    pub max_requests: usize,
+    pub rate_window: Duration,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            page_size: 1_000,
+            max_pages: 100,
+            stop_before: None,
+            max_requests: 5,
+            rate_window: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Outcome of one backfill run for a series
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesProgress {
+    pub mint: String,
+    pub timeframe: Option<ChartTimeframe>,
+    pub new_records: usize,
+    pub pages: usize,
+    pub state: SeriesState,
+}
+
+/// Backfills candle and price history into a `HistoryStore`, resuming from saved progress.
+///
+/// Each run first tops up the newest data, paging back until it meets the newest stored candle
+/// so no gap is left between runs, then pages backwards from the oldest stored candle until the
+/// API returns nothing older, `stop_before` is reached or `max_pages` is used up. Overlapping
+/// candles whose values changed (such as the bar that was still open last run) are re-written.
+/// Progress is saved after every page, so an interrupted run loses at most one page.
+pub struct HistoryDownloader {
+    client: MarketDataClient,
+    store: HistoryStore,
+    config: DownloadConfig,
+    limiter: RateLimiter,
+}
+
+impl HistoryDownloader {
+    /// Creates a downloader
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - MarketDataClient - Client used to fetch history
+    /// * `store` - HistoryStore - Destination store
+    /// * `config` - DownloadConfig - Paging and rate limit settings
+    ///
+    /// # Returns
+    ///
+    /// HistoryDownloader - A new downloader
+    pub fn new(client: MarketDataClient, store: HistoryStore, config: DownloadConfig) -> Self {
+        let limiter = RateLimiter::new(config.max_requests.max(1), config.rate_window);
+        Self {
+            client,
+            store,
+            config,
+            limiter,
+        }
+    }
+
+    pub fn store(&self) -> &HistoryStore {
+        &self.store
+    }
+
+    /// Backfills candles for every mint and timeframe
+    ///
+    /// # Arguments
+    ///
+    /// * `mints` - &[String] - Token mints to download
+    /// * `timeframes` - &[ChartTimeframe] - Timeframes to download for each mint
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<SeriesProgress>, HistoryError> - Progress for each series
+    pub async fn backfill(
+        &mut self,
+        mints: &[String],
+        timeframes: &[ChartTimeframe],
+    ) -> Result<Vec<SeriesProgress>, HistoryError> {
+        let mut progress = Vec::with_capacity(mints.len() * timeframes.len());
+        for mint in mints {
+            for timeframe in timeframes {
+                progress.push(self.backfill_candles(mint, *timeframe).await?);
+            }
+        }
+        Ok(progress)
+    }
+
+    /// Backfills candles for one mint and timeframe
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - &str - Token mint
+    /// * `timeframe` - ChartTimeframe - Candle timeframe
+    ///
+    /// # Returns
+    ///
+    /// Result<SeriesProgress, HistoryError> - What this run added
+    pub async fn backfill_candles(
+        &mut self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+    ) -> Result<SeriesProgress, HistoryError> {
+        let mut stored: BTreeMap<i64, Candle> = self
+            .store
+            .load_candles(mint, timeframe)?
+            .into_iter()
+            .map(|c| (c.timestamp, c))
+            .collect();
+        let mut state = self.store.load_candle_state(mint, timeframe)?;
+        let (mut new_records, mut pages) = (0, 0);
+
+        // Finish a gap left by an earlier run before topping up, so at most one gap is pending
+        let gap_closed = state.gap.is_none()
+            || self
+                .fill_gap(mint, timeframe, &mut stored, &mut state, &mut pages, &mut new_records)
+                .await?;
+        if gap_closed {
+            let stored_newest = stored.keys().next_back().copied();
+            let latest = self.fetch_page(mint, timeframe, None).await?;
+            pages += 1;
+            let latest_oldest = latest.iter().map(|c| c.timestamp).min();
+            let fresh = take_changed(latest, &mut stored, None);
+            new_records += self.save_page(mint, timeframe, &fresh, &stored, &mut state)?;
+
+            // The latest page does not reach back to what is stored, so page down to it
+            if let (Some(newest), Some(oldest)) = (stored_newest, latest_oldest)
+                && oldest > newest
+            {
+                state.gap = Some((newest, oldest));
+                self.fill_gap(mint, timeframe, &mut stored, &mut state, &mut pages, &mut new_records).await?;
+            }
+        }
+
+        while state.gap.is_none() && !state.complete && pages < self.config.max_pages {
+            let Some(cursor) = stored.keys().next().copied() else {
+                state.complete = true;
+                break;
+            };
+            if self.config.stop_before.is_some_and(|stop| cursor <= stop) {
+                state.complete = true;
+                break;
+            }
+
+            let page = self.fetch_page(mint, timeframe, Some(cursor)).await?;
+            pages += 1;
+            if page.is_empty() {
+                state.complete = true;
+                break;
+            }
+            ensure_cursor_honoured(mint, timeframe, &page, cursor)?;
+            let older = take_changed(page, &mut stored, Some(cursor));
+            new_records += self.save_page(mint, timeframe, &older, &stored, &mut state)?;
+        }
+        self.store.save_candle_state(mint, timeframe, &state)?;
+
+        Ok(SeriesProgress {
+            mint: mint.to_string(),
+            timeframe: Some(timeframe),
+            new_records,
+            pages,
+            state,
+        })
+    }
+
+    /// Pages backwards through `state.gap` until it closes or the page budget is used up
+    ///
+    /// # Returns
+    ///
+    /// Result<bool, HistoryError> - True once no gap is pending
+    async fn fill_gap(
+        &mut self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+        stored: &mut BTreeMap<i64, Candle>,
+        state: &mut SeriesState,
+        pages: &mut usize,
+        new_records: &mut usize,
+    ) -> Result<bool, HistoryError> {
+        while let Some((after, before)) = state.gap {
+            if *pages >= self.config.max_pages {
+                return Ok(false);
+            }
+
+            let page = self.fetch_page(mint, timeframe, Some(before)).await?;
+            *pages += 1;
+            if !page.is_empty() {
+                ensure_cursor_honoured(mint, timeframe, &page, before)?;
+            }
+            let oldest = page.iter().map(|c| c.timestamp).filter(|&t| t < before).min();
+            let fresh = take_changed(page, stored, Some(before));
+            // Reaching the stored side, or running out of history, closes the gap
+            state.gap = oldest.filter(|&oldest| oldest > after).map(|oldest| (after, oldest));
+            *new_records += self.save_page(mint, timeframe, &fresh, stored, state)?;
+        }
+        Ok(true)
+    }
+
+    /// Merges the current price feed into the store.
+    ///
+    /// The price feed endpoint has no cursor, so history accumulates across runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - &str - Token mint
+    /// * `period` - TimePeriod - Feed window to request
+    ///
+    /// # Returns
+    ///
+    /// Result<SeriesProgress, HistoryError> - What this run added
+    pub async fn update_price_feed(
+        &mut self,
+        mint: &str,
+        period: TimePeriod,
+    ) -> Result<SeriesProgress, HistoryError> {
+        let mut known: BTreeSet<i64> = self.store.load_prices(mint)?.iter().map(|p| p.timestamp).collect();
+
+        self.limiter.wait_if_needed().await;
+        let feed = self.client.get_price_feed(mint, period).await?;
+        let mut fresh: Vec<_> = feed
+            .prices
+            .into_iter()
+            .filter(|p| known.insert(p.timestamp))
+            .collect();
+        fresh.sort_by_key(|p| p.timestamp);
+        self.store.append_prices(mint, &fresh)?;
+
+        let mut state = self.store.load_price_state(mint)?;
+        update_state(&mut state, known.iter().copied());
+        self.store.save_price_state(mint, &state)?;
+
+        Ok(SeriesProgress {
+            mint: mint.to_string(),
+            timeframe: None,
+            new_records: fresh.len(),
+            pages: 1,
+            state,
+        })
+    }
+
+    async fn fetch_page(
+        &mut self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+        before: Option<i64>,
+    ) -> Result<Vec<Candle>, HistoryError> {
+        self.limiter.wait_if_needed().await;
+        let chart = self
+            .client
+            .get_token_chart_before(mint, timeframe, None, before, Some(self.config.page_size))
+            .await?;
+        Ok(chart.candles)
+    }
+
+    fn save_page(
+        &self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+        candles: &[Candle],
+        stored: &BTreeMap<i64, Candle>,
+        state: &mut SeriesState,
+    ) -> Result<usize, HistoryError> {
+        self.store.append_candles(mint, timeframe, candles)?;
+        update_state(state, stored.keys().copied());
+        self.store.save_candle_state(mint, timeframe, state)?;
+        Ok(candles.len())
+    }
+}
+
+/// Keeps candles that are new or whose values changed (and older than `before`, if set),
+/// sorted oldest first. The store keeps the last written record, so changed bars are re-appended.
+fn take_changed(page: Vec<Candle>, stored: &mut BTreeMap<i64, Candle>, before: Option<i64>) -> Vec<Candle> {
+    let mut fresh: Vec<Candle> = page
+        .into_iter()
+        .filter(|c| before.is_none_or(|cursor| c.timestamp < cursor))
+        .filter(|c| stored.get(&c.timestamp) != Some(c))
+        .collect();
+    fresh.sort_by_key(|c| c.timestamp);
+    fresh.dedup_by_key(|c| c.timestamp);
+    for candle in &fresh {
+        stored.insert(candle.timestamp, candle.clone());
+    }
+    fresh
+}
+
+/// Fails when a non-empty page has nothing older than the cursor it was requested with
+fn ensure_cursor_honoured(mint: &str, timeframe: ChartTimeframe, page: &[Candle], before: i64) -> Result<(), HistoryError> {
+    if page.iter().any(|c| c.timestamp < before) {
+        return Ok(());
+    }
+    Err(HistoryError::CursorIgnored {
+        mint: mint.to_string(),
+        timeframe: timeframe.as_str().to_string(),
+        before,
+    })
+}
+
+fn update_state(state: &mut SeriesState, mut timestamps: impl DoubleEndedIterator<Item = i64> + ExactSizeIterator) {
+    state.records = timestamps.len();
+    state.oldest = timestamps.next();
+    state.newest = timestamps.next_back().or(state.oldest);
+    state.updated_at = chrono::Utc::now().timestamp();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: i64) -> Candle {
+        Candle { timestamp, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume: 0.0 }
+    }
+
+    #[test]
+    fn test_take_changed_skips_known_and_newer_than_cursor() {
+        let mut stored: BTreeMap<i64, Candle> = [120, 180].into_iter().map(|t| (t, candle(t))).collect();
+        let page = vec![candle(180), candle(60), candle(0), candle(240), candle(60)];
+
+        let fresh = take_changed(page, &mut stored, Some(120));
+        assert_eq!(fresh.iter().map(|c| c.timestamp).collect::<Vec<_>>(), vec![0, 60]);
+        assert_eq!(stored.len(), 4);
+
+        let mut state = SeriesState::default();
+        update_state(&mut state, stored.keys().copied());
+        assert_eq!((state.oldest, state.newest, state.records), (Some(0), Some(180), 4));
+    }
+
+    #[test]
+    fn test_take_changed_rewrites_revised_bars() {
+        let mut stored: BTreeMap<i64, Candle> = [60, 120].into_iter().map(|t| (t, candle(t))).collect();
+        // The bar at 120 was still open when stored; the latest page has its final values
+        let mut closed = candle(120);
+        closed.close = 2.0;
+        let page = vec![candle(60), closed.clone(), candle(180)];
+
+        let fresh = take_changed(page, &mut stored, None);
+        assert_eq!(fresh, vec![closed.clone(), candle(180)]);
+        assert_eq!(stored[&120], closed);
+    }
+
+    #[test]
+    fn test_cursor_page_with_only_known_bars_is_an_error() {
+        let page = vec![candle(120), candle(180)];
+        assert!(matches!(
+            ensure_cursor_honoured("mint", ChartTimeframe::OneMinute, &page, 120),
+            Err(HistoryError::CursorIgnored { before: 120, .. })
+        ));
+        assert!(ensure_cursor_honoured("mint", ChartTimeframe::OneMinute, &[candle(60)], 120).is_ok());
+    }
+}
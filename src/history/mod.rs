@@ -0,0 +1,28 @@
+pub mod downloader;
+pub mod store;
+
+use thiserror::Error;
+
+use crate::api::market_data::MarketDataError;
+
+pub use downloader::{DownloadConfig, HistoryDownloader, SeriesProgress};
+pub use store::{HistoryStore, SeriesState};
+
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Market data error: {0}")]
+    MarketData(#[from] MarketDataError),
+
+    #[error("Chart endpoint ignored the paging cursor for {mint} ({timeframe}): nothing returned before {before}")]
+    CursorIgnored {
+        mint: String,
+        timeframe: String,
+        before: i64,
+    },
+}
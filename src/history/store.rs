@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::history::HistoryError;
+use crate::models::market::{Candle, ChartTimeframe, PricePoint};
+
+/// Backfill progress for one series, persisted so downloads can resume
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesState {
+    /// Oldest record timestamp stored so far
+    pub oldest: Option<i64>,
+    /// Newest record timestamp stored so far
+    pub newest: Option<i64>,
+    /// Set once paging backwards returned no older data
+    pub complete: bool,
+    /// Timestamps (exclusive) bounding candles not yet downloaded between two runs' data
+    #[serde(default)]
+    pub gap: Option<(i64, i64)>,
+    pub records: usize,
+    pub updated_at: i64,
+}
+
+/// Local JSONL store for candle and price history.
+///
+/// Layout: `<root>/<mint>/candles_<timeframe>.jsonl`, `<root>/<mint>/prices.jsonl` and a
+/// `.state.json` file next to each series. Files are append-only; reads return records sorted
+/// by timestamp with duplicates removed (the last written record wins).
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    root: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens a store rooted at `root`, creating the directory if needed
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - impl AsRef<Path> - Directory holding the history files
+    ///
+    /// # Returns
+    ///
+    /// Result<HistoryStore, HistoryError> - The opened store
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, HistoryError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root)?;
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn candles_path(&self, mint: &str, timeframe: ChartTimeframe) -> PathBuf {
+        self.root.join(mint).join(format!("candles_{}.jsonl", timeframe.as_str()))
+    }
+
+    pub fn prices_path(&self, mint: &str) -> PathBuf {
+        self.root.join(mint).join("prices.jsonl")
+    }
+
+    fn state_path(series_path: &Path) -> PathBuf {
+        series_path.with_extension("state.json")
+    }
+
+    /// Loads stored candles, sorted and deduplicated
+    pub fn load_candles(&self, mint: &str, timeframe: ChartTimeframe) -> Result<Vec<Candle>, HistoryError> {
+        let records: Vec<Candle> = read_jsonl(&self.candles_path(mint, timeframe))?;
+        Ok(dedupe(records, |c| c.timestamp))
+    }
+
+    /// Appends candles; callers should skip timestamps already stored
+    pub fn append_candles(
+        &self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+        candles: &[Candle],
+    ) -> Result<(), HistoryError> {
+        append_jsonl(&self.candles_path(mint, timeframe), candles)
+    }
+
+    /// Loads stored price points, sorted and deduplicated
+    pub fn load_prices(&self, mint: &str) -> Result<Vec<PricePoint>, HistoryError> {
+        let records: Vec<PricePoint> = read_jsonl(&self.prices_path(mint))?;
+        Ok(dedupe(records, |p| p.timestamp))
+    }
+
+    /// Appends price points; callers should skip timestamps already stored
+    pub fn append_prices(&self, mint: &str, points: &[PricePoint]) -> Result<(), HistoryError> {
+        append_jsonl(&self.prices_path(mint), points)
+    }
+
+    pub fn load_candle_state(&self, mint: &str, timeframe: ChartTimeframe) -> Result<SeriesState, HistoryError> {
+        read_state(&Self::state_path(&self.candles_path(mint, timeframe)))
+    }
+
+    pub fn save_candle_state(
+        &self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+        state: &SeriesState,
+    ) -> Result<(), HistoryError> {
+        write_state(&Self::state_path(&self.candles_path(mint, timeframe)), state)
+    }
+
+    pub fn load_price_state(&self, mint: &str) -> Result<SeriesState, HistoryError> {
+        read_state(&Self::state_path(&self.prices_path(mint)))
+    }
+
+    pub fn save_price_state(&self, mint: &str, state: &SeriesState) -> Result<(), HistoryError> {
+        write_state(&Self::state_path(&self.prices_path(mint)), state)
+    }
+
+    /// Rewrites a candle file sorted and without duplicates
+    ///
+    /// # Returns
+    ///
+    /// Result<usize, HistoryError> - Number of candles kept
+    pub fn compact_candles(&self, mint: &str, timeframe: ChartTimeframe) -> Result<usize, HistoryError> {
+        let candles = self.load_candles(mint, timeframe)?;
+        rewrite_jsonl(&self.candles_path(mint, timeframe), &candles)?;
+        Ok(candles.len())
+    }
+
+    /// Exports stored candles as CSV (`timestamp,open,high,low,close,volume`)
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - &str - Token mint
+    /// * `timeframe` - ChartTimeframe - Candle timeframe
+    /// * `path` - impl AsRef<Path> - Destination CSV file
+    ///
+    /// # Returns
+    ///
+    /// Result<usize, HistoryError> - Number of rows written
+    pub fn export_candles_csv(
+        &self,
+        mint: &str,
+        timeframe: ChartTimeframe,
+        path: impl AsRef<Path>,
+    ) -> Result<usize, HistoryError> {
+        let candles = self.load_candles(mint, timeframe)?;
+        let mut writer = BufWriter::new(create_file(path.as_ref())?);
+        writeln!(writer, "timestamp,open,high,low,close,volume")?;
+        for c in &candles {
+            writeln!(writer, "{},{},{},{},{},{}", c.timestamp, c.open, c.high, c.low, c.close, c.volume)?;
+        }
+        writer.flush()?;
+        Ok(candles.len())
+    }
+}
+
+fn create_file(path: &Path) -> Result<File, HistoryError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(File::create(path)?)
+}
+
+fn read_jsonl<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, HistoryError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let reader = BufReader::new(File::open(path)?);
+    let mut records = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // A torn final line from an interrupted write is skipped rather than failing the load
+        match serde_json::from_str(&line) {
+            Ok(record) => records.push(record),
+            Err(e) if e.is_eof() => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(records)
+}
+
+fn append_jsonl<T: Serialize>(path: &Path, records: &[T]) -> Result<(), HistoryError> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let ends_with_newline = last_byte(path)?.is_none_or(|b| b == b'\n');
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    if !ends_with_newline {
+        // Terminate a torn line left by an interrupted write so new records stay parseable
+        writer.write_all(b"\n")?;
+    }
+    for record in records {
+        serde_json::to_writer(&mut writer, record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn last_byte(path: &Path) -> Result<Option<u8>, HistoryError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+    let mut byte = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut byte)?;
+    Ok(Some(byte[0]))
+}
+
+fn rewrite_jsonl<T: Serialize>(path: &Path, records: &[T]) -> Result<(), HistoryError> {
+    let tmp = path.with_extension("jsonl.tmp");
+    {
+        let mut writer = BufWriter::new(create_file(&tmp)?);
+        for record in records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+    }
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+fn read_state(path: &Path) -> Result<SeriesState, HistoryError> {
+    if !path.exists() {
+        return Ok(SeriesState::default());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn write_state(path: &Path, state: &SeriesState) -> Result<(), HistoryError> {
+    // Write then rename so an interruption never leaves a half-written state file
+    let tmp = path.with_extension("tmp");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&tmp, serde_json::to_string_pretty(state)?)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+fn dedupe<T, F>(records: Vec<T>, timestamp: F) -> Vec<T>
+where
+    F: Fn(&T) -> i64,
+{
+    let mut by_timestamp = BTreeMap::new();
+    for record in records {
+        by_timestamp.insert(timestamp(&record), record);
+    }
+    by_timestamp.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: i64, close: f64) -> Candle {
+        Candle { timestamp, open: close, high: close, low: close, close, volume: 1.0 }
+    }
+
+    fn temp_store(name: &str) -> HistoryStore {
+        let root = std::env::temp_dir().join(format!("axiom_history_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        HistoryStore::open(root).unwrap()
+    }
+
+    #[test]
+    fn test_append_load_dedupes_and_sorts() {
+        let store = temp_store("dedupe");
+        let tf = ChartTimeframe::OneMinute;
+        store.append_candles("mint", tf, &[candle(120, 1.0), candle(60, 1.0)]).unwrap();
+        store.append_candles("mint", tf, &[candle(120, 2.0), candle(0, 1.0)]).unwrap();
+
+        let candles = store.load_candles("mint", tf).unwrap();
+        assert_eq!(candles.iter().map(|c| c.timestamp).collect::<Vec<_>>(), vec![0, 60, 120]);
+        assert_eq!(candles[2].close, 2.0);
+
+        assert_eq!(store.compact_candles("mint", tf).unwrap(), 3);
+        let csv_path = store.root().join("out.csv");
+        assert_eq!(store.export_candles_csv("mint", tf, &csv_path).unwrap(), 3);
+        assert_eq!(fs::read_to_string(&csv_path).unwrap().lines().count(), 4);
+        let _ = fs::remove_dir_all(store.root());
+    }
+
+    #[test]
+    fn test_state_roundtrip_and_torn_line() {
+        let store = temp_store("state");
+        let tf = ChartTimeframe::OneHour;
+        assert_eq!(store.load_candle_state("mint", tf).unwrap(), SeriesState::default());
+
+        let state = SeriesState {
+            oldest: Some(0),
+            newest: Some(3600),
+            complete: false,
+            gap: Some((0, 3600)),
+            records: 2,
+            updated_at: 1,
+        };
+        store.save_candle_state("mint", tf, &state).unwrap();
+        assert_eq!(store.load_candle_state("mint", tf).unwrap(), state);
+
+        store.append_candles("mint", tf, &[candle(0, 1.0)]).unwrap();
+        let mut file = OpenOptions::new().append(true).open(store.candles_path("mint", tf)).unwrap();
+        file.write_all(b"{\"timestamp\":3600,\"op").unwrap();
+        assert_eq!(store.load_candles("mint", tf).unwrap().len(), 1);
+
+        store.append_candles("mint", tf, &[candle(7200, 1.0)]).unwrap();
+        assert_eq!(store.load_candles("mint", tf).unwrap().len(), 2);
+        let _ = fs::remove_dir_all(store.root());
+    }
+}
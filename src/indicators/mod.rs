@@ -0,0 +1,96 @@
+pub mod momentum;
+pub mod moving_average;
+pub mod volatility;
+pub mod volume;
+
+pub use momentum::{Macd, MacdValue, Rsi};
+pub use moving_average::{Ema, Sma};
+pub use volatility::{Atr, BollingerBands, BollingerValue};
+pub use volume::{Obv, Vwap};
+
+use crate::models::hyperliquid;
+use crate::models::market;
+
+/// Read access to an OHLCV bar, implemented for both Axiom and Hyperliquid candles
+pub trait Ohlcv {
+    fn open(&self) -> f64;
+    fn high(&self) -> f64;
+    fn low(&self) -> f64;
+    fn close(&self) -> f64;
+    fn volume(&self) -> f64;
+
+    /// (high + low + close) / 3
+    fn typical_price(&self) -> f64 {
+        (self.high() + self.low() + self.close()) / 3.0
+    }
+}
+
+impl Ohlcv for market::Candle {
+    fn open(&self) -> f64 {
+        self.open
+    }
+
+    fn high(&self) -> f64 {
+        self.high
+    }
+
+    fn low(&self) -> f64 {
+        self.low
+    }
+
+    fn close(&self) -> f64 {
+        self.close
+    }
+
+    fn volume(&self) -> f64 {
+        self.volume
+    }
+}
+
+impl Ohlcv for hyperliquid::Candle {
+    fn open(&self) -> f64 {
+        self.open.to_f64()
+    }
+
+    fn high(&self) -> f64 {
+        self.high.to_f64()
+    }
+
+    fn low(&self) -> f64 {
+        self.low.to_f64()
+    }
+
+    fn close(&self) -> f64 {
+        self.close.to_f64()
+    }
+
+    fn volume(&self) -> f64 {
+        self.volume.to_f64()
+    }
+}
+
+/// A streaming indicator fed one candle at a time.
+///
+/// `next` returns `None` until enough candles have been seen to produce a value.
+pub trait Indicator {
+    type Output;
+
+    /// Feeds the next candle and returns the updated value
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<Self::Output>;
+
+    /// Clears all state, as if no candles had been seen
+    fn reset(&mut self);
+
+    /// Runs the indicator over a candle series, one output per candle
+    ///
+    /// # Arguments
+    ///
+    /// * `candles` - &[C] - Candles in chronological order
+    ///
+    /// # Returns
+    ///
+    /// Vec<Option<Self::Output>> - Indicator values aligned with `candles`
+    fn batch<C: Ohlcv>(&mut self, candles: &[C]) -> Vec<Option<Self::Output>> {
+        candles.iter().map(|candle| self.next(candle)).collect()
+    }
+}
@@ -0,0 +1,190 @@
+use super::moving_average::Ema;
+use super::{Indicator, Ohlcv};
+
+/// Relative strength index using Wilder's smoothing
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    period: usize,
+    previous: Option<f64>,
+    seen: usize,
+    avg_gain: f64,
+    avg_loss: f64,
+}
+
+impl Rsi {
+    /// Creates an RSI
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - usize - Smoothing period, commonly 14; panics if zero
+    ///
+    /// # Returns
+    ///
+    /// Rsi - A new, empty RSI
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "RSI period must be positive");
+        Self {
+            period,
+            previous: None,
+            seen: 0,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+        }
+    }
+
+    /// Feeds the next value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - f64 - The next value in the series
+    ///
+    /// # Returns
+    ///
+    /// Option<f64> - RSI between 0 and 100 once `period` changes have been seen
+    pub fn next_value(&mut self, value: f64) -> Option<f64> {
+        let previous = self.previous.replace(value)?;
+        let change = value - previous;
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+        let period = self.period as f64;
+
+        self.seen += 1;
+        if self.seen <= self.period {
+            // Simple average over the first `period` changes
+            self.avg_gain += gain / period;
+            self.avg_loss += loss / period;
+            if self.seen < self.period {
+                return None;
+            }
+        } else {
+            self.avg_gain = (self.avg_gain * (period - 1.0) + gain) / period;
+            self.avg_loss = (self.avg_loss * (period - 1.0) + loss) / period;
+        }
+
+        if self.avg_loss == 0.0 {
+            return Some(if self.avg_gain == 0.0 { 50.0 } else { 100.0 });
+        }
+        let rs = self.avg_gain / self.avg_loss;
+        Some(100.0 - 100.0 / (1.0 + rs))
+    }
+}
+
+impl Indicator for Rsi {
+    type Output = f64;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<f64> {
+        self.next_value(candle.close())
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdValue {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// Moving average convergence/divergence
+#[derive(Debug, Clone)]
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+}
+
+impl Default for Macd {
+    /// The standard 12 / 26 / 9 configuration
+    fn default() -> Self {
+        Self::new(12, 26, 9)
+    }
+}
+
+impl Macd {
+    /// Creates a MACD
+    ///
+    /// # Arguments
+    ///
+    /// * `fast` - usize - Fast EMA period
+    /// * `slow` - usize - Slow EMA period
+    /// * `signal` - usize - Signal line EMA period
+    ///
+    /// # Returns
+    ///
+    /// Macd - A new, empty MACD
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        Self {
+            fast: Ema::new(fast),
+            slow: Ema::new(slow),
+            signal: Ema::new(signal),
+        }
+    }
+
+    /// Feeds the next value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - f64 - The next value in the series
+    ///
+    /// # Returns
+    ///
+    /// Option<MacdValue> - MACD, signal and histogram once the signal line is ready
+    pub fn next_value(&mut self, value: f64) -> Option<MacdValue> {
+        let fast = self.fast.next_value(value);
+        let slow = self.slow.next_value(value);
+        let macd = fast? - slow?;
+        let signal = self.signal.next_value(macd)?;
+        Some(MacdValue {
+            macd,
+            signal,
+            histogram: macd - signal,
+        })
+    }
+}
+
+impl Indicator for Macd {
+    type Output = MacdValue;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<MacdValue> {
+        self.next_value(candle.close())
+    }
+
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_bounds() {
+        let mut rising = Rsi::new(3);
+        let values: Vec<_> = (1..=5).map(|v| rising.next_value(v as f64)).collect();
+        assert_eq!(values, vec![None, None, None, Some(100.0), Some(100.0)]);
+
+        let mut mixed = Rsi::new(2);
+        mixed.next_value(10.0);
+        mixed.next_value(12.0);
+        assert_eq!(mixed.next_value(10.0), Some(50.0));
+    }
+
+    #[test]
+    fn test_macd_warmup_and_histogram() {
+        let mut macd = Macd::new(2, 3, 2);
+        let outputs: Vec<_> = [1.0, 2.0, 3.0, 4.0, 5.0]
+            .iter()
+            .map(|&v| macd.next_value(v))
+            .collect();
+
+        assert!(outputs[..3].iter().all(Option::is_none));
+        let last = outputs[4].unwrap();
+        assert!((last.histogram - (last.macd - last.signal)).abs() < 1e-12);
+        assert!(last.macd > 0.0);
+    }
+}
@@ -0,0 +1,182 @@
+use super::{Indicator, Ohlcv};
+use std::collections::VecDeque;
+
+/// Simple moving average of closes
+#[derive(Debug, Clone)]
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Sma {
+    /// Creates a simple moving average
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - usize - Number of values averaged; panics if zero
+    ///
+    /// # Returns
+    ///
+    /// Sma - A new, empty moving average
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "SMA period must be positive");
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+        }
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Feeds the next value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - f64 - The next value in the series
+    ///
+    /// # Returns
+    ///
+    /// Option<f64> - The average once `period` values have been seen
+    pub fn next_value(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+        self.sum += value;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or_default();
+        }
+        self.value()
+    }
+
+    /// Gets the current average without feeding a value
+    pub fn value(&self) -> Option<f64> {
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+
+    pub(crate) fn window(&self) -> &VecDeque<f64> {
+        &self.window
+    }
+}
+
+impl Indicator for Sma {
+    type Output = f64;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<f64> {
+        self.next_value(candle.close())
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+    }
+}
+
+/// Exponential moving average of closes, seeded with the SMA of the first `period` values
+#[derive(Debug, Clone)]
+pub struct Ema {
+    period: usize,
+    alpha: f64,
+    seed: Sma,
+    current: Option<f64>,
+}
+
+impl Ema {
+    /// Creates an exponential moving average with smoothing 2 / (period + 1)
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - usize - Smoothing period; panics if zero
+    ///
+    /// # Returns
+    ///
+    /// Ema - A new, empty moving average
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "EMA period must be positive");
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            seed: Sma::new(period),
+            current: None,
+        }
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Feeds the next value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - f64 - The next value in the series
+    ///
+    /// # Returns
+    ///
+    /// Option<f64> - The average once `period` values have been seen
+    pub fn next_value(&mut self, value: f64) -> Option<f64> {
+        self.current = match self.current {
+            Some(previous) => Some(previous + self.alpha * (value - previous)),
+            None => self.seed.next_value(value),
+        };
+        self.current
+    }
+
+    /// Gets the current average without feeding a value
+    pub fn value(&self) -> Option<f64> {
+        self.current
+    }
+}
+
+impl Indicator for Ema {
+    type Output = f64;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<f64> {
+        self.next_value(candle.close())
+    }
+
+    fn reset(&mut self) {
+        self.seed.reset();
+        self.current = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Candle;
+
+    fn closes(values: &[f64]) -> Vec<Candle> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                timestamp: i as i64,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sma_window() {
+        let values = Sma::new(3).batch(&closes(&[1.0, 2.0, 3.0, 4.0, 5.0]));
+        assert_eq!(values, vec![None, None, Some(2.0), Some(3.0), Some(4.0)]);
+    }
+
+    #[test]
+    fn test_ema_seeded_with_sma() {
+        let mut ema = Ema::new(3);
+        assert_eq!(ema.next_value(1.0), None);
+        assert_eq!(ema.next_value(2.0), None);
+        assert_eq!(ema.next_value(3.0), Some(2.0));
+        assert_eq!(ema.next_value(6.0), Some(4.0));
+
+        ema.reset();
+        assert_eq!(ema.value(), None);
+    }
+}
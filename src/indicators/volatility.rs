@@ -0,0 +1,174 @@
+use super::moving_average::Sma;
+use super::{Indicator, Ohlcv};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BollingerValue {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+    /// (upper - lower) / middle
+    pub bandwidth: f64,
+}
+
+/// Bollinger bands: SMA of closes plus and minus `multiplier` population standard deviations
+#[derive(Debug, Clone)]
+pub struct BollingerBands {
+    sma: Sma,
+    multiplier: f64,
+}
+
+impl Default for BollingerBands {
+    /// The standard 20 period, 2 standard deviation configuration
+    fn default() -> Self {
+        Self::new(20, 2.0)
+    }
+}
+
+impl BollingerBands {
+    /// Creates Bollinger bands
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - usize - Moving average period; panics if zero
+    /// * `multiplier` - f64 - Band width in standard deviations
+    ///
+    /// # Returns
+    ///
+    /// BollingerBands - New, empty bands
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            sma: Sma::new(period),
+            multiplier,
+        }
+    }
+
+    /// Feeds the next value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - f64 - The next value in the series
+    ///
+    /// # Returns
+    ///
+    /// Option<BollingerValue> - The bands once `period` values have been seen
+    pub fn next_value(&mut self, value: f64) -> Option<BollingerValue> {
+        let middle = self.sma.next_value(value)?;
+        let window = self.sma.window();
+        let variance = window.iter().map(|v| (v - middle).powi(2)).sum::<f64>() / window.len() as f64;
+        let offset = self.multiplier * variance.sqrt();
+
+        Some(BollingerValue {
+            upper: middle + offset,
+            middle,
+            lower: middle - offset,
+            bandwidth: if middle != 0.0 { 2.0 * offset / middle } else { 0.0 },
+        })
+    }
+}
+
+impl Indicator for BollingerBands {
+    type Output = BollingerValue;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<BollingerValue> {
+        self.next_value(candle.close())
+    }
+
+    fn reset(&mut self) {
+        self.sma.reset();
+    }
+}
+
+/// Average true range using Wilder's smoothing
+#[derive(Debug, Clone)]
+pub struct Atr {
+    period: usize,
+    previous_close: Option<f64>,
+    seed: Sma,
+    current: Option<f64>,
+}
+
+impl Atr {
+    /// Creates an ATR
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - usize - Smoothing period, commonly 14; panics if zero
+    ///
+    /// # Returns
+    ///
+    /// Atr - A new, empty ATR
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            previous_close: None,
+            seed: Sma::new(period),
+            current: None,
+        }
+    }
+
+    /// Feeds the next bar
+    ///
+    /// # Arguments
+    ///
+    /// * `high` - f64 - Bar high
+    /// * `low` - f64 - Bar low
+    /// * `close` - f64 - Bar close
+    ///
+    /// # Returns
+    ///
+    /// Option<f64> - The ATR once `period` bars have been seen
+    pub fn next_bar(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let true_range = match self.previous_close.replace(close) {
+            Some(previous) => (high - low).max((high - previous).abs()).max((low - previous).abs()),
+            None => high - low,
+        };
+
+        let period = self.period as f64;
+        self.current = match self.current {
+            Some(atr) => Some((atr * (period - 1.0) + true_range) / period),
+            None => self.seed.next_value(true_range),
+        };
+        self.current
+    }
+}
+
+impl Indicator for Atr {
+    type Output = f64;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<f64> {
+        self.next_bar(candle.high(), candle.low(), candle.close())
+    }
+
+    fn reset(&mut self) {
+        self.previous_close = None;
+        self.seed.reset();
+        self.current = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bollinger_constant_series_has_zero_width() {
+        let mut bands = BollingerBands::new(3, 2.0);
+        bands.next_value(5.0);
+        bands.next_value(5.0);
+        let value = bands.next_value(5.0).unwrap();
+        assert_eq!((value.upper, value.middle, value.lower), (5.0, 5.0, 5.0));
+
+        let value = bands.next_value(8.0).unwrap();
+        assert_eq!(value.middle, 6.0);
+        assert!((value.upper - (6.0 + 2.0 * 2.0_f64.sqrt())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_atr_uses_gaps() {
+        let mut atr = Atr::new(2);
+        assert_eq!(atr.next_bar(10.0, 8.0, 9.0), None);
+        // Gap up: true range is high - previous close
+        assert_eq!(atr.next_bar(14.0, 13.0, 13.5), Some(3.5));
+        assert_eq!(atr.next_bar(14.0, 13.0, 13.5), Some(2.25));
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Indicator, Ohlcv};
+
+/// Volume weighted average price over typical prices, cumulative since the last reset.
+///
+/// Call `reset` at session boundaries for a session VWAP.
+#[derive(Debug, Clone, Default)]
+pub struct Vwap {
+    price_volume: f64,
+    volume: f64,
+}
+
+impl Vwap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next trade or bar
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - f64 - Trade price or bar typical price
+    /// * `volume` - f64 - Volume at that price
+    ///
+    /// # Returns
+    ///
+    /// Option<f64> - The VWAP once any volume has been seen
+    pub fn next_trade(&mut self, price: f64, volume: f64) -> Option<f64> {
+        self.price_volume += price * volume;
+        self.volume += volume;
+        self.value()
+    }
+
+    /// Gets the current VWAP without feeding a value
+    pub fn value(&self) -> Option<f64> {
+        (self.volume > 0.0).then(|| self.price_volume / self.volume)
+    }
+}
+
+impl Indicator for Vwap {
+    type Output = f64;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<f64> {
+        self.next_trade(candle.typical_price(), candle.volume())
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// On-balance volume: cumulative volume signed by the direction of the close
+#[derive(Debug, Clone, Default)]
+pub struct Obv {
+    previous_close: Option<f64>,
+    current: f64,
+}
+
+impl Obv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next bar
+    ///
+    /// # Arguments
+    ///
+    /// * `close` - f64 - Bar close
+    /// * `volume` - f64 - Bar volume
+    ///
+    /// # Returns
+    ///
+    /// f64 - The updated OBV; the first bar starts at zero
+    pub fn next_bar(&mut self, close: f64, volume: f64) -> f64 {
+        if let Some(previous) = self.previous_close.replace(close) {
+            if close > previous {
+                self.current += volume;
+            } else if close < previous {
+                self.current -= volume;
+            }
+        }
+        self.current
+    }
+}
+
+impl Indicator for Obv {
+    type Output = f64;
+
+    fn next<C: Ohlcv>(&mut self, candle: &C) -> Option<f64> {
+        Some(self.next_bar(candle.close(), candle.volume()))
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::Decimal;
+    use crate::models::hyperliquid::Candle;
+
+    fn candle(close: &str, volume: &str) -> Candle {
+        let close: Decimal = close.parse().unwrap();
+        Candle {
+            timestamp: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: volume.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_vwap_and_obv_on_hyperliquid_candles() {
+        let candles = vec![candle("10", "1"), candle("12", "3"), candle("11", "2")];
+
+        let vwap = Vwap::new().batch(&candles);
+        assert_eq!(vwap.last().copied().flatten(), Some((10.0 + 36.0 + 22.0) / 6.0));
+
+        let obv = Obv::new().batch(&candles);
+        assert_eq!(obv, vec![Some(0.0), Some(3.0), Some(1.0)]);
+    }
+}
@@ -6,10 +6,18 @@ pub mod models;
 pub mod websocket;
 pub mod client;
 pub mod errors;
+pub mod wallets;
+pub mod risk;
+pub mod indicators;
+pub mod history;
+pub mod pricing;
 
 // Re-export main types for convenience
 pub use client::EnhancedClient;
 pub use auth::AuthClient;
 pub use auth::TokenManager;
 pub use websocket::WebSocketClient;
-pub use errors::{AxiomError, Result};
\ No newline at end of file
+pub use errors::{AxiomError, Result};
+pub use models::solana::{Pubkey, Lamports, TokenAmount};
+pub use models::decimal::Decimal;
+pub use wallets::WalletBook;
\ No newline at end of file
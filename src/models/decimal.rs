@@ -0,0 +1,607 @@
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Neg;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Maximum number of fractional digits a `Decimal` can carry
+pub const MAX_SCALE: u32 = 24;
+
+/// Fractional digits kept when a division does not terminate
+const DIV_SCALE: u32 = 18;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DecimalError {
+    #[error("Invalid decimal: {0}")]
+    Invalid(String),
+
+    #[error("Decimal overflow")]
+    Overflow,
+
+    #[error("Scale {0} exceeds maximum of {MAX_SCALE}")]
+    ScaleTooLarge(u32),
+}
+
+/// Rounding strategy used by `Decimal` rounding helpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Toward zero (truncate)
+    Down,
+    /// Away from zero
+    Up,
+    /// Toward negative infinity
+    Floor,
+    /// Toward positive infinity
+    Ceil,
+    /// To nearest, ties away from zero
+    #[default]
+    HalfUp,
+    /// To nearest, ties to even
+    HalfEven,
+}
+
+/// An exact base-10 number: `mantissa * 10^-scale`.
+///
+/// Hyperliquid sends prices, sizes, funding rates and margin values as decimal strings.
+/// `Decimal` parses them without going through `f64` and serializes them back verbatim,
+/// including trailing zeros, so `"1.50"` round-trips as `"1.50"`.
+#[derive(Clone, Copy, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal { mantissa: 0, scale: 0 };
+    pub const ONE: Decimal = Decimal { mantissa: 1, scale: 0 };
+
+    /// Creates a decimal from a mantissa and scale
+    ///
+    /// # Arguments
+    ///
+    /// * `mantissa` - i128 - The unscaled integer value
+    /// * `scale` - u32 - Number of fractional digits
+    ///
+    /// # Returns
+    ///
+    /// Decimal - `mantissa * 10^-scale`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` exceeds `MAX_SCALE`
+    pub const fn new(mantissa: i128, scale: u32) -> Self {
+        assert!(scale <= MAX_SCALE, "decimal scale exceeds MAX_SCALE");
+        Self { mantissa, scale }
+    }
+
+    /// Converts a float using its shortest round-trip representation
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - f64 - The float to convert
+    ///
+    /// # Returns
+    ///
+    /// Option<Decimal> - None for NaN, infinities or values that do not fit
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let text = value.to_string();
+        match text.parse::<Decimal>() {
+            Ok(d) => Some(d),
+            // Very small floats print with more digits than MAX_SCALE
+            Err(DecimalError::ScaleTooLarge(_)) => {
+                parse_unbounded(&text).map(|(m, s)| rescale_round(m, s, MAX_SCALE, RoundingMode::HalfEven))
+            }
+            Err(_) => None,
+        }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_sign_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    pub fn is_sign_positive(&self) -> bool {
+        self.mantissa > 0
+    }
+
+    pub fn abs(&self) -> Self {
+        Self { mantissa: self.mantissa.abs(), scale: self.scale }
+    }
+
+    /// Gets the base-10 order of magnitude, i.e. `floor(log10(|self|))`
+    ///
+    /// # Returns
+    ///
+    /// Option<i32> - The exponent of the leading digit, or None for zero
+    pub fn magnitude(&self) -> Option<i32> {
+        if self.mantissa == 0 {
+            return None;
+        }
+        Some(digit_count(self.mantissa.unsigned_abs()) as i32 - 1 - self.scale as i32)
+    }
+
+    /// Converts to `f64` (lossy, for display and statistics only)
+    ///
+    /// # Returns
+    ///
+    /// f64 - The nearest float
+    pub fn to_f64(&self) -> f64 {
+        // Going through the string keeps the conversion correctly rounded
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Removes trailing fractional zeros
+    ///
+    /// # Returns
+    ///
+    /// Decimal - The same value with the smallest scale
+    pub fn normalize(&self) -> Self {
+        let mut mantissa = self.mantissa;
+        let mut scale = self.scale;
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Self { mantissa, scale }
+    }
+
+    /// Changes the scale, padding with zeros or rounding as needed
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - u32 - Target number of fractional digits
+    /// * `mode` - RoundingMode - Rounding used when digits are dropped
+    ///
+    /// # Returns
+    ///
+    /// Option<Decimal> - None if the result overflows or `scale` exceeds `MAX_SCALE`
+    pub fn rescale(&self, scale: u32, mode: RoundingMode) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        if scale >= self.scale {
+            let mantissa = self.mantissa.checked_mul(pow10(scale - self.scale)?)?;
+            Some(Self { mantissa, scale })
+        } else {
+            Some(rescale_round(self.mantissa, self.scale, scale, mode))
+        }
+    }
+
+    /// Rounds to a number of decimal places
+    ///
+    /// # Arguments
+    ///
+    /// * `dp` - u32 - Decimal places to keep
+    /// * `mode` - RoundingMode - Rounding strategy
+    ///
+    /// # Returns
+    ///
+    /// Decimal - The rounded value; values with fewer places are returned unchanged
+    pub fn round_dp(&self, dp: u32, mode: RoundingMode) -> Self {
+        if dp >= self.scale {
+            *self
+        } else {
+            rescale_round(self.mantissa, self.scale, dp, mode)
+        }
+    }
+
+    /// Rounds to a number of significant figures
+    ///
+    /// # Arguments
+    ///
+    /// * `figures` - u32 - Significant figures to keep
+    /// * `mode` - RoundingMode - Rounding strategy
+    ///
+    /// # Returns
+    ///
+    /// Decimal - The rounded value
+    pub fn round_sig_figs(&self, figures: u32, mode: RoundingMode) -> Self {
+        let digits = digit_count(self.mantissa.unsigned_abs());
+        if self.mantissa == 0 || figures == 0 || digits <= figures {
+            return *self;
+        }
+
+        let drop = digits - figures;
+        if drop <= self.scale {
+            return self.round_dp(self.scale - drop, mode);
+        }
+
+        // Rounding happens left of the decimal point: round the digits, then pad with zeros
+        let rounded = div_round(self.mantissa, pow10(drop).unwrap_or(i128::MAX), mode);
+        let mantissa = rounded.saturating_mul(pow10(drop - self.scale).unwrap_or(i128::MAX));
+        Self { mantissa, scale: 0 }
+    }
+
+    /// Rounds to a multiple of an increment such as a tick or lot size
+    ///
+    /// # Arguments
+    ///
+    /// * `increment` - Decimal - The step size (must be positive)
+    /// * `mode` - RoundingMode - Rounding strategy
+    ///
+    /// # Returns
+    ///
+    /// Option<Decimal> - None if the increment is not positive or the result overflows
+    pub fn round_to_increment(&self, increment: Decimal, mode: RoundingMode) -> Option<Self> {
+        if increment.mantissa <= 0 {
+            return None;
+        }
+        let scale = self.scale.max(increment.scale);
+        let value = self.rescale(scale, mode)?.mantissa;
+        let step = increment.rescale(scale, mode)?.mantissa;
+        let mantissa = div_round(value, step, mode).checked_mul(step)?;
+        Some(Self { mantissa, scale })
+    }
+
+    pub fn checked_add(self, other: Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let a = self.rescale(scale, RoundingMode::Down)?.mantissa;
+        let b = other.rescale(scale, RoundingMode::Down)?.mantissa;
+        Some(Self { mantissa: a.checked_add(b)?, scale })
+    }
+
+    pub fn checked_sub(self, other: Decimal) -> Option<Decimal> {
+        let negated = Self { mantissa: other.mantissa.checked_neg()?, scale: other.scale };
+        self.checked_add(negated)
+    }
+
+    pub fn checked_mul(self, other: Decimal) -> Option<Decimal> {
+        let mantissa = self.mantissa.checked_mul(other.mantissa)?;
+        let scale = self.scale + other.scale;
+        if scale <= MAX_SCALE {
+            Some(Self { mantissa, scale })
+        } else {
+            Some(rescale_round(mantissa, scale, MAX_SCALE, RoundingMode::HalfEven))
+        }
+    }
+
+    /// Divides, keeping up to 18 fractional digits (or more if the inputs already have them)
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Decimal - The divisor
+    ///
+    /// # Returns
+    ///
+    /// Option<Decimal> - None on division by zero or overflow
+    pub fn checked_div(self, other: Decimal) -> Option<Decimal> {
+        if other.mantissa == 0 {
+            return None;
+        }
+        let scale = self.scale.max(other.scale).max(DIV_SCALE);
+        // (a / 10^sa) / (b / 10^sb) = a * 10^(scale + sb - sa) / b / 10^scale
+        let shift = scale + other.scale - self.scale;
+        let numerator = self.mantissa.checked_mul(pow10(shift)?)?;
+        let mantissa = div_round(numerator, other.mantissa, RoundingMode::HalfEven);
+        Some(Self { mantissa, scale }.normalize_to(self.scale.max(other.scale)))
+    }
+
+    fn normalize_to(self, min_scale: u32) -> Self {
+        let mut result = self;
+        while result.scale > min_scale && result.mantissa % 10 == 0 {
+            result.mantissa /= 10;
+            result.scale -= 1;
+        }
+        result
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mantissa, scale) =
+            parse_unbounded(s).ok_or_else(|| DecimalError::Invalid(s.to_string()))?;
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+        Ok(Self { mantissa, scale })
+    }
+}
+
+/// Parses `[-+]digits[.digits][e[-+]digits]` into a mantissa and scale
+fn parse_unbounded(s: &str) -> Option<(i128, u32)> {
+    let s = s.trim();
+    let (number, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+
+    let (negative, digits) = match number.as_bytes().first()? {
+        b'-' => (true, &number[1..]),
+        b'+' => (false, &number[1..]),
+        _ => (false, number),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let mut mantissa: i128 = 0;
+    for b in whole.bytes().chain(fraction.bytes()) {
+        mantissa = mantissa.checked_mul(10)?.checked_add((b - b'0') as i128)?;
+    }
+
+    let mut scale = fraction.len() as i64 - exponent as i64;
+    if scale < 0 {
+        mantissa = mantissa.checked_mul(pow10(u32::try_from(-scale).ok()?)?)?;
+        scale = 0;
+    }
+
+    Some((if negative { -mantissa } else { mantissa }, u32::try_from(scale).ok()?))
+}
+
+fn pow10(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+fn digit_count(mut value: u128) -> u32 {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+/// Divides two integers, rounding the quotient with `mode`
+fn div_round(numerator: i128, denominator: i128, mode: RoundingMode) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder == 0 {
+        return quotient;
+    }
+
+    let positive = (numerator < 0) == (denominator < 0);
+    let away = if positive { quotient + 1 } else { quotient - 1 };
+    let twice = remainder.unsigned_abs().saturating_mul(2);
+    let divisor = denominator.unsigned_abs();
+
+    let round_away = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::Floor => !positive,
+        RoundingMode::Ceil => positive,
+        RoundingMode::HalfUp => twice >= divisor,
+        RoundingMode::HalfEven => twice > divisor || (twice == divisor && quotient % 2 != 0),
+    };
+
+    if round_away { away } else { quotient }
+}
+
+/// Drops fractional digits from `mantissa` at `from` scale down to `to` scale
+fn rescale_round(mantissa: i128, from: u32, to: u32, mode: RoundingMode) -> Decimal {
+    let drop = from - to;
+    let mantissa = match pow10(drop) {
+        Some(divisor) => div_round(mantissa, divisor, mode),
+        // Every digit is dropped and the value is below half a unit
+        None => match mode {
+            RoundingMode::Up => mantissa.signum(),
+            RoundingMode::Ceil if mantissa > 0 => 1,
+            RoundingMode::Floor if mantissa < 0 => -1,
+            _ => 0,
+        },
+    };
+    Decimal { mantissa, scale: to }
+}
+
+impl TryFrom<f64> for Decimal {
+    type Error = DecimalError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Decimal::from_f64(value).ok_or_else(|| DecimalError::Invalid(value.to_string()))
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Self { mantissa: value as i128, scale: 0 }
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Self {
+        Self { mantissa: value as i128, scale: 0 }
+    }
+}
+
+impl From<u32> for Decimal {
+    fn from(value: u32) -> Self {
+        Self { mantissa: value as i128, scale: 0 }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        if self.scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = self.scale as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        write!(f, "{}{}.{}", sign, whole, fraction)
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Decimal({})", self)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare integer parts first so rescaling can never overflow
+        let a_unit = pow10(self.scale).unwrap_or(1);
+        let b_unit = pow10(other.scale).unwrap_or(1);
+        let a_int = self.mantissa / a_unit;
+        let b_int = other.mantissa / b_unit;
+        a_int.cmp(&b_int).then_with(|| {
+            let a_frac = (self.mantissa % a_unit) * pow10(MAX_SCALE - self.scale).unwrap_or(1);
+            let b_frac = (other.mantissa % b_unit) * pow10(MAX_SCALE - other.scale).unwrap_or(1);
+            a_frac.cmp(&b_frac)
+        })
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal { mantissa: -self.mantissa, scale: self.scale }
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DecimalVisitor;
+
+        impl Visitor<'_> for DecimalVisitor {
+            type Value = Decimal;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a decimal number as a string or number")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Decimal, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Decimal, E> {
+                Ok(Decimal::from(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Decimal, E> {
+                Ok(Decimal::from(v))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Decimal, E> {
+                Decimal::try_from(v).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display_roundtrip() {
+        for s in ["0", "1.50", "-0.0001", "123456.789", "0.000000000000000001"] {
+            assert_eq!(d(s).to_string(), s);
+        }
+        assert_eq!(d("1e-3").to_string(), "0.001");
+        assert_eq!(d("2.5E2").to_string(), "250");
+        assert!("abc".parse::<Decimal>().is_err());
+        assert!("1.2.3".parse::<Decimal>().is_err());
+        assert!("".parse::<Decimal>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_is_exact() {
+        assert_eq!(d("0.1").checked_add(d("0.2")), Some(d("0.3")));
+        assert_eq!(d("1.5").checked_sub(d("2.25")), Some(d("-0.75")));
+        assert_eq!(d("1.5").checked_mul(d("0.02")), Some(d("0.03")));
+        let max = d("170141183460469231731687303715884105727");
+        assert!(max.checked_add(Decimal::ONE).is_none());
+        assert!(max.checked_mul(d("2")).is_none());
+        assert!(Decimal::ZERO.checked_sub(max).unwrap().checked_sub(d("2")).is_none());
+        assert_eq!(d("1").checked_div(d("3")).unwrap().to_string(), "0.333333333333333333");
+        assert_eq!(d("10").checked_div(d("4")).unwrap(), d("2.5"));
+        assert!(d("1").checked_div(Decimal::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_comparison_ignores_scale() {
+        assert_eq!(d("1.50"), d("1.5"));
+        assert!(d("-1.5") < d("-1.49"));
+        assert!(d("100") > d("99.999999"));
+        assert_eq!(d("1.50").normalize().to_string(), "1.5");
+        assert_eq!(d("0.00123").magnitude(), Some(-3));
+        assert_eq!(d("97123.5").magnitude(), Some(4));
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(d("1.2345").round_dp(2, RoundingMode::HalfUp), d("1.23"));
+        assert_eq!(d("1.235").round_dp(2, RoundingMode::HalfUp), d("1.24"));
+        assert_eq!(d("1.225").round_dp(2, RoundingMode::HalfEven), d("1.22"));
+        assert_eq!(d("-1.235").round_dp(2, RoundingMode::Floor), d("-1.24"));
+        assert_eq!(d("-1.235").round_dp(2, RoundingMode::Down), d("-1.23"));
+        assert_eq!(d("123456.7").round_sig_figs(5, RoundingMode::HalfUp), d("123460"));
+        assert_eq!(d("0.0123456").round_sig_figs(5, RoundingMode::HalfUp), d("0.012346"));
+        assert_eq!(
+            d("101.37").round_to_increment(d("0.25"), RoundingMode::HalfUp).unwrap(),
+            d("101.25")
+        );
+        assert_eq!(
+            d("101.38").round_to_increment(d("0.25"), RoundingMode::Ceil).unwrap(),
+            d("101.5")
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let value: Decimal = serde_json::from_str("\"42.10\"").unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"42.10\"");
+        let number: Decimal = serde_json::from_str("0.25").unwrap();
+        assert_eq!(number, d("0.25"));
+    }
+}
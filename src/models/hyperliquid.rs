@@ -1,3 +1,4 @@
+use crate::models::decimal::{Decimal, RoundingMode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -7,8 +8,8 @@ use std::collections::HashMap;
 pub struct ClearinghouseState {
     pub margin_summary: MarginSummary,
     pub cross_margin_summary: MarginSummary,
-    pub cross_maintenance_margin_used: String,
-    pub withdrawable: String,
+    pub cross_maintenance_margin_used: Decimal,
+    pub withdrawable: Decimal,
     pub asset_positions: Vec<AssetPosition>,
     pub time: u64,
 }
@@ -17,10 +18,10 @@ pub struct ClearinghouseState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginSummary {
-    pub account_value: String,
-    pub total_ntl_pos: String,
-    pub total_raw_usd: String,
-    pub total_margin_used: String,
+    pub account_value: Decimal,
+    pub total_ntl_pos: Decimal,
+    pub total_raw_usd: Decimal,
+    pub total_margin_used: Decimal,
 }
 
 /// Asset position information
@@ -29,9 +30,9 @@ pub struct MarginSummary {
 pub struct AssetPosition {
     pub position: Position,
     pub entry: PositionEntry,
-    pub unrealized_pnl: String,
-    pub roe: String,
-    pub margin_used: String,
+    pub unrealized_pnl: Decimal,
+    pub roe: Decimal,
+    pub margin_used: Decimal,
     pub coin: String,
 }
 
@@ -40,15 +41,15 @@ pub struct AssetPosition {
 #[serde(rename_all = "camelCase")]
 pub struct Position {
     pub coin: String,
-    pub entry_px: String,
+    pub entry_px: Decimal,
     pub leverage: Leverage,
-    pub liquidation_px: Option<String>,
-    pub margin_used: String,
-    pub max_leverage: String,
-    pub position_value: String,
-    pub return_on_equity: String,
-    pub szi: String,
-    pub unrealized_pnl: String,
+    pub liquidation_px: Option<Decimal>,
+    pub margin_used: Decimal,
+    pub max_leverage: Decimal,
+    pub position_value: Decimal,
+    pub return_on_equity: Decimal,
+    pub szi: Decimal,
+    pub unrealized_pnl: Decimal,
 }
 
 /// Position leverage information
@@ -57,15 +58,15 @@ pub struct Position {
 pub struct Leverage {
     pub type_: String,
     pub value: u32,
-    pub raw_usd: String,
+    pub raw_usd: Decimal,
 }
 
 /// Position entry information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PositionEntry {
-    pub entry_px: String,
-    pub szi: String,
+    pub entry_px: Decimal,
+    pub szi: Decimal,
     pub time: u64,
 }
 
@@ -93,29 +94,108 @@ pub struct MarketInfo {
     pub is_delisted: Option<bool>,
 }
 
+/// Maximum decimal places for perpetual prices before subtracting `sz_decimals`
+const MAX_PERP_PRICE_DECIMALS: u32 = 6;
+
+/// Maximum significant figures Hyperliquid accepts for non-integer prices
+const MAX_PRICE_SIG_FIGS: u32 = 5;
+
+impl MarketInfo {
+    /// Gets the smallest order size increment for this market
+    ///
+    /// Returns:
+    ///     Decimal: `10^-sz_decimals`.
+    pub fn size_increment(&self) -> Decimal {
+        Decimal::new(1, self.sz_decimals as u32)
+    }
+
+    /// Gets the maximum number of decimal places allowed in a price
+    ///
+    /// Returns:
+    ///     u32: `6 - sz_decimals` for perpetual markets.
+    pub fn max_price_decimals(&self) -> u32 {
+        MAX_PERP_PRICE_DECIMALS.saturating_sub(self.sz_decimals as u32)
+    }
+
+    /// Gets the effective tick size at a given price level.
+    ///
+    /// Hyperliquid allows at most five significant figures and `max_price_decimals`
+    /// decimal places; integer prices are always valid.
+    ///
+    /// Args:
+    ///     px: Decimal - The reference price.
+    ///
+    /// Returns:
+    ///     Decimal: The price increment at that level.
+    pub fn tick_size(&self, px: Decimal) -> Decimal {
+        let sig_fig_exponent = px
+            .magnitude()
+            .map(|m| m - (MAX_PRICE_SIG_FIGS as i32 - 1))
+            .unwrap_or(0);
+        let exponent = sig_fig_exponent.max(-(self.max_price_decimals() as i32)).min(0);
+        Decimal::new(1, exponent.unsigned_abs())
+    }
+
+    /// Rounds a price to a valid tick for this market.
+    ///
+    /// Args:
+    ///     px: Decimal - The desired price.
+    ///     mode: RoundingMode - Use `Floor` for bids and `Ceil` for asks to stay passive.
+    ///
+    /// Returns:
+    ///     Decimal: The rounded price.
+    pub fn round_price(&self, px: Decimal, mode: RoundingMode) -> Decimal {
+        px.round_to_increment(self.tick_size(px), mode).unwrap_or(px)
+    }
+
+    /// Rounds a size down to this market's `sz_decimals`.
+    ///
+    /// Args:
+    ///     sz: Decimal - The desired size.
+    ///
+    /// Returns:
+    ///     Decimal: The truncated size.
+    pub fn round_size(&self, sz: Decimal) -> Decimal {
+        sz.round_dp(self.sz_decimals as u32, RoundingMode::Down)
+    }
+}
+
 /// Margin table information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginTable {
     pub id: u32,
-    pub initial_margin_frac: String,
-    pub maintenance_margin_frac: String,
-    pub max_position_size: String,
+    pub initial_margin_frac: Decimal,
+    pub maintenance_margin_frac: Decimal,
+    pub max_position_size: Decimal,
 }
 
 /// All mid prices
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AllMids(pub HashMap<String, String>);
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllMids(pub HashMap<String, Decimal>);
+
+impl AllMids {
+    /// Get the mid price for a coin.
+    ///
+    /// Args:
+    ///     coin: &str - The coin symbol (e.g., "BTC", "ETH").
+    ///
+    /// Returns:
+    ///     Option<Decimal>: The mid price, if the coin is listed.
+    pub fn get(&self, coin: &str) -> Option<Decimal> {
+        self.0.get(coin).copied()
+    }
+}
 
 /// Open order information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenOrder {
     pub coin: String,
-    pub limit_px: String,
+    pub limit_px: Decimal,
     pub oid: u64,
     pub side: String,
-    pub sz: String,
+    pub sz: Decimal,
     pub timestamp: u64,
     pub cloid: Option<String>,
     pub reduce_only: bool,
@@ -128,16 +208,16 @@ pub struct OpenOrder {
 #[serde(rename_all = "camelCase")]
 pub struct UserFill {
     pub coin: String,
-    pub px: String,
-    pub sz: String,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub side: String,
     pub timestamp: u64,
-    pub start_position: String,
+    pub start_position: Decimal,
     pub dir: String,
     pub hash: String,
     pub oid: u64,
     pub crossed: bool,
-    pub fee: String,
+    pub fee: Decimal,
     pub liquidation: Option<bool>,
 }
 
@@ -150,10 +230,10 @@ pub struct Orderbook {
 }
 
 /// Orderbook level (bids or asks)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderbookLevel {
-    pub px: String,
-    pub sz: String,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub n: u32,
 }
 
@@ -163,8 +243,8 @@ pub struct OrderbookLevel {
 pub struct RecentTrade {
     pub coin: String,
     pub side: String,
-    pub px: String,
-    pub sz: String,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub timestamp: u64,
     pub hash: String,
 }
@@ -174,15 +254,15 @@ pub struct RecentTrade {
 #[serde(rename_all = "camelCase")]
 pub struct MarketStats {
     pub coin: String,
-    pub day_ntl_vlm: String,
-    pub day_change: String,
-    pub funding: String,
-    pub open_interest: String,
-    pub prev_day_px: String,
-    pub mark_px: String,
-    pub mid_px: String,
-    pub impact_px: Vec<String>,
-    pub premium: String,
+    pub day_ntl_vlm: Decimal,
+    pub day_change: Decimal,
+    pub funding: Decimal,
+    pub open_interest: Decimal,
+    pub prev_day_px: Decimal,
+    pub mark_px: Decimal,
+    pub mid_px: Decimal,
+    pub impact_px: Vec<Decimal>,
+    pub premium: Decimal,
 }
 
 /// Funding payment information
@@ -190,9 +270,9 @@ pub struct MarketStats {
 #[serde(rename_all = "camelCase")]
 pub struct FundingPayment {
     pub coin: String,
-    pub usdc: String,
-    pub szi: String,
-    pub funding_rate: String,
+    pub usdc: Decimal,
+    pub szi: Decimal,
+    pub funding_rate: Decimal,
     pub timestamp: u64,
 }
 
@@ -200,19 +280,23 @@ pub struct FundingPayment {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Candle {
     pub timestamp: u64,
-    pub open: String,
-    pub high: String,
-    pub low: String,
-    pub close: String,
-    pub volume: String,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
 }
 
 /// WebSocket subscription types for Hyperliquid
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// Serializes to the wire form, e.g. `{"type":"l2Book","coin":"BTC"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
 pub enum HyperliquidSubscription {
     AllMids,
     Notification { user: String },
+    /// The server retired `webData` in favour of `webData2`
+    #[serde(rename = "webData2")]
     WebData { user: String },
     Candle { coin: String, interval: String },
     L2Book { coin: String },
@@ -299,4 +383,44 @@ pub struct TokenBalance {
     pub balance: f64,
     pub value_usd: f64,
     pub decimals: u8,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(sz_decimals: u8) -> MarketInfo {
+        MarketInfo {
+            name: "TEST".to_string(),
+            sz_decimals,
+            max_leverage: 50,
+            margin_table_id: 0,
+            is_delisted: None,
+        }
+    }
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_tick_size_follows_sig_figs_and_decimals() {
+        let btc = market(5);
+        assert_eq!(btc.tick_size(d("97123.5")), d("1"));
+        assert_eq!(btc.round_price(d("97123.5"), RoundingMode::Floor), d("97123"));
+        assert_eq!(btc.round_size(d("0.1234567")), d("0.12345"));
+
+        let meme = market(0);
+        assert_eq!(meme.tick_size(d("0.0123456")), d("0.000001"));
+        assert_eq!(meme.round_price(d("0.0123456"), RoundingMode::HalfUp), d("0.012346"));
+        assert_eq!(meme.tick_size(d("12.345678")), d("0.001"));
+    }
+
+    #[test]
+    fn test_string_fields_roundtrip() {
+        let json = r#"{"px":"97123.50","sz":"0.00120","n":3}"#;
+        let level: OrderbookLevel = serde_json::from_str(json).unwrap();
+        assert_eq!(level.px, d("97123.5"));
+        assert_eq!(serde_json::to_string(&level).unwrap(), json);
+    }
+}
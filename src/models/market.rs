@@ -1,3 +1,4 @@
+use crate::models::solana::Pubkey;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,11 +7,17 @@ pub struct TrendingToken {
     pub symbol: String,
     pub name: String,
     pub price_usd: f64,
-    pub price_change_24h: f64,
-    pub price_change_7d: f64,
-    pub volume_24h: f64,
-    pub market_cap: f64,
-    pub holders: u64,
+    /// Market cap change over the requested period, in percent; the endpoint reports no price change
+    pub market_cap_change_percent: f64,
+    /// Only set when the endpoint reports a 7d change
+    pub price_change_7d: Option<f64>,
+    /// Volume over the requested period, in SOL
+    pub volume_sol: f64,
+    pub market_cap_sol: f64,
+    /// Holder count, when the endpoint reports it
+    pub holders: Option<u64>,
+    /// Percent of supply held by the ten largest holders
+    pub top_10_holders_percent: f64,
     pub rank: u32,
     pub logo_uri: Option<String>,
 }
@@ -64,6 +71,166 @@ pub struct TokenAnalysis {
     pub top_og_coins: Vec<RelatedToken>,
 }
 
+/// Token metadata and creator analysis for a token keyed by mint or pair address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenProfile {
+    #[serde(rename = "tokenAddress")]
+    pub mint_address: String,
+    #[serde(default)]
+    pub pair_address: String,
+    #[serde(rename = "tokenTicker", default)]
+    pub symbol: String,
+    #[serde(rename = "tokenName", default)]
+    pub name: String,
+    #[serde(rename = "tokenDecimals", default)]
+    pub decimals: u8,
+    #[serde(default)]
+    pub supply: f64,
+    #[serde(default)]
+    pub liquidity_sol: f64,
+    #[serde(default)]
+    pub liquidity_token: f64,
+    #[serde(default)]
+    pub protocol: String,
+    #[serde(default)]
+    pub protocol_details: Option<serde_json::Value>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(rename = "tokenImage", default)]
+    pub logo_uri: Option<String>,
+    /// None when not reported, Some(None) when the authority has been revoked
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub mint_authority: Option<Option<String>>,
+    /// None when not reported, Some(None) when the authority has been revoked
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub freeze_authority: Option<Option<String>>,
+    #[serde(default)]
+    pub lp_burned: Option<f64>,
+    #[serde(default)]
+    pub creator_risk_level: Option<String>,
+    #[serde(default)]
+    pub creator_rug_count: Option<u32>,
+    #[serde(default)]
+    pub creator_token_count: u32,
+    #[serde(rename = "topMarketCapCoins", default)]
+    pub top_market_cap_coins: Vec<RelatedToken>,
+    #[serde(rename = "topOgCoins", default)]
+    pub top_og_coins: Vec<RelatedToken>,
+}
+
+/// Deserializes a nullable field so a missing field (None) differs from an explicit null (Some(None))
+pub(crate) fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl TokenProfile {
+    /// Combines token info and an optional creator analysis into a profile
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - TokenInfo - Token metadata looked up by address
+    /// * `analysis` - Option<TokenAnalysis> - Creator analysis; None leaves the creator fields unset
+    ///
+    /// # Returns
+    ///
+    /// TokenProfile - The combined profile
+    pub fn from_parts(info: TokenInfo, analysis: Option<TokenAnalysis>) -> Self {
+        let mut profile = Self {
+            mint_address: info.mint_address,
+            pair_address: info.pair_address,
+            symbol: info.symbol,
+            name: info.name,
+            decimals: info.decimals,
+            supply: info.supply,
+            liquidity_sol: info.liquidity_sol,
+            liquidity_token: info.liquidity_token,
+            protocol: info.protocol,
+            protocol_details: info.protocol_details,
+            created_at: info.created_at,
+            logo_uri: info.logo_uri,
+            mint_authority: Some(info.mint_authority),
+            freeze_authority: Some(info.freeze_authority),
+            lp_burned: Some(info.lp_burned),
+            creator_risk_level: None,
+            creator_rug_count: None,
+            creator_token_count: 0,
+            top_market_cap_coins: Vec::new(),
+            top_og_coins: Vec::new(),
+        };
+        if let Some(analysis) = analysis {
+            profile.creator_risk_level = Some(analysis.creator_risk_level);
+            profile.creator_rug_count = Some(analysis.creator_rug_count);
+            profile.creator_token_count = analysis.creator_token_count;
+            profile.top_market_cap_coins = analysis.top_market_cap_coins;
+            profile.top_og_coins = analysis.top_og_coins;
+        }
+        profile
+    }
+
+    /// Whether the mint authority is still set, so supply can be inflated; None if not reported
+    pub fn has_mint_authority(&self) -> Option<bool> {
+        self.mint_authority
+            .as_ref()
+            .map(|a| a.as_deref().is_some_and(|a| !a.is_empty()))
+    }
+
+    /// Whether the freeze authority is still set, so holders can be frozen; None if not reported
+    pub fn has_freeze_authority(&self) -> Option<bool> {
+        self.freeze_authority
+            .as_ref()
+            .map(|a| a.as_deref().is_some_and(|a| !a.is_empty()))
+    }
+
+    /// Splits the profile into the separate info and analysis structs
+    ///
+    /// # Returns
+    ///
+    /// Option<(TokenInfo, Option<TokenAnalysis>)> - None if the authorities or LP burn were not
+    /// reported; the analysis is None if the creator risk level or rug count were not reported
+    pub fn into_parts(self) -> Option<(TokenInfo, Option<TokenAnalysis>)> {
+        let (Some(mint_authority), Some(freeze_authority), Some(lp_burned)) =
+            (self.mint_authority, self.freeze_authority, self.lp_burned)
+        else {
+            return None;
+        };
+
+        let analysis = match (self.creator_risk_level, self.creator_rug_count) {
+            (Some(creator_risk_level), Some(creator_rug_count)) => Some(TokenAnalysis {
+                creator_risk_level,
+                creator_rug_count,
+                creator_token_count: self.creator_token_count,
+                top_market_cap_coins: self.top_market_cap_coins,
+                top_og_coins: self.top_og_coins,
+            }),
+            _ => None,
+        };
+        let info = TokenInfo {
+            mint_address: self.mint_address,
+            symbol: self.symbol,
+            name: self.name,
+            decimals: self.decimals,
+            supply: self.supply,
+            liquidity_sol: self.liquidity_sol,
+            liquidity_token: self.liquidity_token,
+            pair_address: self.pair_address,
+            protocol: self.protocol,
+            protocol_details: self.protocol_details,
+            created_at: self.created_at,
+            logo_uri: self.logo_uri,
+            mint_authority,
+            freeze_authority,
+            lp_burned,
+            extra: None,
+        };
+        Some((info, analysis))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RelatedToken {
@@ -89,7 +256,7 @@ pub struct RelatedToken {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceData {
-    pub mint_address: String,
+    pub mint_address: Pubkey,
     pub price_usd: f64,
     pub price_sol: f64,
     pub timestamp: i64,
@@ -123,10 +290,33 @@ pub struct MarketStats {
 pub struct TokenChart {
     pub mint_address: String,
     pub timeframe: ChartTimeframe,
+    /// Quote currency of the candle prices; None if the server did not report it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<ChartCurrency>,
     pub candles: Vec<Candle>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Quote currency of chart candle prices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ChartCurrency {
+    #[serde(alias = "sol")]
+    Sol,
+    #[serde(alias = "usd")]
+    Usd,
+}
+
+impl ChartCurrency {
+    /// Gets the currency string used by the chart endpoint
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChartCurrency::Sol => "SOL",
+            ChartCurrency::Usd => "USD",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Candle {
     pub timestamp: i64,
     pub open: f64,
@@ -136,7 +326,7 @@ pub struct Candle {
     pub volume: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ChartTimeframe {
     #[serde(rename = "1m")]
@@ -155,7 +345,56 @@ pub enum ChartTimeframe {
     OneWeek,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ChartTimeframe {
+    /// Every supported timeframe, shortest first
+    pub const ALL: [ChartTimeframe; 7] = [
+        ChartTimeframe::OneMinute,
+        ChartTimeframe::FiveMinutes,
+        ChartTimeframe::FifteenMinutes,
+        ChartTimeframe::OneHour,
+        ChartTimeframe::FourHours,
+        ChartTimeframe::OneDay,
+        ChartTimeframe::OneWeek,
+    ];
+
+    /// Gets the interval string used by the chart endpoint
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChartTimeframe::OneMinute => "1m",
+            ChartTimeframe::FiveMinutes => "5m",
+            ChartTimeframe::FifteenMinutes => "15m",
+            ChartTimeframe::OneHour => "1h",
+            ChartTimeframe::FourHours => "4h",
+            ChartTimeframe::OneDay => "1d",
+            ChartTimeframe::OneWeek => "1w",
+        }
+    }
+
+    /// Gets the candle duration in seconds
+    pub fn as_secs(&self) -> i64 {
+        match self {
+            ChartTimeframe::OneMinute => 60,
+            ChartTimeframe::FiveMinutes => 300,
+            ChartTimeframe::FifteenMinutes => 900,
+            ChartTimeframe::OneHour => 3_600,
+            ChartTimeframe::FourHours => 14_400,
+            ChartTimeframe::OneDay => 86_400,
+            ChartTimeframe::OneWeek => 604_800,
+        }
+    }
+
+    /// Gets the start of the candle containing `timestamp` (seconds). Weeks start on Monday.
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        // The Unix epoch fell on a Thursday; Monday 1970-01-05 is four days later
+        let offset = match self {
+            ChartTimeframe::OneWeek => 4 * 86_400,
+            _ => 0,
+        };
+        timestamp - (timestamp - offset).rem_euclid(self.as_secs())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TimePeriod {
     #[serde(rename = "1h")]
@@ -168,6 +407,18 @@ pub enum TimePeriod {
     ThirtyDays,
 }
 
+impl TimePeriod {
+    /// Gets the period string used by the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimePeriod::OneHour => "1h",
+            TimePeriod::TwentyFourHours => "24h",
+            TimePeriod::SevenDays => "7d",
+            TimePeriod::ThirtyDays => "30d",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenSearch {
     pub query: String,
@@ -202,4 +453,145 @@ pub struct TokenSearchResult {
     pub website: Option<String>,
     pub twitter: Option<String>,
     pub telegram: Option<String>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_token_profile_distinguishes_missing_from_revoked() {
+        let profile: TokenProfile = serde_json::from_value(json!({
+            "tokenAddress": "So11111111111111111111111111111111111111112",
+            "tokenTicker": "TEST",
+            "mintAuthority": null,
+            "freezeAuthority": "Freeze1111",
+            "lpBurned": 100.0
+        }))
+        .unwrap();
+
+        assert_eq!(profile.mint_authority, Some(None));
+        assert_eq!(profile.has_mint_authority(), Some(false));
+        assert_eq!(profile.has_freeze_authority(), Some(true));
+        assert_eq!(profile.creator_risk_level, None);
+        assert_eq!(profile.creator_rug_count, None);
+
+        let sparse: TokenProfile = serde_json::from_value(json!({
+            "tokenAddress": "So11111111111111111111111111111111111111112"
+        }))
+        .unwrap();
+        assert_eq!(sparse.has_mint_authority(), None);
+        assert_eq!(sparse.lp_burned, None);
+        assert!(sparse.into_parts().is_none());
+    }
+
+    #[test]
+    fn test_token_profile_from_pair_info_and_analysis() {
+        let info: TokenInfo = serde_json::from_value(json!({
+            "tokenAddress": "So11111111111111111111111111111111111111112",
+            "tokenTicker": "TEST",
+            "tokenName": "Test Token",
+            "tokenDecimals": 6,
+            "supply": 1000000000.0,
+            "liquiditySol": 85.2,
+            "liquidityToken": 206900000.0,
+            "pairAddress": "Pair1111",
+            "protocol": "Pump V1",
+            "protocolDetails": null,
+            "createdAt": "2025-01-01T00:00:00.000Z",
+            "tokenImage": null,
+            "mintAuthority": null,
+            "freezeAuthority": "Freeze1111",
+            "lpBurned": 100.0,
+            "extra": null
+        }))
+        .unwrap();
+        let analysis: TokenAnalysis = serde_json::from_value(json!({
+            "creatorRiskLevel": "High",
+            "creatorRugCount": 3,
+            "creatorTokenCount": 12,
+            "topMarketCapCoins": [{
+                "tokenAddress": "Other1111",
+                "tokenTicker": "OTHER",
+                "tokenName": "Other Token",
+                "pairAddress": "OtherPair1111",
+                "marketCap": 52000.0,
+                "createdAt": "2024-12-01T00:00:00.000Z",
+                "lastTradeTime": "2024-12-02T00:00:00.000Z",
+                "image": null,
+                "migrated": false,
+                "bondingCurvePercent": 37.5
+            }],
+            "topOgCoins": []
+        }))
+        .unwrap();
+
+        let profile = TokenProfile::from_parts(info.clone(), Some(analysis));
+        assert_eq!(profile.pair_address, "Pair1111");
+        assert_eq!(profile.has_mint_authority(), Some(false));
+        assert_eq!(profile.has_freeze_authority(), Some(true));
+        assert_eq!(profile.lp_burned, Some(100.0));
+        assert_eq!(profile.creator_risk_level.as_deref(), Some("High"));
+        assert_eq!(profile.creator_rug_count, Some(3));
+        assert_eq!(profile.top_market_cap_coins[0].symbol, "OTHER");
+
+        let without_creator = TokenProfile::from_parts(info, None);
+        assert_eq!(without_creator.creator_rug_count, None);
+        let (_, analysis) = without_creator.into_parts().unwrap();
+        assert!(analysis.is_none());
+    }
+
+    #[test]
+    fn test_chart_reports_its_currency() {
+        let chart: TokenChart = serde_json::from_value(json!({
+            "mint_address": "So11111111111111111111111111111111111111112",
+            "timeframe": "1m",
+            "currency": "SOL",
+            "candles": []
+        }))
+        .unwrap();
+        assert_eq!(chart.currency, Some(ChartCurrency::Sol));
+
+        let unreported: TokenChart = serde_json::from_value(json!({
+            "mint_address": "So11111111111111111111111111111111111111112",
+            "timeframe": "1m",
+            "candles": []
+        }))
+        .unwrap();
+        assert_eq!(unreported.currency, None);
+    }
+
+    #[test]
+    fn test_token_profile_into_parts() {
+        let profile: TokenProfile = serde_json::from_value(json!({
+            "tokenAddress": "So11111111111111111111111111111111111111112",
+            "pairAddress": "Pair1111",
+            "tokenTicker": "TEST",
+            "tokenDecimals": 6,
+            "liquiditySol": 42.5,
+            "mintAuthority": null,
+            "freezeAuthority": null,
+            "lpBurned": 99.5,
+            "creatorRiskLevel": "Low",
+            "creatorRugCount": 1,
+            "creatorTokenCount": 4
+        }))
+        .unwrap();
+
+        let (info, analysis) = profile.clone().into_parts().unwrap();
+        assert_eq!(info.pair_address, "Pair1111");
+        assert_eq!(info.decimals, 6);
+        assert_eq!(info.mint_authority, None);
+        assert_eq!(info.lp_burned, 99.5);
+        let analysis = analysis.unwrap();
+        assert_eq!(analysis.creator_risk_level, "Low");
+        assert_eq!(analysis.creator_rug_count, 1);
+        assert_eq!(analysis.creator_token_count, 4);
+
+        let mut no_creator = profile;
+        no_creator.creator_rug_count = None;
+        let (_, analysis) = no_creator.into_parts().unwrap();
+        assert!(analysis.is_none());
+    }
+}
@@ -6,4 +6,9 @@ pub mod social;
 pub mod notifications;
 pub mod hyperliquid;
 pub mod infrastructure;
-pub mod turnkey;
\ No newline at end of file
+pub mod turnkey;
+pub mod solana;
+pub mod decimal;
+pub mod token_accounts;
+pub mod position_analytics;
+pub mod screener;
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::models::solana::{Pubkey, TokenAmount};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,23 +14,39 @@ pub struct TokenBalance {
     pub mint_address: String,
     pub symbol: String,
     pub name: String,
-    pub amount: f64,
+    #[serde(with = "crate::models::solana::raw_amount")]
+    pub amount: u128,
     pub decimals: u8,
     pub ui_amount: f64,
     pub value_usd: f64,
     pub price_per_token: f64,
 }
 
+impl TokenBalance {
+    /// Gets the exact raw balance together with the mint decimals
+    ///
+    /// # Returns
+    ///
+    /// TokenAmount - The exact token amount
+    pub fn token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.amount, self.decimals)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchBalanceRequest {
     #[serde(rename = "publicKeys")]
-    pub public_keys: Vec<String>,
+    pub public_keys: Vec<Pubkey>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchBalanceResponse {
     pub balances: HashMap<String, WalletBalance>,
     pub timestamp: i64,
+    /// Wallets reported without a USD value that could not be priced from SOL; their
+    /// `total_value_usd` is zero
+    #[serde(default)]
+    pub unpriced: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,579 @@
+use crate::models::market::Candle;
+use crate::models::portfolio_v5::Transaction;
+use crate::models::solana::WRAPPED_SOL_MINT;
+use crate::models::trading::{OrderResponse, OrderType, QuoteResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Strategy key used for trades without a strategy tag
+pub const UNTAGGED_STRATEGY: &str = "untagged";
+
+/// Fraction of the bought size that may remain for a position to still count as closed
+const CLOSED_TOLERANCE: f64 = 1e-6;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PositionAnalyticsError {
+    #[error("Trade has no buy fills: {0}")]
+    NoEntry(String),
+
+    #[error("Invalid fill: {0}")]
+    InvalidFill(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FillSide {
+    Buy,
+    Sell,
+}
+
+/// A single executed entry or exit, priced in SOL per token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    pub signature: Option<String>,
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub side: FillSide,
+    pub amount_tokens: f64,
+    pub price_sol: f64,
+    /// Price the quote promised, used for slippage
+    pub quoted_price_sol: Option<f64>,
+    #[serde(default)]
+    pub fee_sol: f64,
+}
+
+impl Fill {
+    ///
+    /// Builds a fill from an executed order and the quote it was placed against.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - &OrderResponse - The executed order
+    /// * `quote` - Option<&QuoteResponse> - The quote used to place the order
+    ///
+    /// # Returns
+    ///
+    /// Option<Fill> - The fill, or None for swaps
+    ///
+    pub fn from_order(order: &OrderResponse, quote: Option<&QuoteResponse>) -> Option<Self> {
+        let (side, amount_tokens) = match order.transaction_type {
+            OrderType::Buy => (FillSide::Buy, order.amount_out),
+            OrderType::Sell => (FillSide::Sell, order.amount_in),
+            OrderType::Swap => return None,
+        };
+
+        Some(Self {
+            signature: Some(order.signature.clone()),
+            timestamp: order.timestamp,
+            side,
+            amount_tokens,
+            price_sol: order.price_per_token,
+            quoted_price_sol: quote.and_then(quoted_price),
+            fee_sol: order.fee,
+        })
+    }
+
+    ///
+    /// Builds a fill from a portfolio transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - &Transaction - A buy or sell from the portfolio history
+    ///
+    /// # Returns
+    ///
+    /// Option<Fill> - The fill, or None if the transaction is not a priced buy or sell
+    ///
+    pub fn from_transaction(transaction: &Transaction) -> Option<Self> {
+        let side = match transaction.transaction_type.as_deref()?.to_lowercase().as_str() {
+            "buy" => FillSide::Buy,
+            "sell" => FillSide::Sell,
+            _ => return None,
+        };
+
+        Some(Self {
+            signature: transaction.signature.clone(),
+            timestamp: transaction.timestamp?,
+            side,
+            amount_tokens: transaction.amount?,
+            price_sol: transaction.price?,
+            quoted_price_sol: None,
+            fee_sol: 0.0,
+        })
+    }
+
+    ///
+    /// Gets the slippage against the quoted price in basis points.
+    ///
+    /// Positive values mean the fill was worse than quoted.
+    ///
+    /// # Returns
+    ///
+    /// Option<f64> - Slippage in bps, None without a quote
+    ///
+    pub fn slippage_bps(&self) -> Option<f64> {
+        let quoted = self.quoted_price_sol.filter(|q| *q > 0.0)?;
+        let diff = match self.side {
+            FillSide::Buy => self.price_sol - quoted,
+            FillSide::Sell => quoted - self.price_sol,
+        };
+        Some(diff / quoted * 10_000.0)
+    }
+}
+
+fn quoted_price(quote: &QuoteResponse) -> Option<f64> {
+    if quote.in_amount <= 0.0 || quote.out_amount <= 0.0 {
+        return None;
+    }
+    if quote.input_mint == WRAPPED_SOL_MINT {
+        Some(quote.in_amount / quote.out_amount)
+    } else {
+        Some(quote.out_amount / quote.in_amount)
+    }
+}
+
+/// All fills for one round trip in a token, optionally tagged with a strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeRecord {
+    pub token_mint: String,
+    pub strategy: Option<String>,
+    pub fills: Vec<Fill>,
+}
+
+impl TradeRecord {
+    pub fn new(token_mint: &str) -> Self {
+        Self {
+            token_mint: token_mint.to_string(),
+            strategy: None,
+            fills: Vec::new(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: &str) -> Self {
+        self.strategy = Some(strategy.to_string());
+        self
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.fills.push(fill);
+        self
+    }
+
+    ///
+    /// Groups portfolio transactions into one trade record per token.
+    ///
+    /// # Arguments
+    ///
+    /// * `transactions` - &[Transaction] - Portfolio transaction history
+    ///
+    /// # Returns
+    ///
+    /// Vec<TradeRecord> - Untagged trade records, one per token
+    ///
+    pub fn from_transactions(transactions: &[Transaction]) -> Vec<Self> {
+        let mut by_token: BTreeMap<String, TradeRecord> = BTreeMap::new();
+        for transaction in transactions {
+            let (Some(token), Some(fill)) = (&transaction.token_address, Fill::from_transaction(transaction)) else {
+                continue;
+            };
+            by_token
+                .entry(token.clone())
+                .or_insert_with(|| TradeRecord::new(token))
+                .fills
+                .push(fill);
+        }
+        by_token.into_values().collect()
+    }
+
+    pub fn strategy_key(&self) -> &str {
+        self.strategy.as_deref().unwrap_or(UNTAGGED_STRATEGY)
+    }
+
+    pub fn entry_time(&self) -> Option<i64> {
+        self.fills
+            .iter()
+            .filter(|f| f.side == FillSide::Buy)
+            .map(|f| f.timestamp)
+            .min()
+    }
+}
+
+/// Excursion, holding and execution metrics for one trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionAnalytics {
+    pub token_mint: String,
+    pub strategy: String,
+    pub entry_time: i64,
+    /// Last sell time, None while the position is still open
+    pub exit_time: Option<i64>,
+    pub holding_secs: i64,
+    pub tokens_bought: f64,
+    pub tokens_sold: f64,
+    pub avg_entry_price: f64,
+    pub avg_exit_price: Option<f64>,
+    pub realized_pnl_sol: f64,
+    /// Highest price seen while holding
+    pub peak_price: f64,
+    /// Lowest price seen while holding
+    pub trough_price: f64,
+    /// Maximum adverse excursion from the average entry, as a percentage (<= 0)
+    pub mae_percent: f64,
+    /// Maximum favorable excursion from the average entry, as a percentage (>= 0)
+    pub mfe_percent: f64,
+    pub peak_unrealized_pnl_sol: f64,
+    /// How far the exit (or last close, if open) sat below the peak, as a percentage
+    pub drawdown_from_peak_percent: f64,
+    /// Share of the favorable move captured at exit: 1.0 sold the top, 0.0 sold at entry
+    pub exit_efficiency: Option<f64>,
+    /// SOL that selling at the peak would have added
+    pub left_on_table_sol: Option<f64>,
+    pub avg_slippage_bps: Option<f64>,
+    pub candles_used: usize,
+}
+
+impl PositionAnalytics {
+    pub fn is_closed(&self) -> bool {
+        self.exit_time.is_some()
+    }
+
+    ///
+    /// Computes position analytics from a trade's fills and the price candles covering it.
+    ///
+    /// Candles are matched by start time, so the candle containing the entry contributes its
+    /// full range. Fill prices are also treated as observed prices.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - &TradeRecord - The trade's fills
+    /// * `candles` - &[Candle] - Candles for the token, timestamps in seconds
+    /// * `candle_secs` - i64 - Candle duration in seconds
+    /// * `now` - i64 - Evaluation time for open positions
+    ///
+    /// # Returns
+    ///
+    /// Result<PositionAnalytics, PositionAnalyticsError> - The computed analytics
+    ///
+    pub fn compute(
+        trade: &TradeRecord,
+        candles: &[Candle],
+        candle_secs: i64,
+        now: i64,
+    ) -> Result<Self, PositionAnalyticsError> {
+        let mut fills = trade.fills.clone();
+        fills.sort_by_key(|f| f.timestamp);
+        if let Some(bad) = fills
+            .iter()
+            .find(|f| !(f.amount_tokens > 0.0 && f.price_sol > 0.0))
+        {
+            return Err(PositionAnalyticsError::InvalidFill(format!(
+                "{} at {}",
+                trade.token_mint, bad.timestamp
+            )));
+        }
+
+        let entry_time = trade
+            .entry_time()
+            .ok_or_else(|| PositionAnalyticsError::NoEntry(trade.token_mint.clone()))?;
+
+        let (mut bought, mut cost, mut sold, mut proceeds, mut fees) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut slippage = Mean::default();
+        for fill in &fills {
+            match fill.side {
+                FillSide::Buy => {
+                    bought += fill.amount_tokens;
+                    cost += fill.amount_tokens * fill.price_sol;
+                }
+                FillSide::Sell => {
+                    sold += fill.amount_tokens;
+                    proceeds += fill.amount_tokens * fill.price_sol;
+                }
+            }
+            fees += fill.fee_sol;
+            if let Some(bps) = fill.slippage_bps() {
+                slippage.add_weighted(bps, fill.amount_tokens);
+            }
+        }
+
+        let avg_entry_price = cost / bought;
+        let avg_exit_price = (sold > 0.0).then(|| proceeds / sold);
+        let closed = sold >= bought * (1.0 - CLOSED_TOLERANCE);
+        let exit_time = closed.then(|| fills.last().map(|f| f.timestamp)).flatten();
+        let end = exit_time.unwrap_or(now);
+
+        let window: Vec<&Candle> = candles
+            .iter()
+            .filter(|c| c.timestamp + candle_secs > entry_time && c.timestamp <= end)
+            .collect();
+
+        let fill_prices = fills.iter().map(|f| f.price_sol);
+        let peak_price = window
+            .iter()
+            .map(|c| c.high)
+            .chain(fill_prices.clone())
+            .fold(f64::MIN, f64::max);
+        let trough_price = window
+            .iter()
+            .map(|c| c.low)
+            .chain(fill_prices)
+            .fold(f64::MAX, f64::min);
+
+        let peak_unrealized_pnl_sol = peak_unrealized(&fills, &window, candle_secs);
+
+        let reference_exit = avg_exit_price
+            .filter(|_| closed)
+            .or_else(|| window.last().map(|c| c.close))
+            .unwrap_or(avg_entry_price);
+        let favorable_move = peak_price - avg_entry_price;
+
+        Ok(Self {
+            token_mint: trade.token_mint.clone(),
+            strategy: trade.strategy_key().to_string(),
+            entry_time,
+            exit_time,
+            holding_secs: (end - entry_time).max(0),
+            tokens_bought: bought,
+            tokens_sold: sold,
+            avg_entry_price,
+            avg_exit_price,
+            realized_pnl_sol: proceeds - avg_entry_price * sold.min(bought) - fees,
+            peak_price,
+            trough_price,
+            mae_percent: ((trough_price / avg_entry_price - 1.0) * 100.0).min(0.0),
+            mfe_percent: ((peak_price / avg_entry_price - 1.0) * 100.0).max(0.0),
+            peak_unrealized_pnl_sol,
+            drawdown_from_peak_percent: ((peak_price - reference_exit) / peak_price * 100.0).max(0.0),
+            exit_efficiency: (closed && favorable_move > 0.0)
+                .then(|| (reference_exit - avg_entry_price) / favorable_move),
+            left_on_table_sol: closed.then(|| ((peak_price - reference_exit) * sold).max(0.0)),
+            avg_slippage_bps: slippage.value(),
+            candles_used: window.len(),
+        })
+    }
+}
+
+/// Highest unrealized PnL over the holding period, using the running position and cost basis
+fn peak_unrealized(fills: &[Fill], window: &[&Candle], candle_secs: i64) -> f64 {
+    let mut peak: f64 = 0.0;
+    let (mut held, mut cost_basis) = (0.0_f64, 0.0_f64);
+    let mut fill_iter = fills.iter().peekable();
+
+    let apply = |fill: &Fill, held: &mut f64, cost_basis: &mut f64| match fill.side {
+        FillSide::Buy => {
+            let new_held = *held + fill.amount_tokens;
+            *cost_basis = (*cost_basis * *held + fill.price_sol * fill.amount_tokens) / new_held;
+            *held = new_held;
+        }
+        FillSide::Sell => *held = (*held - fill.amount_tokens).max(0.0),
+    };
+
+    for candle in window {
+        let candle_end = candle.timestamp + candle_secs;
+        while let Some(fill) = fill_iter.next_if(|f| f.timestamp < candle_end) {
+            apply(fill, &mut held, &mut cost_basis);
+            peak = peak.max((fill.price_sol - cost_basis) * held);
+        }
+        peak = peak.max((candle.high - cost_basis) * held);
+    }
+    for fill in fill_iter {
+        apply(fill, &mut held, &mut cost_basis);
+        peak = peak.max((fill.price_sol - cost_basis) * held);
+    }
+    peak
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Mean {
+    sum: f64,
+    weight: f64,
+}
+
+impl Mean {
+    fn add(&mut self, value: f64) {
+        self.add_weighted(value, 1.0);
+    }
+
+    fn add_weighted(&mut self, value: f64, weight: f64) {
+        self.sum += value * weight;
+        self.weight += weight;
+    }
+
+    fn value(&self) -> Option<f64> {
+        (self.weight > 0.0).then(|| self.sum / self.weight)
+    }
+}
+
+/// Aggregate position analytics for one strategy tag
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyAnalytics {
+    pub trades: usize,
+    pub closed_trades: usize,
+    pub winners: usize,
+    pub total_realized_pnl_sol: f64,
+    pub total_left_on_table_sol: f64,
+    pub avg_holding_secs: f64,
+    pub avg_mae_percent: f64,
+    pub avg_mfe_percent: f64,
+    pub avg_drawdown_from_peak_percent: f64,
+    pub avg_exit_efficiency: Option<f64>,
+    pub avg_slippage_bps: Option<f64>,
+}
+
+impl StrategyAnalytics {
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.closed_trades > 0).then(|| self.winners as f64 / self.closed_trades as f64)
+    }
+
+    ///
+    /// Aggregates position analytics per strategy tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - &[PositionAnalytics] - Analytics for individual trades
+    ///
+    /// # Returns
+    ///
+    /// BTreeMap<String, StrategyAnalytics> - Aggregates keyed by strategy
+    ///
+    pub fn by_strategy(positions: &[PositionAnalytics]) -> BTreeMap<String, StrategyAnalytics> {
+        let mut groups: BTreeMap<String, Vec<&PositionAnalytics>> = BTreeMap::new();
+        for position in positions {
+            groups.entry(position.strategy.clone()).or_default().push(position);
+        }
+        groups
+            .into_iter()
+            .map(|(strategy, positions)| (strategy, Self::aggregate(&positions)))
+            .collect()
+    }
+
+    fn aggregate(positions: &[&PositionAnalytics]) -> Self {
+        let mut stats = Self::default();
+        let (mut holding, mut mae, mut mfe, mut drawdown) =
+            (Mean::default(), Mean::default(), Mean::default(), Mean::default());
+        let (mut efficiency, mut slippage) = (Mean::default(), Mean::default());
+
+        for position in positions {
+            stats.trades += 1;
+            stats.total_realized_pnl_sol += position.realized_pnl_sol;
+            if position.is_closed() {
+                stats.closed_trades += 1;
+                if position.realized_pnl_sol > 0.0 {
+                    stats.winners += 1;
+                }
+            }
+            stats.total_left_on_table_sol += position.left_on_table_sol.unwrap_or(0.0);
+            holding.add(position.holding_secs as f64);
+            mae.add(position.mae_percent);
+            mfe.add(position.mfe_percent);
+            drawdown.add(position.drawdown_from_peak_percent);
+            if let Some(value) = position.exit_efficiency {
+                efficiency.add(value);
+            }
+            if let Some(value) = position.avg_slippage_bps {
+                slippage.add(value);
+            }
+        }
+
+        stats.avg_holding_secs = holding.value().unwrap_or(0.0);
+        stats.avg_mae_percent = mae.value().unwrap_or(0.0);
+        stats.avg_mfe_percent = mfe.value().unwrap_or(0.0);
+        stats.avg_drawdown_from_peak_percent = drawdown.value().unwrap_or(0.0);
+        stats.avg_exit_efficiency = efficiency.value();
+        stats.avg_slippage_bps = slippage.value();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: i64, low: f64, high: f64, close: f64) -> Candle {
+        Candle { timestamp, open: close, high, low, close, volume: 0.0 }
+    }
+
+    fn fill(timestamp: i64, side: FillSide, amount_tokens: f64, price_sol: f64) -> Fill {
+        Fill {
+            signature: None,
+            timestamp,
+            side,
+            amount_tokens,
+            price_sol,
+            quoted_price_sol: None,
+            fee_sol: 0.0,
+        }
+    }
+
+    fn round_trip() -> TradeRecord {
+        TradeRecord::new("mint")
+            .with_strategy("breakout")
+            .with_fill(Fill { quoted_price_sol: Some(0.99), ..fill(100, FillSide::Buy, 100.0, 1.0) })
+            .with_fill(fill(400, FillSide::Sell, 100.0, 1.5))
+    }
+
+    #[test]
+    fn test_excursions_and_exit_efficiency() {
+        let candles = vec![
+            candle(60, 0.95, 1.05, 1.0),
+            candle(120, 0.8, 1.1, 1.0),
+            candle(180, 1.0, 2.0, 1.8),
+            candle(240, 1.4, 1.9, 1.5),
+            candle(600, 0.1, 5.0, 0.1),
+        ];
+        let stats = PositionAnalytics::compute(&round_trip(), &candles, 60, 1_000).unwrap();
+
+        assert_eq!(stats.holding_secs, 300);
+        assert_eq!(stats.candles_used, 4);
+        assert!((stats.mae_percent + 20.0).abs() < 1e-9);
+        assert!((stats.mfe_percent - 100.0).abs() < 1e-9);
+        assert!((stats.peak_unrealized_pnl_sol - 100.0).abs() < 1e-9);
+        assert!((stats.drawdown_from_peak_percent - 25.0).abs() < 1e-9);
+        assert!((stats.exit_efficiency.unwrap() - 0.5).abs() < 1e-9);
+        assert!((stats.left_on_table_sol.unwrap() - 50.0).abs() < 1e-9);
+        assert!((stats.realized_pnl_sol - 50.0).abs() < 1e-9);
+        assert!(stats.avg_slippage_bps.unwrap() > 100.0);
+    }
+
+    #[test]
+    fn test_open_position_uses_last_close() {
+        let trade = TradeRecord::new("mint").with_fill(fill(0, FillSide::Buy, 10.0, 2.0));
+        let candles = vec![candle(0, 1.5, 4.0, 3.0)];
+        let stats = PositionAnalytics::compute(&trade, &candles, 60, 120).unwrap();
+
+        assert!(!stats.is_closed());
+        assert_eq!(stats.holding_secs, 120);
+        assert_eq!(stats.strategy, UNTAGGED_STRATEGY);
+        assert!((stats.drawdown_from_peak_percent - 25.0).abs() < 1e-9);
+        assert_eq!(stats.exit_efficiency, None);
+    }
+
+    #[test]
+    fn test_no_entry_is_error() {
+        let trade = TradeRecord::new("mint").with_fill(fill(0, FillSide::Sell, 1.0, 1.0));
+        assert!(matches!(
+            PositionAnalytics::compute(&trade, &[], 60, 0),
+            Err(PositionAnalyticsError::NoEntry(_))
+        ));
+    }
+
+    #[test]
+    fn test_strategy_aggregation() {
+        let candles = vec![candle(60, 0.9, 2.0, 1.5)];
+        let win = PositionAnalytics::compute(&round_trip(), &candles, 60, 1_000).unwrap();
+        let loss_trade = TradeRecord::new("other")
+            .with_strategy("breakout")
+            .with_fill(fill(0, FillSide::Buy, 10.0, 1.0))
+            .with_fill(fill(60, FillSide::Sell, 10.0, 0.5));
+        let loss = PositionAnalytics::compute(&loss_trade, &[], 60, 1_000).unwrap();
+
+        let by_strategy = StrategyAnalytics::by_strategy(&[win, loss]);
+        let breakout = &by_strategy["breakout"];
+        assert_eq!(breakout.trades, 2);
+        assert_eq!(breakout.win_rate(), Some(0.5));
+        assert!((breakout.total_realized_pnl_sol - 45.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,550 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::market::{TimePeriod, TrendingToken};
+
+/// Protocols whose pairs trade on a bonding curve until they migrate
+const BONDING_CURVE_PROTOCOLS: &[&str] = &["Pump V1", "Moonshot", "LaunchLab", "Bonk", "Meteora DBC", "Boop"];
+
+/// A token row from the trending or search endpoints, with every field the server returns.
+///
+/// Fields are lenient because the two endpoints return overlapping but different sets: missing
+/// or null fields take their default, and numbers may arrive as floats or strings. Anything not
+/// modelled here is kept in `extra`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScreenerToken {
+    #[serde(deserialize_with = "lenient_string")]
+    pub pair_address: String,
+    #[serde(rename = "tokenAddress", deserialize_with = "lenient_string")]
+    pub mint_address: String,
+    #[serde(rename = "tokenTicker", deserialize_with = "lenient_string")]
+    pub symbol: String,
+    #[serde(rename = "tokenName", deserialize_with = "lenient_string")]
+    pub name: String,
+    #[serde(rename = "tokenImage")]
+    pub logo_uri: Option<String>,
+    #[serde(rename = "tokenDecimals")]
+    pub decimals: Option<u8>,
+    #[serde(deserialize_with = "lenient_string")]
+    pub protocol: String,
+    pub protocol_details: Option<serde_json::Value>,
+    #[serde(deserialize_with = "lenient_string")]
+    pub created_at: String,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub supply: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub liquidity_sol: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub liquidity_token: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub price_usd: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub price_sol: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub market_cap_sol: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub market_cap_percent_change: f64,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume_sol: f64,
+    #[serde(deserialize_with = "lenient_u64")]
+    pub buy_count: u64,
+    #[serde(deserialize_with = "lenient_u64")]
+    pub sell_count: u64,
+    /// Holder count, when the endpoint reports it
+    #[serde(alias = "holders")]
+    pub num_holders: Option<u64>,
+    /// Percent of supply held by the ten largest holders
+    #[serde(rename = "top10Holders", deserialize_with = "lenient_f64")]
+    pub top_10_holders_percent: f64,
+    pub dev_holds_percent: Option<f64>,
+    pub bonding_curve_percent: Option<f64>,
+    pub lp_burned: Option<f64>,
+    /// None when not reported, Some(None) when the authority has been revoked
+    #[serde(deserialize_with = "crate::models::market::nullable", skip_serializing_if = "Option::is_none")]
+    pub mint_authority: Option<Option<String>>,
+    /// None when not reported, Some(None) when the authority has been revoked
+    #[serde(deserialize_with = "crate::models::market::nullable", skip_serializing_if = "Option::is_none")]
+    pub freeze_authority: Option<Option<String>>,
+    pub website: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub discord: Option<String>,
+    /// Position in the server's listing, starting at 1
+    pub rank: u32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Reads a string that may be null or a number
+fn lenient_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(String::new()),
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(de::Error::custom(format!("expected a string, got {}", other))),
+    }
+}
+
+/// Reads a number that may be null or a numeric string
+fn lenient_f64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(0.0),
+        Value::Number(n) => n.as_f64().ok_or_else(|| de::Error::custom(format!("invalid number {}", n))),
+        Value::String(s) => s.trim().parse().map_err(|_| de::Error::custom(format!("invalid number {:?}", s))),
+        other => Err(de::Error::custom(format!("expected a number, got {}", other))),
+    }
+}
+
+/// Reads a count that may be null, a float or a numeric string
+fn lenient_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let count = lenient_f64(deserializer)?;
+    if count.is_finite() && count >= 0.0 {
+        Ok(count as u64)
+    } else {
+        Err(de::Error::custom(format!("invalid count {}", count)))
+    }
+}
+
+impl ScreenerToken {
+    /// Age in seconds at `now`, if `created_at` parses as RFC 3339
+    pub fn age_secs(&self, now: DateTime<Utc>) -> Option<i64> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .map(|created| (now - created.with_timezone(&Utc)).num_seconds())
+    }
+
+    /// Returns true once the token has left its bonding curve.
+    ///
+    /// Uses `bondingCurvePercent` when the server reports it, otherwise the protocol name.
+    pub fn is_migrated(&self) -> bool {
+        match self.bonding_curve_percent {
+            Some(percent) => percent >= 100.0,
+            None => !BONDING_CURVE_PROTOCOLS.iter().any(|p| p.eq_ignore_ascii_case(&self.protocol)),
+        }
+    }
+
+    /// Buys divided by sells; None when there were no sells
+    pub fn buy_sell_ratio(&self) -> Option<f64> {
+        (self.sell_count > 0).then(|| self.buy_count as f64 / self.sell_count as f64)
+    }
+}
+
+impl From<&ScreenerToken> for TrendingToken {
+    fn from(token: &ScreenerToken) -> Self {
+        TrendingToken {
+            mint_address: token.mint_address.clone(),
+            symbol: token.symbol.clone(),
+            name: token.name.clone(),
+            price_usd: token.price_usd,
+            market_cap_change_percent: token.market_cap_percent_change,
+            price_change_7d: token
+                .extra
+                .get("priceChange7d")
+                .and_then(|v| v.as_f64()),
+            volume_sol: token.volume_sol,
+            market_cap_sol: token.market_cap_sol,
+            holders: token.num_holders,
+            top_10_holders_percent: token.top_10_holders_percent,
+            rank: token.rank,
+            logo_uri: token.logo_uri.clone(),
+        }
+    }
+}
+
+/// Lifecycle stage used by `ScreenerFilter::stage`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStage {
+    BondingCurve,
+    Migrated,
+}
+
+/// Multi-criteria filter; unset criteria match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenerFilter {
+    pub min_market_cap_sol: Option<f64>,
+    pub max_market_cap_sol: Option<f64>,
+    pub min_volume_sol: Option<f64>,
+    pub max_volume_sol: Option<f64>,
+    pub min_liquidity_sol: Option<f64>,
+    pub min_holders: Option<u64>,
+    pub max_top_10_holders_percent: Option<f64>,
+    pub min_age_secs: Option<i64>,
+    pub max_age_secs: Option<i64>,
+    /// Allowed protocols, compared case-insensitively; empty allows all
+    pub protocols: Vec<String>,
+    pub stage: Option<TokenStage>,
+}
+
+impl ScreenerFilter {
+    /// Checks a token against every set criterion
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - &ScreenerToken - The token to check
+    /// * `now` - DateTime<Utc> - Reference time for age criteria
+    ///
+    /// # Returns
+    ///
+    /// bool - True if the token matches
+    pub fn matches(&self, token: &ScreenerToken, now: DateTime<Utc>) -> bool {
+        let at_least = |min: Option<f64>, value: f64| min.is_none_or(|min| value >= min);
+        let at_most = |max: Option<f64>, value: f64| max.is_none_or(|max| value <= max);
+
+        if !at_least(self.min_market_cap_sol, token.market_cap_sol)
+            || !at_most(self.max_market_cap_sol, token.market_cap_sol)
+            || !at_least(self.min_volume_sol, token.volume_sol)
+            || !at_most(self.max_volume_sol, token.volume_sol)
+            || !at_least(self.min_liquidity_sol, token.liquidity_sol)
+            || !at_most(self.max_top_10_holders_percent, token.top_10_holders_percent)
+        {
+            return false;
+        }
+        if self
+            .min_holders
+            .is_some_and(|min| token.num_holders.is_none_or(|holders| holders < min))
+        {
+            return false;
+        }
+        if self.min_age_secs.is_some() || self.max_age_secs.is_some() {
+            let Some(age) = token.age_secs(now) else {
+                return false;
+            };
+            if self.min_age_secs.is_some_and(|min| age < min) || self.max_age_secs.is_some_and(|max| age > max) {
+                return false;
+            }
+        }
+        if !self.protocols.is_empty() && !self.protocols.iter().any(|p| p.eq_ignore_ascii_case(&token.protocol)) {
+            return false;
+        }
+        match self.stage {
+            Some(TokenStage::Migrated) => token.is_migrated(),
+            Some(TokenStage::BondingCurve) => !token.is_migrated(),
+            None => true,
+        }
+    }
+}
+
+/// Value a listing is sorted by
+#[derive(Clone)]
+pub enum SortKey {
+    /// Keep the server's order
+    Rank,
+    MarketCap,
+    MarketCapChange,
+    Volume,
+    Liquidity,
+    Holders,
+    Top10HoldersPercent,
+    /// Newest first when descending
+    Age,
+    BuySellRatio,
+    /// Any score computed from the token
+    Custom(Arc<dyn Fn(&ScreenerToken) -> f64 + Send + Sync>),
+}
+
+impl SortKey {
+    /// Creates a custom sort key
+    pub fn custom(score: impl Fn(&ScreenerToken) -> f64 + Send + Sync + 'static) -> Self {
+        SortKey::Custom(Arc::new(score))
+    }
+
+    fn value(&self, token: &ScreenerToken) -> f64 {
+        match self {
+            SortKey::Rank => token.rank as f64,
+            SortKey::MarketCap => token.market_cap_sol,
+            SortKey::MarketCapChange => token.market_cap_percent_change,
+            SortKey::Volume => token.volume_sol,
+            SortKey::Liquidity => token.liquidity_sol,
+            SortKey::Holders => token.num_holders.unwrap_or(0) as f64,
+            SortKey::Top10HoldersPercent => token.top_10_holders_percent,
+            SortKey::Age => DateTime::parse_from_rfc3339(&token.created_at)
+                .map(|created| created.timestamp() as f64)
+                .unwrap_or(f64::MIN),
+            SortKey::BuySellRatio => match token.buy_sell_ratio() {
+                Some(ratio) => ratio,
+                // Only buys ranks above any ratio; no trades at all has nothing to rank by
+                None if token.buy_count > 0 => f64::MAX,
+                None => f64::NAN,
+            },
+            SortKey::Custom(score) => score(token),
+        }
+    }
+}
+
+impl fmt::Debug for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortKey::Rank => write!(f, "Rank"),
+            SortKey::MarketCap => write!(f, "MarketCap"),
+            SortKey::MarketCapChange => write!(f, "MarketCapChange"),
+            SortKey::Volume => write!(f, "Volume"),
+            SortKey::Liquidity => write!(f, "Liquidity"),
+            SortKey::Holders => write!(f, "Holders"),
+            SortKey::Top10HoldersPercent => write!(f, "Top10HoldersPercent"),
+            SortKey::Age => write!(f, "Age"),
+            SortKey::BuySellRatio => write!(f, "BuySellRatio"),
+            SortKey::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Where a screen gets its candidates from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScreenerSource {
+    Trending(TimePeriod),
+    Search { query: String, limit: Option<usize> },
+}
+
+/// A complete screen: source, filter, ranking and result size
+#[derive(Debug, Clone)]
+pub struct ScreenerQuery {
+    pub source: ScreenerSource,
+    pub filter: ScreenerFilter,
+    pub sort_by: SortKey,
+    pub order: SortOrder,
+    pub limit: Option<usize>,
+}
+
+impl ScreenerQuery {
+    /// Creates a query over a source that keeps the server's order and applies no filters
+    pub fn new(source: ScreenerSource) -> Self {
+        Self {
+            source,
+            filter: ScreenerFilter::default(),
+            sort_by: SortKey::Rank,
+            order: SortOrder::Ascending,
+            limit: None,
+        }
+    }
+
+    pub fn trending(period: TimePeriod) -> Self {
+        Self::new(ScreenerSource::Trending(period))
+    }
+
+    pub fn search(query: impl Into<String>, limit: Option<usize>) -> Self {
+        Self::new(ScreenerSource::Search { query: query.into(), limit })
+    }
+
+    pub fn with_filter(mut self, filter: ScreenerFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn sort_by(mut self, key: SortKey, order: SortOrder) -> Self {
+        self.sort_by = key;
+        self.order = order;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Filters, sorts and truncates a fetched listing
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - Vec<ScreenerToken> - Tokens returned by the source
+    /// * `now` - DateTime<Utc> - Reference time for age criteria
+    ///
+    /// # Returns
+    ///
+    /// Vec<ScreenerToken> - The ranked result
+    pub fn apply(&self, tokens: Vec<ScreenerToken>, now: DateTime<Utc>) -> Vec<ScreenerToken> {
+        let mut scored: Vec<(f64, ScreenerToken)> = tokens
+            .into_iter()
+            .filter(|token| self.filter.matches(token, now))
+            .map(|token| (self.sort_by.value(&token), token))
+            .collect();
+
+        // Stable sort so ties keep the server's order; tokens without a value (NaN) go last
+        scored.sort_by(|(a, _), (b, _)| match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+                match self.order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            }
+        });
+
+        let mut tokens: Vec<ScreenerToken> = scored.into_iter().map(|(_, token)| token).collect();
+        if let Some(limit) = self.limit {
+            tokens.truncate(limit);
+        }
+        tokens
+    }
+}
+
+/// Change in a watched listing between two refreshes
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScreenerEvent {
+    /// Token appeared in the listing at `position` (0-based)
+    Entered { token: ScreenerToken, position: usize },
+    /// Token dropped out; `token` is its last known row
+    Left { token: ScreenerToken },
+    /// Full listing after a refresh, emitted after the entered/left events
+    Refreshed { tokens: Vec<ScreenerToken> },
+    /// Refresh failed; the previous listing is kept
+    Error(String),
+}
+
+/// Computes entered/left events between two listings, keyed by mint address
+///
+/// # Arguments
+///
+/// * `previous` - &[ScreenerToken] - Listing from the last refresh
+/// * `current` - &[ScreenerToken] - Listing from this refresh
+///
+/// # Returns
+///
+/// Vec<ScreenerEvent> - Left events first, then entered events in listing order
+pub fn diff_listings(previous: &[ScreenerToken], current: &[ScreenerToken]) -> Vec<ScreenerEvent> {
+    let before: HashMap<&str, &ScreenerToken> = previous.iter().map(|t| (t.mint_address.as_str(), t)).collect();
+    let after: HashMap<&str, &ScreenerToken> = current.iter().map(|t| (t.mint_address.as_str(), t)).collect();
+
+    let left = previous
+        .iter()
+        .filter(|t| !after.contains_key(t.mint_address.as_str()))
+        .map(|t| ScreenerEvent::Left { token: t.clone() });
+    let entered = current
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| !before.contains_key(t.mint_address.as_str()))
+        .map(|(position, t)| ScreenerEvent::Entered { token: t.clone(), position });
+
+    left.chain(entered).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn token(mint: &str, market_cap_sol: f64, volume_sol: f64, protocol: &str, created_at: &str) -> ScreenerToken {
+        serde_json::from_value(json!({
+            "tokenAddress": mint,
+            "tokenTicker": mint.to_uppercase(),
+            "marketCapSol": market_cap_sol,
+            "volumeSol": volume_sol,
+            "protocol": protocol,
+            "createdAt": created_at,
+            "top10Holders": 20.0,
+            "bondingCurvePercent": null,
+            "someNewField": 1
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parsing_keeps_unknown_fields() {
+        let t = token("a", 1.0, 2.0, "Pump V1", "2026-01-01T00:00:00Z");
+        assert_eq!(t.top_10_holders_percent, 20.0);
+        assert_eq!(t.num_holders, None);
+        assert_eq!(t.extra.get("someNewField"), Some(&json!(1)));
+        assert!(!t.is_migrated());
+
+        let trending = TrendingToken::from(&t);
+        assert_eq!(trending.holders, None);
+        assert_eq!(trending.price_change_7d, None);
+        assert_eq!((trending.market_cap_sol, trending.volume_sol), (1.0, 2.0));
+    }
+
+    #[test]
+    fn test_filter_and_sort() {
+        let now = DateTime::parse_from_rfc3339("2026-01-02T00:00:00Z").unwrap().with_timezone(&Utc);
+        let tokens = vec![
+            token("a", 100.0, 50.0, "Pump V1", "2026-01-01T23:00:00Z"),
+            token("b", 500.0, 80.0, "Raydium CPMM", "2026-01-01T00:00:00Z"),
+            token("c", 900.0, 10.0, "Pump AMM", "2026-01-01T12:00:00Z"),
+            token("d", 50.0, 90.0, "Raydium CPMM", "2025-12-01T00:00:00Z"),
+        ];
+
+        let query = ScreenerQuery::trending(TimePeriod::OneHour)
+            .with_filter(ScreenerFilter {
+                min_market_cap_sol: Some(80.0),
+                max_age_secs: Some(2 * 86_400),
+                stage: Some(TokenStage::Migrated),
+                ..Default::default()
+            })
+            .sort_by(SortKey::Volume, SortOrder::Descending);
+        let mints: Vec<_> = query.apply(tokens.clone(), now).into_iter().map(|t| t.mint_address).collect();
+        assert_eq!(mints, vec!["b", "c"]);
+
+        let query = ScreenerQuery::trending(TimePeriod::OneHour)
+            .sort_by(SortKey::custom(|t| t.market_cap_sol / t.volume_sol.max(1.0)), SortOrder::Ascending)
+            .limit(2);
+        let mints: Vec<_> = query.apply(tokens, now).into_iter().map(|t| t.mint_address).collect();
+        assert_eq!(mints, vec!["d", "a"]);
+    }
+
+    #[test]
+    fn test_parsing_tolerates_null_and_string_fields() {
+        let t: ScreenerToken = serde_json::from_value(json!({
+            "tokenAddress": "a",
+            "createdAt": null,
+            "protocol": null,
+            "supply": "1000000000",
+            "buyCount": 12.0,
+            "sellCount": null,
+            "mintAuthority": null
+        }))
+        .unwrap();
+        assert_eq!((t.created_at.as_str(), t.protocol.as_str()), ("", ""));
+        assert_eq!(t.supply, 1e9);
+        assert_eq!((t.buy_count, t.sell_count), (12, 0));
+        assert_eq!((t.mint_authority, t.freeze_authority), (Some(None), None));
+    }
+
+    #[test]
+    fn test_buy_sell_ratio_ranks_untraded_tokens_last() {
+        let now = Utc::now();
+        let traded = |mint: &str, buys: u64, sells: u64| ScreenerToken {
+            mint_address: mint.to_string(),
+            buy_count: buys,
+            sell_count: sells,
+            ..Default::default()
+        };
+        let tokens = vec![traded("dead", 0, 0), traded("even", 5, 5), traded("buys", 3, 0), traded("sells", 1, 4)];
+
+        for (order, expected) in [
+            (SortOrder::Descending, ["buys", "even", "sells", "dead"]),
+            (SortOrder::Ascending, ["sells", "even", "buys", "dead"]),
+        ] {
+            let query = ScreenerQuery::trending(TimePeriod::OneHour).sort_by(SortKey::BuySellRatio, order);
+            let mints: Vec<_> = query.apply(tokens.clone(), now).into_iter().map(|t| t.mint_address).collect();
+            assert_eq!(mints, expected);
+        }
+    }
+
+    #[test]
+    fn test_diff_listings() {
+        let previous = vec![token("a", 1.0, 1.0, "", ""), token("b", 1.0, 1.0, "", "")];
+        let current = vec![token("b", 2.0, 1.0, "", ""), token("c", 1.0, 1.0, "", "")];
+
+        let events = diff_listings(&previous, &current);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], ScreenerEvent::Left { token } if token.mint_address == "a"));
+        assert!(matches!(&events[1], ScreenerEvent::Entered { token, position: 1 } if token.mint_address == "c"));
+    }
+}
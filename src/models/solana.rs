@@ -0,0 +1,720 @@
+use crate::utils::base58;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Number of lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Number of decimals used by native SOL
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Wrapped SOL mint address
+pub const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PubkeyError {
+    #[error("Public key cannot be empty")]
+    Empty,
+
+    #[error("Invalid base58 encoding: {0}")]
+    InvalidBase58(String),
+
+    #[error("Invalid public key length: expected 32 bytes, got {0}")]
+    InvalidLength(usize),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("Invalid amount: {0}")]
+    Invalid(String),
+
+    #[error("Amount has more than {0} decimal places")]
+    TooPrecise(u8),
+
+    #[error("Amount overflow")]
+    Overflow,
+
+    #[error("Decimals mismatch: {0} vs {1}")]
+    DecimalsMismatch(u8, u8),
+}
+
+/// A 32-byte Solana public key (wallet, mint, pair or token account address)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pubkey([u8; 32]);
+
+impl Pubkey {
+    /// Creates a public key from raw bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - [u8; 32] - The raw key bytes
+    ///
+    /// # Returns
+    ///
+    /// Pubkey - The public key
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Gets the raw key bytes
+    ///
+    /// # Returns
+    ///
+    /// &[u8; 32] - The raw key bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Checks whether a string is a valid base58 public key
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - &str - The address to check
+    ///
+    /// # Returns
+    ///
+    /// bool - True if the address decodes to exactly 32 bytes
+    pub fn is_valid(s: &str) -> bool {
+        s.parse::<Pubkey>().is_ok()
+    }
+}
+
+impl FromStr for Pubkey {
+    type Err = PubkeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(PubkeyError::Empty);
+        }
+
+        let bytes = base58::decode(s).ok_or_else(|| PubkeyError::InvalidBase58(s.to_string()))?;
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|b: Vec<u8>| PubkeyError::InvalidLength(b.len()))?;
+
+        Ok(Self(bytes))
+    }
+}
+
+impl TryFrom<&str> for Pubkey {
+    type Error = PubkeyError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<[u8; 32]> for Pubkey {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&base58::encode(&self.0))
+    }
+}
+
+impl fmt::Debug for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pubkey({})", self)
+    }
+}
+
+impl Serialize for Pubkey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pubkey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// An exact amount of native SOL expressed in lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Lamports(pub u64);
+
+impl Lamports {
+    pub const ZERO: Lamports = Lamports(0);
+
+    /// Converts a SOL amount to lamports, rounding to the nearest lamport
+    ///
+    /// # Arguments
+    ///
+    /// * `sol` - f64 - Amount of SOL
+    ///
+    /// # Returns
+    ///
+    /// Result<Lamports, AmountError> - The lamport amount
+    pub fn from_sol(sol: f64) -> Result<Self, AmountError> {
+        if !sol.is_finite() || sol < 0.0 {
+            return Err(AmountError::Invalid(sol.to_string()));
+        }
+
+        let lamports = (sol * LAMPORTS_PER_SOL as f64).round();
+        if lamports > u64::MAX as f64 {
+            return Err(AmountError::Overflow);
+        }
+
+        Ok(Self(lamports as u64))
+    }
+
+    /// Parses a decimal SOL string (e.g. "1.25") into lamports without rounding
+    ///
+    /// # Arguments
+    ///
+    /// * `sol` - &str - Decimal SOL amount
+    ///
+    /// # Returns
+    ///
+    /// Result<Lamports, AmountError> - The exact lamport amount
+    pub fn from_sol_str(sol: &str) -> Result<Self, AmountError> {
+        let amount = TokenAmount::from_ui_str(sol, SOL_DECIMALS)?;
+        u64::try_from(amount.raw).map(Self).map_err(|_| AmountError::Overflow)
+    }
+
+    /// Gets the raw lamport count
+    ///
+    /// # Returns
+    ///
+    /// u64 - Number of lamports
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Converts to SOL as a float (for display and USD estimates only)
+    ///
+    /// # Returns
+    ///
+    /// f64 - Amount of SOL
+    pub fn as_sol(self) -> f64 {
+        self.0 as f64 / LAMPORTS_PER_SOL as f64
+    }
+
+    /// Converts to a token amount with 9 decimals
+    ///
+    /// # Returns
+    ///
+    /// TokenAmount - The equivalent wrapped SOL amount
+    pub fn to_token_amount(self) -> TokenAmount {
+        TokenAmount::new(self.0 as u128, SOL_DECIMALS)
+    }
+
+    /// Adds two amounts, returning None on overflow
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Lamports - Amount to add
+    ///
+    /// # Returns
+    ///
+    /// Option<Lamports> - The sum, or None if it exceeds u64::MAX lamports
+    pub fn checked_add(self, other: Lamports) -> Option<Lamports> {
+        self.0.checked_add(other.0).map(Lamports)
+    }
+
+    /// Subtracts an amount, returning None if it would go below zero
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Lamports - Amount to subtract
+    ///
+    /// # Returns
+    ///
+    /// Option<Lamports> - The difference, or None on underflow
+    pub fn checked_sub(self, other: Lamports) -> Option<Lamports> {
+        self.0.checked_sub(other.0).map(Lamports)
+    }
+
+    /// Adds two amounts, clamping at u64::MAX lamports
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Lamports - Amount to add
+    ///
+    /// # Returns
+    ///
+    /// Lamports - The clamped sum
+    pub fn saturating_add(self, other: Lamports) -> Lamports {
+        Lamports(self.0.saturating_add(other.0))
+    }
+
+    /// Subtracts an amount, clamping at zero
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Lamports - Amount to subtract
+    ///
+    /// # Returns
+    ///
+    /// Lamports - The clamped difference
+    pub fn saturating_sub(self, other: Lamports) -> Lamports {
+        Lamports(self.0.saturating_sub(other.0))
+    }
+}
+
+impl From<u64> for Lamports {
+    fn from(lamports: u64) -> Self {
+        Self(lamports)
+    }
+}
+
+impl fmt::Display for Lamports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} SOL", self.to_token_amount())
+    }
+}
+
+impl Sum for Lamports {
+    fn sum<I: Iterator<Item = Lamports>>(iter: I) -> Lamports {
+        // Totals are display and planning figures; clamp rather than panic on overflow
+        iter.fold(Lamports::ZERO, Lamports::saturating_add)
+    }
+}
+
+/// An exact SPL token amount: the raw integer amount plus the mint's decimals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct TokenAmount {
+    #[serde(with = "raw_amount")]
+    pub raw: u128,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    /// Creates a token amount from a raw integer amount
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - u128 - Raw amount in base units
+    /// * `decimals` - u8 - Mint decimals
+    ///
+    /// # Returns
+    ///
+    /// TokenAmount - The token amount
+    pub const fn new(raw: u128, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Creates a zero amount for a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `decimals` - u8 - Mint decimals
+    ///
+    /// # Returns
+    ///
+    /// TokenAmount - A zero amount
+    pub const fn zero(decimals: u8) -> Self {
+        Self { raw: 0, decimals }
+    }
+
+    /// Parses a decimal UI amount (e.g. "1234.000001") exactly
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - &str - The decimal amount
+    /// * `decimals` - u8 - Mint decimals
+    ///
+    /// # Returns
+    ///
+    /// Result<TokenAmount, AmountError> - The exact amount, or an error if it has more precision than the mint
+    pub fn from_ui_str(s: &str, decimals: u8) -> Result<Self, AmountError> {
+        let s = s.trim();
+        let (whole, fraction) = match s.split_once('.') {
+            Some((w, f)) => (w, f),
+            None => (s, ""),
+        };
+
+        if (whole.is_empty() && fraction.is_empty())
+            || !whole.bytes().all(|b| b.is_ascii_digit())
+            || !fraction.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(AmountError::Invalid(s.to_string()));
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > decimals as usize {
+            return Err(AmountError::TooPrecise(decimals));
+        }
+
+        let scale = pow10(decimals)?;
+        let whole: u128 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| AmountError::Overflow)?
+        };
+        let fraction_raw: u128 = if fraction.is_empty() {
+            0
+        } else {
+            let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+            padded.parse().map_err(|_| AmountError::Overflow)?
+        };
+
+        let raw = whole
+            .checked_mul(scale)
+            .and_then(|w| w.checked_add(fraction_raw))
+            .ok_or(AmountError::Overflow)?;
+
+        Ok(Self { raw, decimals })
+    }
+
+    /// Converts a floating point UI amount, rounding to the nearest base unit
+    ///
+    /// # Arguments
+    ///
+    /// * `ui_amount` - f64 - The UI amount
+    /// * `decimals` - u8 - Mint decimals
+    ///
+    /// # Returns
+    ///
+    /// Result<TokenAmount, AmountError> - The rounded amount
+    pub fn from_ui_amount(ui_amount: f64, decimals: u8) -> Result<Self, AmountError> {
+        if !ui_amount.is_finite() || ui_amount < 0.0 {
+            return Err(AmountError::Invalid(ui_amount.to_string()));
+        }
+
+        let raw = (ui_amount * 10f64.powi(decimals as i32)).round();
+        if raw >= u128::MAX as f64 {
+            return Err(AmountError::Overflow);
+        }
+
+        Ok(Self { raw: raw as u128, decimals })
+    }
+
+    /// Converts to a floating point UI amount (for display and estimates only)
+    ///
+    /// # Returns
+    ///
+    /// f64 - The UI amount
+    pub fn ui_amount(&self) -> f64 {
+        self.raw as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.raw == 0
+    }
+
+    pub fn checked_add(self, other: TokenAmount) -> Result<TokenAmount, AmountError> {
+        self.ensure_same_decimals(&other)?;
+        let raw = self.raw.checked_add(other.raw).ok_or(AmountError::Overflow)?;
+        Ok(Self { raw, decimals: self.decimals })
+    }
+
+    pub fn checked_sub(self, other: TokenAmount) -> Result<TokenAmount, AmountError> {
+        self.ensure_same_decimals(&other)?;
+        let raw = self.raw.checked_sub(other.raw).ok_or(AmountError::Overflow)?;
+        Ok(Self { raw, decimals: self.decimals })
+    }
+
+    /// Scales the amount by `numerator / denominator`, rounding down
+    ///
+    /// # Arguments
+    ///
+    /// * `numerator` - u128 - Ratio numerator
+    /// * `denominator` - u128 - Ratio denominator
+    ///
+    /// # Returns
+    ///
+    /// Option<TokenAmount> - The scaled amount, or None on overflow or a zero denominator
+    pub fn checked_mul_ratio(self, numerator: u128, denominator: u128) -> Option<TokenAmount> {
+        if denominator == 0 {
+            return None;
+        }
+        let raw = self.raw.checked_mul(numerator)? / denominator;
+        Some(Self { raw, decimals: self.decimals })
+    }
+
+    fn ensure_same_decimals(&self, other: &TokenAmount) -> Result<(), AmountError> {
+        if self.decimals != other.decimals {
+            return Err(AmountError::DecimalsMismatch(self.decimals, other.decimals));
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for TokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        Some(self.raw.cmp(&other.raw))
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.raw);
+        }
+
+        let digits = format!("{:0>width$}", self.raw, width = self.decimals as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            write!(f, "{}", whole)
+        } else {
+            write!(f, "{}.{}", whole, fraction)
+        }
+    }
+}
+
+fn pow10(decimals: u8) -> Result<u128, AmountError> {
+    10u128.checked_pow(decimals as u32).ok_or(AmountError::Overflow)
+}
+
+/// Serde helpers for raw integer amounts that the API sends as numbers or strings.
+///
+/// Serializes as a string so values above 2^53 survive JavaScript consumers.
+pub mod raw_amount {
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(raw: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(raw)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        struct RawVisitor;
+
+        impl Visitor<'_> for RawVisitor {
+            type Value = u128;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a non-negative integer amount as a number or string")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<u128, E> {
+                Ok(v as u128)
+            }
+
+            fn visit_u128<E: de::Error>(self, v: u128) -> Result<u128, E> {
+                Ok(v)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<u128, E> {
+                u128::try_from(v).map_err(|_| E::custom(format!("negative amount: {}", v)))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<u128, E> {
+                if v.is_finite() && v >= 0.0 && v.fract() == 0.0 {
+                    Ok(v as u128)
+                } else {
+                    Err(E::custom(format!("invalid raw amount: {}", v)))
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<u128, E> {
+                v.parse().map_err(|_| E::custom(format!("invalid raw amount: {}", v)))
+            }
+        }
+
+        deserializer.deserialize_any(RawVisitor)
+    }
+}
+
+/// Serde helpers for `Lamports` fields that the API expresses as an amount of SOL.
+///
+/// Written as an exact decimal string so large amounts are not rounded through f64; numbers and
+/// strings are both read.
+pub mod sol_amount {
+    use super::Lamports;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(lamports: &Lamports, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&lamports.to_token_amount())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Lamports, D::Error> {
+        let sol = super::ui_amount::deserialize(deserializer)?;
+        Lamports::from_sol_str(&sol.to_string()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde helpers for `TokenAmount` fields that the API expresses as a UI amount.
+///
+/// Written as an exact decimal string so large amounts are not rounded through f64; numbers and
+/// strings are both read. The mint decimals are not on the wire; deserializing keeps the
+/// precision that was written.
+pub mod ui_amount {
+    use super::TokenAmount;
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(amount: &TokenAmount, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(amount)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TokenAmount, D::Error> {
+        struct UiVisitor;
+
+        impl Visitor<'_> for UiVisitor {
+            type Value = TokenAmount;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a non-negative decimal amount as a number or string")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<TokenAmount, E> {
+                Ok(TokenAmount::new(v as u128, 0))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<TokenAmount, E> {
+                u128::try_from(v)
+                    .map(|raw| TokenAmount::new(raw, 0))
+                    .map_err(|_| E::custom(format!("negative amount: {}", v)))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<TokenAmount, E> {
+                if !v.is_finite() {
+                    return Err(E::custom(format!("invalid amount: {}", v)));
+                }
+                self.visit_str(&v.to_string())
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<TokenAmount, E> {
+                let decimals = v.split_once('.').map_or(0, |(_, f)| f.trim().len());
+                let decimals = u8::try_from(decimals).map_err(|_| E::custom(format!("invalid amount: {}", v)))?;
+                TokenAmount::from_ui_str(v, decimals).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(UiVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pubkey_roundtrip() {
+        let key: Pubkey = WRAPPED_SOL_MINT.parse().unwrap();
+        assert_eq!(key.to_string(), WRAPPED_SOL_MINT);
+
+        let usdc: Pubkey = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".parse().unwrap();
+        assert_ne!(key, usdc);
+
+        let json = serde_json::to_string(&usdc).unwrap();
+        assert_eq!(json, "\"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v\"");
+        assert_eq!(serde_json::from_str::<Pubkey>(&json).unwrap(), usdc);
+    }
+
+    #[test]
+    fn test_pubkey_rejects_invalid() {
+        assert_eq!("".parse::<Pubkey>(), Err(PubkeyError::Empty));
+        // '0', 'O', 'I' and 'l' are not in the base58 alphabet
+        assert!(matches!(
+            "0o11111111111111111111111111111111111111112".parse::<Pubkey>(),
+            Err(PubkeyError::InvalidBase58(_))
+        ));
+        assert!(matches!(
+            "So1111111111111111111111111111111111111111l".parse::<Pubkey>(),
+            Err(PubkeyError::InvalidBase58(_))
+        ));
+        assert!(matches!(
+            "So1111111111111111111111111111111".parse::<Pubkey>(),
+            Err(PubkeyError::InvalidLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_lamports() {
+        assert_eq!(Lamports::from_sol(1.5).unwrap(), Lamports(1_500_000_000));
+        assert_eq!(Lamports::from_sol_str("0.000000001").unwrap(), Lamports(1));
+        assert!(Lamports::from_sol_str("0.0000000001").is_err());
+        assert!(Lamports::from_sol(-1.0).is_err());
+        assert_eq!(Lamports(2_500_000_000).to_string(), "2.5 SOL");
+
+        let total: Lamports = [Lamports(1), Lamports(2)].into_iter().sum();
+        assert_eq!(total, Lamports(3));
+        assert_eq!(Lamports(u64::MAX).checked_add(Lamports(1)), None);
+        assert_eq!(Lamports(1).checked_sub(Lamports(2)), None);
+        assert_eq!(Lamports(1).saturating_sub(Lamports(2)), Lamports::ZERO);
+        let clamped: Lamports = [Lamports(u64::MAX), Lamports(1)].into_iter().sum();
+        assert_eq!(clamped, Lamports(u64::MAX));
+    }
+
+    #[test]
+    fn test_token_amount_exact() {
+        let supply = TokenAmount::from_ui_str("999999999999.123456", 6).unwrap();
+        assert_eq!(supply.raw, 999_999_999_999_123_456);
+        assert_eq!(supply.to_string(), "999999999999.123456");
+
+        let one = TokenAmount::from_ui_str("0.000001", 6).unwrap();
+        let sum = supply.checked_add(one).unwrap();
+        assert_eq!(sum.to_string(), "999999999999.123457");
+
+        assert_eq!(
+            supply.checked_add(TokenAmount::new(1, 9)),
+            Err(AmountError::DecimalsMismatch(6, 9))
+        );
+        assert!(one.checked_sub(supply).is_err());
+        assert_eq!(TokenAmount::new(1_000_000, 6).to_string(), "1");
+        assert_eq!(TokenAmount::new(5, 0).to_string(), "5");
+    }
+
+    #[test]
+    fn test_token_amount_serde() {
+        let amount = TokenAmount::new(u64::MAX as u128 + 1, 9);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, r#"{"raw":"18446744073709551616","decimals":9}"#);
+        assert_eq!(serde_json::from_str::<TokenAmount>(&json).unwrap(), amount);
+
+        let numeric: TokenAmount = serde_json::from_str(r#"{"raw":42,"decimals":6}"#).unwrap();
+        assert_eq!(numeric.raw, 42);
+    }
+
+    #[test]
+    fn test_ui_amount_serde() {
+        #[derive(Serialize, Deserialize)]
+        struct Order {
+            #[serde(with = "ui_amount")]
+            amount: TokenAmount,
+        }
+
+        let order = Order { amount: TokenAmount::new(1_500_000, 6) };
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(json, r#"{"amount":"1.5"}"#);
+
+        let parsed: Order = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.amount, TokenAmount::new(15, 1));
+        let parsed: Order = serde_json::from_str(r#"{"amount":"0.000001"}"#).unwrap();
+        assert_eq!(parsed.amount, TokenAmount::new(1, 6));
+        assert!(serde_json::from_str::<Order>(r#"{"amount":-1.0}"#).is_err());
+
+        // Well above 2^53, where f64 can no longer hold every integer
+        let large = Order { amount: TokenAmount::new(123_456_789_012_345_678_901, 6) };
+        let json = serde_json::to_string(&large).unwrap();
+        assert_eq!(json, r#"{"amount":"123456789012345.678901"}"#);
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap().amount, large.amount);
+    }
+
+    #[test]
+    fn test_sol_amount_serde_is_exact() {
+        #[derive(Serialize, Deserialize)]
+        struct Order {
+            #[serde(with = "sol_amount")]
+            amount: Lamports,
+        }
+
+        let large = Order { amount: Lamports(u64::MAX) };
+        let json = serde_json::to_string(&large).unwrap();
+        assert_eq!(json, r#"{"amount":"18446744073.709551615"}"#);
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap().amount, Lamports(u64::MAX));
+        assert_eq!(serde_json::from_str::<Order>(r#"{"amount":1.5}"#).unwrap().amount, Lamports(1_500_000_000));
+        assert!(serde_json::from_str::<Order>(r#"{"amount":"0.0000000001"}"#).is_err());
+    }
+}
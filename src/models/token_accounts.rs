@@ -0,0 +1,422 @@
+use crate::models::solana::{Lamports, Pubkey, TokenAmount};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// SPL Token program id
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// SPL Token-2022 program id
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Rent-exempt minimum for a 165-byte SPL token account
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: Lamports = Lamports(2_039_280);
+
+/// SPL token instruction tags
+const BURN_INSTRUCTION: u8 = 8;
+const CLOSE_ACCOUNT_INSTRUCTION: u8 = 9;
+
+/// Token program that owns a token account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum TokenProgram {
+    #[default]
+    #[serde(rename = "token", alias = "spl-token", alias = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    Token,
+    #[serde(rename = "token-2022", alias = "spl-token-2022", alias = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb")]
+    Token2022,
+}
+
+impl TokenProgram {
+    /// Gets the on-chain program id
+    ///
+    /// # Returns
+    ///
+    /// Pubkey - The program id
+    pub fn program_id(&self) -> Pubkey {
+        let id = match self {
+            TokenProgram::Token => TOKEN_PROGRAM_ID,
+            TokenProgram::Token2022 => TOKEN_2022_PROGRAM_ID,
+        };
+        id.parse().expect("token program ids are valid base58")
+    }
+}
+
+/// A single SPL token account owned by a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccount {
+    #[serde(alias = "tokenAccount", alias = "pubkey")]
+    pub address: Pubkey,
+    #[serde(alias = "tokenAddress")]
+    pub mint: Pubkey,
+    #[serde(alias = "balanceRaw", with = "crate::models::solana::raw_amount")]
+    pub amount: u128,
+    #[serde(alias = "tokenDecimals")]
+    pub decimals: u8,
+    #[serde(default, alias = "programId")]
+    pub program: TokenProgram,
+    #[serde(default = "default_rent", alias = "lamports")]
+    pub rent_lamports: Lamports,
+    #[serde(default, alias = "tokenTicker")]
+    pub symbol: Option<String>,
+}
+
+fn default_rent() -> Lamports {
+    TOKEN_ACCOUNT_RENT_LAMPORTS
+}
+
+impl TokenAccount {
+    /// Gets the exact balance of the account
+    ///
+    /// # Returns
+    ///
+    /// TokenAmount - The balance with mint decimals
+    pub fn token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.amount, self.decimals)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amount == 0
+    }
+}
+
+/// Token accounts for one wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletTokenAccounts {
+    pub owner: Pubkey,
+    pub accounts: Vec<TokenAccount>,
+}
+
+impl WalletTokenAccounts {
+    /// Gets the total rent locked in all token accounts
+    ///
+    /// # Returns
+    ///
+    /// Lamports - Sum of account rent
+    pub fn total_rent(&self) -> Lamports {
+        self.accounts.iter().map(|a| a.rent_lamports).sum()
+    }
+}
+
+/// Account metadata for a Solana instruction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// A ready-to-sign token program instruction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl TokenInstruction {
+    /// Builds an SPL `Burn` instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - TokenProgram - Program owning the account
+    /// * `account` - Pubkey - Token account to burn from
+    /// * `mint` - Pubkey - Token mint
+    /// * `owner` - Pubkey - Account owner (signer)
+    /// * `amount` - u64 - Raw amount to burn
+    ///
+    /// # Returns
+    ///
+    /// TokenInstruction - The burn instruction
+    pub fn burn(program: TokenProgram, account: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        let mut data = Vec::with_capacity(9);
+        data.push(BURN_INSTRUCTION);
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        Self {
+            program_id: program.program_id(),
+            accounts: vec![
+                AccountMeta { pubkey: account, is_signer: false, is_writable: true },
+                AccountMeta { pubkey: mint, is_signer: false, is_writable: true },
+                AccountMeta { pubkey: owner, is_signer: true, is_writable: false },
+            ],
+            data,
+        }
+    }
+
+    /// Builds an SPL `CloseAccount` instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - TokenProgram - Program owning the account
+    /// * `account` - Pubkey - Token account to close
+    /// * `destination` - Pubkey - Receiver of the reclaimed rent
+    /// * `owner` - Pubkey - Account owner (signer)
+    ///
+    /// # Returns
+    ///
+    /// TokenInstruction - The close instruction
+    pub fn close_account(program: TokenProgram, account: Pubkey, destination: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            program_id: program.program_id(),
+            accounts: vec![
+                AccountMeta { pubkey: account, is_signer: false, is_writable: true },
+                AccountMeta { pubkey: destination, is_signer: false, is_writable: true },
+                AccountMeta { pubkey: owner, is_signer: true, is_writable: false },
+            ],
+            data: vec![CLOSE_ACCOUNT_INSTRUCTION],
+        }
+    }
+}
+
+/// What to do with a token account during cleanup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TokenAccountOperation {
+    /// Close an account that already holds zero tokens
+    Close { account: Pubkey, program: TokenProgram, rent: Lamports },
+    /// Burn the remaining dust, then close the account
+    BurnAndClose {
+        account: Pubkey,
+        mint: Pubkey,
+        program: TokenProgram,
+        amount: u64,
+        rent: Lamports,
+    },
+}
+
+impl TokenAccountOperation {
+    /// Gets the rent reclaimed by this operation
+    ///
+    /// # Returns
+    ///
+    /// Lamports - Reclaimed rent
+    pub fn rent(&self) -> Lamports {
+        match self {
+            TokenAccountOperation::Close { rent, .. } => *rent,
+            TokenAccountOperation::BurnAndClose { rent, .. } => *rent,
+        }
+    }
+
+    /// Builds the instructions for this operation
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Pubkey - Account owner (signer)
+    /// * `destination` - Pubkey - Receiver of the reclaimed rent
+    ///
+    /// # Returns
+    ///
+    /// Vec<TokenInstruction> - One close, or a burn followed by a close
+    pub fn instructions(&self, owner: Pubkey, destination: Pubkey) -> Vec<TokenInstruction> {
+        match *self {
+            TokenAccountOperation::Close { account, program, .. } => {
+                vec![TokenInstruction::close_account(program, account, destination, owner)]
+            }
+            TokenAccountOperation::BurnAndClose { account, mint, program, amount, .. } => vec![
+                TokenInstruction::burn(program, account, mint, owner, amount),
+                TokenInstruction::close_account(program, account, destination, owner),
+            ],
+        }
+    }
+
+    fn instruction_count(&self) -> usize {
+        match self {
+            TokenAccountOperation::Close { .. } => 1,
+            TokenAccountOperation::BurnAndClose { .. } => 2,
+        }
+    }
+}
+
+/// Options controlling which accounts are closed and how they are batched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupOptions {
+    /// Burn and close accounts whose UI balance is at or below this amount
+    pub burn_dust_below: Option<f64>,
+    /// Mints that must never be burned or closed
+    pub exclude_mints: HashSet<Pubkey>,
+    /// Maximum instructions per transaction
+    pub max_instructions_per_batch: usize,
+    /// Receiver of reclaimed rent (defaults to the owner)
+    pub rent_destination: Option<Pubkey>,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> Self {
+        Self {
+            burn_dust_below: None,
+            exclude_mints: HashSet::new(),
+            max_instructions_per_batch: 20,
+            rent_destination: None,
+        }
+    }
+}
+
+/// A group of operations that fits in one transaction
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupBatch {
+    pub operations: Vec<TokenAccountOperation>,
+    pub instructions: Vec<TokenInstruction>,
+    pub rent_reclaimed: Lamports,
+}
+
+/// Batched close/burn plan for a wallet's token accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupPlan {
+    pub owner: Pubkey,
+    pub batches: Vec<CleanupBatch>,
+    pub total_rent_reclaimed: Lamports,
+    pub accounts_closed: usize,
+    pub accounts_burned: usize,
+}
+
+impl CleanupPlan {
+    /// Builds a cleanup plan for empty and dust token accounts
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Pubkey - Wallet that owns the accounts
+    /// * `accounts` - &[TokenAccount] - The wallet's token accounts
+    /// * `options` - &CleanupOptions - Dust threshold, exclusions and batch size
+    ///
+    /// # Returns
+    ///
+    /// CleanupPlan - The batched plan (empty if nothing qualifies)
+    pub fn build(owner: Pubkey, accounts: &[TokenAccount], options: &CleanupOptions) -> Self {
+        let destination = options.rent_destination.unwrap_or(owner);
+        let max_instructions = options.max_instructions_per_batch.max(2);
+
+        let operations = accounts
+            .iter()
+            .filter(|a| !options.exclude_mints.contains(&a.mint))
+            .filter_map(|a| Self::operation_for(a, options));
+
+        let mut batches: Vec<CleanupBatch> = Vec::new();
+        let mut current = CleanupBatch::default();
+        let mut accounts_closed = 0;
+        let mut accounts_burned = 0;
+
+        for operation in operations {
+            if current.instructions.len() + operation.instruction_count() > max_instructions {
+                batches.push(std::mem::take(&mut current));
+            }
+
+            accounts_closed += 1;
+            if matches!(operation, TokenAccountOperation::BurnAndClose { .. }) {
+                accounts_burned += 1;
+            }
+            current.rent_reclaimed = current.rent_reclaimed.saturating_add(operation.rent());
+            current.instructions.extend(operation.instructions(owner, destination));
+            current.operations.push(operation);
+        }
+
+        if !current.operations.is_empty() {
+            batches.push(current);
+        }
+
+        let total_rent_reclaimed = batches.iter().map(|b| b.rent_reclaimed).sum();
+
+        Self {
+            owner,
+            batches,
+            total_rent_reclaimed,
+            accounts_closed,
+            accounts_burned,
+        }
+    }
+
+    fn operation_for(account: &TokenAccount, options: &CleanupOptions) -> Option<TokenAccountOperation> {
+        if account.is_empty() {
+            return Some(TokenAccountOperation::Close {
+                account: account.address,
+                program: account.program,
+                rent: account.rent_lamports,
+            });
+        }
+
+        let threshold = options.burn_dust_below?;
+        if account.token_amount().ui_amount() > threshold {
+            return None;
+        }
+
+        Some(TokenAccountOperation::BurnAndClose {
+            account: account.address,
+            mint: account.mint,
+            program: account.program,
+            amount: u64::try_from(account.amount).ok()?,
+            rent: account.rent_lamports,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> Pubkey {
+        Pubkey::new([seed; 32])
+    }
+
+    fn account(seed: u8, amount: u128, program: TokenProgram) -> TokenAccount {
+        TokenAccount {
+            address: key(seed),
+            mint: key(seed.wrapping_add(100)),
+            amount,
+            decimals: 6,
+            program,
+            rent_lamports: TOKEN_ACCOUNT_RENT_LAMPORTS,
+            symbol: None,
+        }
+    }
+
+    #[test]
+    fn test_closes_empty_and_burns_dust() {
+        let owner = key(1);
+        let accounts = vec![
+            account(10, 0, TokenProgram::Token),
+            account(11, 500, TokenProgram::Token2022),
+            account(12, 5_000_000, TokenProgram::Token),
+        ];
+        let options = CleanupOptions {
+            burn_dust_below: Some(0.01),
+            ..Default::default()
+        };
+
+        let plan = CleanupPlan::build(owner, &accounts, &options);
+        assert_eq!(plan.accounts_closed, 2);
+        assert_eq!(plan.accounts_burned, 1);
+        assert_eq!(plan.total_rent_reclaimed, Lamports(2 * 2_039_280));
+
+        let instructions = &plan.batches[0].instructions;
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0].data, vec![9]);
+        assert_eq!(instructions[1].data[0], 8);
+        assert_eq!(u64::from_le_bytes(instructions[1].data[1..].try_into().unwrap()), 500);
+        assert_eq!(instructions[1].program_id, TokenProgram::Token2022.program_id());
+    }
+
+    #[test]
+    fn test_batches_keep_burn_and_close_together() {
+        let accounts: Vec<_> = (0..5).map(|i| account(10 + i, 1, TokenProgram::Token)).collect();
+        let options = CleanupOptions {
+            burn_dust_below: Some(1.0),
+            max_instructions_per_batch: 5,
+            ..Default::default()
+        };
+
+        let plan = CleanupPlan::build(key(1), &accounts, &options);
+        assert_eq!(plan.batches.len(), 3);
+        assert!(plan.batches.iter().all(|b| b.instructions.len() % 2 == 0));
+        assert_eq!(plan.accounts_closed, 5);
+    }
+
+    #[test]
+    fn test_excluded_mints_are_skipped() {
+        let accounts = vec![account(10, 0, TokenProgram::Token)];
+        let mut options = CleanupOptions::default();
+        options.exclude_mints.insert(accounts[0].mint);
+
+        let plan = CleanupPlan::build(key(1), &accounts, &options);
+        assert!(plan.batches.is_empty());
+        assert_eq!(plan.total_rent_reclaimed, Lamports::ZERO);
+    }
+}
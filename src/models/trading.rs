@@ -1,26 +1,36 @@
+use crate::models::solana::{Lamports, Pubkey, TokenAmount};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuyOrderRequest {
-    pub token_mint: String,
-    pub amount_sol: f64,
+    pub token_mint: Pubkey,
+    #[serde(with = "crate::models::solana::sol_amount")]
+    pub amount_sol: Lamports,
     pub slippage_percent: f64,
     pub priority_fee: Option<f64>,
+    /// Wallet to execute from; `None` uses the account's active wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_address: Option<Pubkey>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SellOrderRequest {
-    pub token_mint: String,
-    pub amount_tokens: f64,
+    pub token_mint: Pubkey,
+    #[serde(with = "crate::models::solana::ui_amount")]
+    pub amount_tokens: TokenAmount,
     pub slippage_percent: f64,
     pub priority_fee: Option<f64>,
+    /// Wallet to execute from; `None` uses the account's active wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_address: Option<Pubkey>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapOrderRequest {
-    pub from_mint: String,
-    pub to_mint: String,
-    pub amount: f64,
+    pub from_mint: Pubkey,
+    pub to_mint: Pubkey,
+    #[serde(with = "crate::models::solana::ui_amount")]
+    pub amount: TokenAmount,
     pub slippage_percent: f64,
     pub priority_fee: Option<f64>,
 }
@@ -58,9 +68,10 @@ pub enum OrderType {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuoteRequest {
-    pub input_mint: String,
-    pub output_mint: String,
-    pub amount: f64,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    #[serde(with = "crate::models::solana::ui_amount")]
+    pub amount: TokenAmount,
     pub slippage_percent: f64,
 }
 
@@ -99,5 +110,27 @@ pub struct TradingLimits {
     pub max_sol_amount: f64,
     pub max_slippage_percent: f64,
     pub default_slippage_percent: f64,
-    pub priority_fee_lamports: u64,
+    pub priority_fee_lamports: Lamports,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_names_its_wallet_only_when_set() {
+        let wallet: Pubkey = "DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK".parse().unwrap();
+        let mut request = BuyOrderRequest {
+            token_mint: "So11111111111111111111111111111111111111112".parse().unwrap(),
+            amount_sol: Lamports::from_sol(0.5).unwrap(),
+            slippage_percent: 5.0,
+            priority_fee: None,
+            wallet_address: None,
+        };
+        assert!(serde_json::to_value(&request).unwrap().get("wallet_address").is_none());
+
+        request.wallet_address = Some(wallet);
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["wallet_address"], "DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod reference;
+pub mod sources;
+
+pub use reference::{DepegStatus, PricingError, ReferenceAsset, ReferencePriceConfig, ReferencePrices, ReferenceQuote};
+pub use sources::{AxiomPriceSource, FixedPriceSource, HyperliquidPriceSource, PriceSource};
\ No newline at end of file
@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::api::hyperliquid::HyperliquidClient;
+use crate::api::market_data::{MarketDataClient, MarketDataError};
+use crate::pricing::sources::{AxiomPriceSource, HyperliquidPriceSource, PriceSource};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PricingError {
+    #[error("Price source {0} failed: {1}")]
+    Source(String, String),
+
+    #[error("Not enough prices for {asset}: {available} available, {required} required")]
+    InsufficientSources {
+        asset: ReferenceAsset,
+        available: usize,
+        required: usize,
+    },
+
+    #[error("No price sources registered")]
+    NoSources,
+
+    #[error("No reference price service configured")]
+    NotConfigured,
+}
+
+/// Assets tracked as reference prices, all quoted in USD
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceAsset {
+    Sol,
+    Usdc,
+    Usdt,
+}
+
+impl ReferenceAsset {
+    pub const ALL: [ReferenceAsset; 3] = [ReferenceAsset::Sol, ReferenceAsset::Usdc, ReferenceAsset::Usdt];
+
+    pub fn mint(&self) -> &'static str {
+        match self {
+            ReferenceAsset::Sol => "So11111111111111111111111111111111111111112",
+            ReferenceAsset::Usdc => "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            ReferenceAsset::Usdt => "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+        }
+    }
+
+    /// Hyperliquid perp symbol, if listed
+    pub fn hyperliquid_coin(&self) -> Option<&'static str> {
+        match self {
+            ReferenceAsset::Sol => Some("SOL"),
+            ReferenceAsset::Usdc | ReferenceAsset::Usdt => None,
+        }
+    }
+
+    pub fn is_stablecoin(&self) -> bool {
+        matches!(self, ReferenceAsset::Usdc | ReferenceAsset::Usdt)
+    }
+}
+
+impl std::fmt::Display for ReferenceAsset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReferenceAsset::Sol => write!(f, "SOL"),
+            ReferenceAsset::Usdc => write!(f, "USDC"),
+            ReferenceAsset::Usdt => write!(f, "USDT"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReferencePriceConfig {
+    /// Quotes older than this are refreshed before use
+    pub max_age: Duration,
+    /// Prices further than this from the median are rejected, with three or more sources
+    pub max_deviation_percent: f64,
+    /// Minimum accepted prices for a quote
+    pub min_sources: usize,
+    /// Stablecoin deviation from $1 reported as a depeg
+    pub depeg_threshold_percent: f64,
+}
+
+impl Default for ReferencePriceConfig {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_secs(30),
+            max_deviation_percent: 2.0,
+            min_sources: 1,
+            depeg_threshold_percent: 0.5,
+        }
+    }
+}
+
+/// One source's price for an asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcePrice {
+    pub source: String,
+    pub price: f64,
+}
+
+/// Aggregated reference price
+#[derive(Debug, Clone)]
+pub struct ReferenceQuote {
+    pub asset: ReferenceAsset,
+    /// Median of the accepted prices
+    pub price: f64,
+    pub accepted: Vec<SourcePrice>,
+    /// Prices rejected as outliers
+    pub rejected: Vec<SourcePrice>,
+    /// Sources that errored, with the error message
+    pub failed: Vec<(String, String)>,
+    /// Unix timestamp of the refresh
+    pub timestamp: i64,
+    fetched_at: Instant,
+}
+
+impl ReferenceQuote {
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+}
+
+/// How far a stablecoin trades from $1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepegStatus {
+    pub asset: ReferenceAsset,
+    pub price: f64,
+    /// Signed deviation from $1 in percent
+    pub deviation_percent: f64,
+    pub depegged: bool,
+}
+
+/// SOL/USD and stablecoin reference prices aggregated from several sources.
+///
+/// Every refresh queries all sources concurrently and takes the median. With three or more
+/// prices, those further than `max_deviation_percent` from the median are rejected and the
+/// median is recomputed; with fewer there is no majority to judge outliers by.
+pub struct ReferencePrices {
+    sources: Vec<Arc<dyn PriceSource>>,
+    config: ReferencePriceConfig,
+    quotes: RwLock<HashMap<ReferenceAsset, ReferenceQuote>>,
+}
+
+impl ReferencePrices {
+    /// Creates a service without sources; register them with `with_source`
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - ReferencePriceConfig - Staleness, outlier and depeg settings
+    ///
+    /// # Returns
+    ///
+    /// ReferencePrices - A new service
+    pub fn new(config: ReferencePriceConfig) -> Self {
+        Self {
+            sources: Vec::new(),
+            config,
+            quotes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a service over the Axiom price endpoint and Hyperliquid mids
+    ///
+    /// # Returns
+    ///
+    /// Result<ReferencePrices, MarketDataError> - A new service
+    pub fn with_default_sources() -> Result<Self, MarketDataError> {
+        Ok(Self::new(ReferencePriceConfig::default())
+            .with_source(AxiomPriceSource::new(MarketDataClient::new()?))
+            .with_source(HyperliquidPriceSource::new(HyperliquidClient::new())))
+    }
+
+    pub fn with_source(mut self, source: impl PriceSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    pub fn config(&self) -> &ReferencePriceConfig {
+        &self.config
+    }
+
+    pub fn source_names(&self) -> Vec<&str> {
+        self.sources.iter().map(|s| s.name()).collect()
+    }
+
+    /// Queries every source and stores a new quote
+    ///
+    /// # Arguments
+    ///
+    /// * `asset` - ReferenceAsset - The asset to refresh
+    ///
+    /// # Returns
+    ///
+    /// Result<ReferenceQuote, PricingError> - The new quote
+    pub async fn refresh(&self, asset: ReferenceAsset) -> Result<ReferenceQuote, PricingError> {
+        if self.sources.is_empty() {
+            return Err(PricingError::NoSources);
+        }
+
+        let results = join_all(self.sources.iter().map(|source| async move {
+            (source.name().to_string(), source.fetch(asset).await)
+        }))
+        .await;
+
+        let mut prices = Vec::new();
+        let mut failed = Vec::new();
+        for (source, result) in results {
+            match result {
+                Ok(Some(price)) if price.is_finite() && price > 0.0 => prices.push(SourcePrice { source, price }),
+                Ok(Some(price)) => failed.push((source, format!("invalid price {}", price))),
+                Ok(None) => {}
+                Err(e) => failed.push((source, e.to_string())),
+            }
+        }
+
+        let (accepted, rejected) = reject_outliers(prices, self.config.max_deviation_percent);
+        if accepted.len() < self.config.min_sources.max(1) {
+            return Err(PricingError::InsufficientSources {
+                asset,
+                available: accepted.len(),
+                required: self.config.min_sources.max(1),
+            });
+        }
+
+        let quote = ReferenceQuote {
+            asset,
+            price: median(accepted.iter().map(|p| p.price).collect()).unwrap_or_default(),
+            accepted,
+            rejected,
+            failed,
+            timestamp: chrono::Utc::now().timestamp(),
+            fetched_at: Instant::now(),
+        };
+        self.quotes.write().await.insert(asset, quote.clone());
+        Ok(quote)
+    }
+
+    /// Gets a quote, refreshing it if missing or older than `max_age`
+    ///
+    /// # Arguments
+    ///
+    /// * `asset` - ReferenceAsset - The asset to price
+    ///
+    /// # Returns
+    ///
+    /// Result<ReferenceQuote, PricingError> - A fresh quote
+    pub async fn quote(&self, asset: ReferenceAsset) -> Result<ReferenceQuote, PricingError> {
+        if let Some(quote) = self.latest(asset).await
+            && !quote.is_stale(self.config.max_age)
+        {
+            return Ok(quote);
+        }
+        self.refresh(asset).await
+    }
+
+    /// Gets the last stored quote, however old
+    pub async fn latest(&self, asset: ReferenceAsset) -> Option<ReferenceQuote> {
+        self.quotes.read().await.get(&asset).cloned()
+    }
+
+    /// Gets a USD price, refreshing it if stale
+    pub async fn price(&self, asset: ReferenceAsset) -> Result<f64, PricingError> {
+        Ok(self.quote(asset).await?.price)
+    }
+
+    /// Gets a price, falling back to the last stored quote if the refresh fails
+    ///
+    /// # Arguments
+    ///
+    /// * `asset` - ReferenceAsset - The asset to price
+    ///
+    /// # Returns
+    ///
+    /// Result<f64, PricingError> - The price, or the refresh error if nothing is stored
+    pub async fn price_or_stale(&self, asset: ReferenceAsset) -> Result<f64, PricingError> {
+        match self.quote(asset).await {
+            Ok(quote) => Ok(quote.price),
+            Err(e) => self.latest(asset).await.map(|quote| quote.price).ok_or(e),
+        }
+    }
+
+    pub async fn sol_usd(&self) -> Result<f64, PricingError> {
+        self.price(ReferenceAsset::Sol).await
+    }
+
+    /// Converts a SOL amount to USD at the reference price
+    ///
+    /// # Arguments
+    ///
+    /// * `sol` - f64 - Amount in SOL
+    ///
+    /// # Returns
+    ///
+    /// Result<f64, PricingError> - Value in USD
+    pub async fn sol_to_usd(&self, sol: f64) -> Result<f64, PricingError> {
+        Ok(sol * self.price_or_stale(ReferenceAsset::Sol).await?)
+    }
+
+    /// Checks a stablecoin against its $1 peg
+    ///
+    /// # Arguments
+    ///
+    /// * `asset` - ReferenceAsset - `Usdc` or `Usdt`
+    ///
+    /// # Returns
+    ///
+    /// Result<DepegStatus, PricingError> - Deviation from the peg
+    pub async fn depeg(&self, asset: ReferenceAsset) -> Result<DepegStatus, PricingError> {
+        let price = self.price(asset).await?;
+        let deviation_percent = (price - 1.0) * 100.0;
+        Ok(DepegStatus {
+            asset,
+            price,
+            deviation_percent,
+            depegged: deviation_percent.abs() > self.config.depeg_threshold_percent,
+        })
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Splits prices into accepted and rejected around the median
+fn reject_outliers(prices: Vec<SourcePrice>, max_deviation_percent: f64) -> (Vec<SourcePrice>, Vec<SourcePrice>) {
+    if prices.len() < 3 {
+        return (prices, Vec::new());
+    }
+    let Some(mid) = median(prices.iter().map(|p| p.price).collect()) else {
+        return (prices, Vec::new());
+    };
+    prices
+        .into_iter()
+        .partition(|p| ((p.price - mid) / mid * 100.0).abs() <= max_deviation_percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::sources::FixedPriceSource;
+
+    fn service(prices: &[f64]) -> ReferencePrices {
+        prices.iter().enumerate().fold(
+            ReferencePrices::new(ReferencePriceConfig::default()),
+            |service, (i, price)| {
+                service.with_source(FixedPriceSource::new(format!("s{}", i)).with_price(ReferenceAsset::Sol, *price))
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_median_with_outlier_rejection() {
+        let prices = service(&[150.0, 151.0, 100.0, 149.0]);
+        let quote = prices.refresh(ReferenceAsset::Sol).await.unwrap();
+        assert_eq!(quote.price, 150.0);
+        assert_eq!(quote.rejected, vec![SourcePrice { source: "s2".to_string(), price: 100.0 }]);
+        assert_eq!(prices.sol_to_usd(2.0).await.unwrap(), 300.0);
+
+        // Two sources cannot outvote each other, so both are kept
+        let quote = service(&[150.0, 100.0]).refresh(ReferenceAsset::Sol).await.unwrap();
+        assert_eq!((quote.price, quote.rejected.len()), (125.0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_missing_coverage_and_depeg() {
+        let prices = service(&[150.0]).with_source(
+            FixedPriceSource::new("stables").with_price(ReferenceAsset::Usdc, 0.99),
+        );
+        let depeg = prices.depeg(ReferenceAsset::Usdc).await.unwrap();
+        assert!(depeg.depegged);
+        assert!((depeg.deviation_percent + 1.0).abs() < 1e-9);
+
+        assert_eq!(
+            prices.price(ReferenceAsset::Usdt).await,
+            Err(PricingError::InsufficientSources { asset: ReferenceAsset::Usdt, available: 0, required: 1 })
+        );
+        assert!(!prices.latest(ReferenceAsset::Usdc).await.unwrap().is_stale(Duration::from_secs(30)));
+    }
+}
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::api::hyperliquid::HyperliquidClient;
+use crate::api::market_data::MarketDataClient;
+use crate::pricing::reference::{PricingError, ReferenceAsset};
+
+/// A source of USD reference prices.
+///
+/// Implement this to register additional feeds with `ReferencePrices`. Return `Ok(None)` for
+/// assets the source does not cover.
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Name used in quotes and error reports
+    fn name(&self) -> &str;
+
+    /// Fetches the current USD price of `asset`
+    ///
+    /// # Arguments
+    ///
+    /// * `asset` - ReferenceAsset - The asset to price
+    ///
+    /// # Returns
+    ///
+    /// Result<Option<f64>, PricingError> - The price, or None if not covered
+    async fn fetch(&self, asset: ReferenceAsset) -> Result<Option<f64>, PricingError>;
+}
+
+/// Axiom `/price` endpoint, priced by mint
+pub struct AxiomPriceSource {
+    client: Mutex<MarketDataClient>,
+}
+
+impl AxiomPriceSource {
+    pub fn new(client: MarketDataClient) -> Self {
+        Self {
+            client: Mutex::new(client),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceSource for AxiomPriceSource {
+    fn name(&self) -> &str {
+        "axiom"
+    }
+
+    async fn fetch(&self, asset: ReferenceAsset) -> Result<Option<f64>, PricingError> {
+        let price = self
+            .client
+            .lock()
+            .await
+            .get_token_price(asset.mint())
+            .await
+            .map_err(|e| PricingError::Source(self.name().to_string(), e.to_string()))?;
+        Ok(Some(price.price_usd))
+    }
+}
+
+/// Hyperliquid `allMids`; only covers assets listed as perps, which excludes the stablecoins
+pub struct HyperliquidPriceSource {
+    client: HyperliquidClient,
+}
+
+impl HyperliquidPriceSource {
+    pub fn new(client: HyperliquidClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl PriceSource for HyperliquidPriceSource {
+    fn name(&self) -> &str {
+        "hyperliquid"
+    }
+
+    async fn fetch(&self, asset: ReferenceAsset) -> Result<Option<f64>, PricingError> {
+        let Some(coin) = asset.hyperliquid_coin() else {
+            return Ok(None);
+        };
+        let mids = self
+            .client
+            .get_all_mids()
+            .await
+            .map_err(|e| PricingError::Source(self.name().to_string(), e.to_string()))?;
+        Ok(mids.get(coin).map(|mid| mid.to_f64()))
+    }
+}
+
+/// Fixed prices, for tests, offline use or a manual override
+pub struct FixedPriceSource {
+    name: String,
+    prices: Vec<(ReferenceAsset, f64)>,
+}
+
+impl FixedPriceSource {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            prices: Vec::new(),
+        }
+    }
+
+    pub fn with_price(mut self, asset: ReferenceAsset, price: f64) -> Self {
+        self.prices.retain(|(a, _)| *a != asset);
+        self.prices.push((asset, price));
+        self
+    }
+}
+
+#[async_trait]
+impl PriceSource for FixedPriceSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn fetch(&self, asset: ReferenceAsset) -> Result<Option<f64>, PricingError> {
+        Ok(self.prices.iter().find(|(a, _)| *a == asset).map(|(_, price)| *price))
+    }
+}
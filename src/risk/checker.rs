@@ -0,0 +1,54 @@
+use crate::api::market_data::{MarketDataClient, MarketDataError};
+use crate::risk::scoring::{RiskConfig, TokenRiskReport};
+
+pub struct RiskChecker {
+    market_data: MarketDataClient,
+    config: RiskConfig,
+}
+
+impl RiskChecker {
+    /// Creates a risk checker with the default rule set
+    ///
+    /// # Returns
+    ///
+    /// Result<RiskChecker, MarketDataError> - A new risk checker instance
+    pub fn new() -> Result<Self, MarketDataError> {
+        Ok(Self::with_config(MarketDataClient::new()?, RiskConfig::default()))
+    }
+
+    /// Creates a risk checker with a custom client and rule set
+    ///
+    /// # Arguments
+    ///
+    /// * `market_data` - MarketDataClient - Client used to fetch token profiles
+    /// * `config` - RiskConfig - The rule set
+    ///
+    /// # Returns
+    ///
+    /// RiskChecker - A new risk checker instance
+    pub fn with_config(market_data: MarketDataClient, config: RiskConfig) -> Self {
+        Self { market_data, config }
+    }
+
+    pub fn config(&self) -> &RiskConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: RiskConfig) {
+        self.config = config;
+    }
+
+    /// Fetches a token profile and scores it in one round-trip
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &str - The token mint address or pair address
+    ///
+    /// # Returns
+    ///
+    /// Result<TokenRiskReport, MarketDataError> - Findings, score and verdict
+    pub async fn assess(&mut self, address: &str) -> Result<TokenRiskReport, MarketDataError> {
+        let profile = self.market_data.get_token_profile(address).await?;
+        Ok(self.config.evaluate_profile(&profile))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod checker;
+pub mod scoring;
+
+pub use checker::RiskChecker;
+pub use scoring::{RiskConfig, RiskError, RiskFinding, RiskRule, Severity, TokenRiskReport};
\ No newline at end of file
@@ -0,0 +1,478 @@
+use crate::models::market::{TokenAnalysis, TokenInfo, TokenProfile};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RiskError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Token rejected by risk check: {0}")]
+    Rejected(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Points deducted from the score of 100 for a finding of this severity
+    pub fn penalty(&self) -> u32 {
+        match self {
+            Severity::Info => 0,
+            Severity::Low => 5,
+            Severity::Medium => 15,
+            Severity::High => 30,
+            Severity::Critical => 60,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single configurable check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RiskRule {
+    /// Mint authority is still set, so supply can be inflated
+    MintAuthority { severity: Severity },
+    /// Freeze authority is still set, so holder accounts can be frozen
+    FreezeAuthority { severity: Severity },
+    /// Less than `min` percent of the LP has been burned
+    MinLpBurnedPercent { min: f64, severity: Severity },
+    /// Pool holds less than `min` SOL of liquidity
+    MinLiquiditySol { min: f64, severity: Severity },
+    /// Creator has rugged more than `max` tokens
+    MaxCreatorRugCount { max: u32, severity: Severity },
+    /// Creator's rugged share of launched tokens exceeds `max` (0.0 - 1.0)
+    MaxCreatorRugRatio { max: f64, severity: Severity },
+    /// Creator risk level is one of `levels` (case-insensitive)
+    CreatorRiskLevel { levels: Vec<String>, severity: Severity },
+}
+
+impl RiskRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RiskRule::MintAuthority { .. } => "mint_authority",
+            RiskRule::FreezeAuthority { .. } => "freeze_authority",
+            RiskRule::MinLpBurnedPercent { .. } => "min_lp_burned_percent",
+            RiskRule::MinLiquiditySol { .. } => "min_liquidity_sol",
+            RiskRule::MaxCreatorRugCount { .. } => "max_creator_rug_count",
+            RiskRule::MaxCreatorRugRatio { .. } => "max_creator_rug_ratio",
+            RiskRule::CreatorRiskLevel { .. } => "creator_risk_level",
+        }
+    }
+
+    fn check(&self, token: &RiskInputs) -> Option<RiskFinding> {
+        let finding = |severity: Severity, message: String| {
+            Some(RiskFinding {
+                rule: self.name().to_string(),
+                severity,
+                message,
+            })
+        };
+
+        match self {
+            RiskRule::MintAuthority { severity } => match &token.mint_authority {
+                Some(Some(authority)) if !authority.is_empty() => {
+                    finding(*severity, format!("Mint authority is set to {}", authority))
+                }
+                None => finding(*severity, "Mint authority was not reported".to_string()),
+                _ => None,
+            },
+            RiskRule::FreezeAuthority { severity } => match &token.freeze_authority {
+                Some(Some(authority)) if !authority.is_empty() => {
+                    finding(*severity, format!("Freeze authority is set to {}", authority))
+                }
+                None => finding(*severity, "Freeze authority was not reported".to_string()),
+                _ => None,
+            },
+            RiskRule::MinLpBurnedPercent { min, severity } => match token.lp_burned {
+                Some(burned) if burned < *min => finding(
+                    *severity,
+                    format!("Only {:.1}% of LP burned (minimum {:.1}%)", burned, min),
+                ),
+                None => finding(*severity, "LP burn was not reported".to_string()),
+                _ => None,
+            },
+            RiskRule::MinLiquiditySol { min, severity } if token.liquidity_sol < *min => finding(
+                *severity,
+                format!("Liquidity {:.2} SOL below minimum {:.2} SOL", token.liquidity_sol, min),
+            ),
+            RiskRule::MaxCreatorRugCount { max, severity } => match &token.creator {
+                Some(creator) if creator.rug_count > *max => finding(
+                    *severity,
+                    format!("Creator has {} rugged tokens (maximum {})", creator.rug_count, max),
+                ),
+                None => finding(*severity, "Creator data was not reported".to_string()),
+                _ => None,
+            },
+            RiskRule::MaxCreatorRugRatio { max, severity } => match &token.creator {
+                Some(creator) if creator.token_count > 0 => {
+                    let ratio = creator.rug_count as f64 / creator.token_count as f64;
+                    (ratio > *max)
+                        .then(|| {
+                            format!(
+                                "Creator rugged {} of {} tokens ({:.0}%)",
+                                creator.rug_count,
+                                creator.token_count,
+                                ratio * 100.0
+                            )
+                        })
+                        .and_then(|message| finding(*severity, message))
+                }
+                None => finding(*severity, "Creator data was not reported".to_string()),
+                _ => None,
+            },
+            RiskRule::CreatorRiskLevel { levels, severity } => match &token.creator {
+                Some(creator) if levels.iter().any(|level| level.eq_ignore_ascii_case(&creator.risk_level)) => {
+                    finding(*severity, format!("Creator risk level is {}", creator.risk_level))
+                }
+                None => finding(*severity, "Creator data was not reported".to_string()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Rule set and pass/fail thresholds. Serializable so it can live in a JSON or TOML file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskConfig {
+    pub rules: Vec<RiskRule>,
+    /// Minimum score (0 - 100) for a token to pass
+    #[serde(default = "default_min_score")]
+    pub min_score: u8,
+    /// Any finding at or above this severity fails the token regardless of score
+    #[serde(default = "default_fail_on")]
+    pub fail_on: Severity,
+}
+
+fn default_min_score() -> u8 {
+    50
+}
+
+fn default_fail_on() -> Severity {
+    Severity::Critical
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                RiskRule::MintAuthority { severity: Severity::Critical },
+                RiskRule::FreezeAuthority { severity: Severity::Critical },
+                RiskRule::MinLpBurnedPercent { min: 90.0, severity: Severity::High },
+                RiskRule::MinLiquiditySol { min: 10.0, severity: Severity::Medium },
+                RiskRule::MaxCreatorRugCount { max: 0, severity: Severity::High },
+                RiskRule::MaxCreatorRugRatio { max: 0.2, severity: Severity::Medium },
+                RiskRule::CreatorRiskLevel {
+                    levels: vec!["high".to_string()],
+                    severity: Severity::High,
+                },
+            ],
+            min_score: default_min_score(),
+            fail_on: default_fail_on(),
+        }
+    }
+}
+
+impl RiskConfig {
+    /// Loads a rule set from a JSON file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - impl AsRef<Path> - Location of the config file
+    ///
+    /// # Returns
+    ///
+    /// Result<RiskConfig, RiskError> - The loaded rule set
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RiskError> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves the rule set as JSON
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - impl AsRef<Path> - Destination file
+    ///
+    /// # Returns
+    ///
+    /// Result<(), RiskError> - Ok if saved
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RiskError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Evaluates a token from separately fetched info and analysis
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - &TokenInfo - Token metadata
+    /// * `analysis` - Option<&TokenAnalysis> - Creator analysis; without it, creator rules report
+    ///   that creator data was not reported
+    ///
+    /// # Returns
+    ///
+    /// TokenRiskReport - Findings, score and verdict
+    pub fn evaluate(&self, info: &TokenInfo, analysis: Option<&TokenAnalysis>) -> TokenRiskReport {
+        self.evaluate_inputs(RiskInputs {
+            mint_address: info.mint_address.clone(),
+            symbol: info.symbol.clone(),
+            mint_authority: Some(info.mint_authority.clone()),
+            freeze_authority: Some(info.freeze_authority.clone()),
+            lp_burned: Some(info.lp_burned),
+            liquidity_sol: info.liquidity_sol,
+            creator: analysis.map(|a| CreatorInputs {
+                risk_level: a.creator_risk_level.clone(),
+                rug_count: a.creator_rug_count,
+                token_count: a.creator_token_count,
+            }),
+        })
+    }
+
+    /// Evaluates a token from a combined profile
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - &TokenProfile - Token metadata and creator analysis; creator rules report
+    ///   missing data if the creator risk level or rug count was not reported
+    ///
+    /// # Returns
+    ///
+    /// TokenRiskReport - Findings, score and verdict
+    pub fn evaluate_profile(&self, profile: &TokenProfile) -> TokenRiskReport {
+        self.evaluate_inputs(RiskInputs {
+            mint_address: profile.mint_address.clone(),
+            symbol: profile.symbol.clone(),
+            mint_authority: profile.mint_authority.clone(),
+            freeze_authority: profile.freeze_authority.clone(),
+            lp_burned: profile.lp_burned,
+            liquidity_sol: profile.liquidity_sol,
+            creator: match (&profile.creator_risk_level, profile.creator_rug_count) {
+                (Some(risk_level), Some(rug_count)) => Some(CreatorInputs {
+                    risk_level: risk_level.clone(),
+                    rug_count,
+                    token_count: profile.creator_token_count,
+                }),
+                _ => None,
+            },
+        })
+    }
+
+    fn evaluate_inputs(&self, token: RiskInputs) -> TokenRiskReport {
+        let mut findings: Vec<RiskFinding> = self.rules.iter().filter_map(|rule| rule.check(&token)).collect();
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+        let penalty: u32 = findings.iter().map(|f| f.severity.penalty()).sum();
+        let score = 100u32.saturating_sub(penalty) as u8;
+        let blocked = findings.iter().any(|f| f.severity >= self.fail_on);
+
+        TokenRiskReport {
+            mint_address: token.mint_address,
+            symbol: token.symbol,
+            score,
+            passed: !blocked && score >= self.min_score,
+            findings,
+        }
+    }
+}
+
+struct CreatorInputs {
+    risk_level: String,
+    rug_count: u32,
+    token_count: u32,
+}
+
+struct RiskInputs {
+    mint_address: String,
+    symbol: String,
+    /// None when not reported, Some(None) when revoked
+    mint_authority: Option<Option<String>>,
+    /// None when not reported, Some(None) when revoked
+    freeze_authority: Option<Option<String>>,
+    lp_burned: Option<f64>,
+    liquidity_sol: f64,
+    creator: Option<CreatorInputs>,
+}
+
+/// A rule that fired for a token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskFinding {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Result of running a rule set against a token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenRiskReport {
+    pub mint_address: String,
+    pub symbol: String,
+    /// 100 minus the penalties of all findings, floored at 0
+    pub score: u8,
+    pub passed: bool,
+    /// Findings ordered from most to least severe
+    pub findings: Vec<RiskFinding>,
+}
+
+impl TokenRiskReport {
+    /// Gets the most severe finding's severity
+    pub fn worst_severity(&self) -> Option<Severity> {
+        self.findings.first().map(|f| f.severity)
+    }
+
+    /// Turns a failed report into an error, for use as a gate before trading
+    ///
+    /// # Returns
+    ///
+    /// Result<(), RiskError> - Ok if the token passed
+    pub fn ensure_passed(&self) -> Result<(), RiskError> {
+        if self.passed {
+            return Ok(());
+        }
+        Err(RiskError::Rejected(self.summary()))
+    }
+
+    /// Gets a one-line description of the verdict
+    pub fn summary(&self) -> String {
+        let reasons: Vec<&str> = self
+            .findings
+            .iter()
+            .filter(|f| f.severity > Severity::Info)
+            .map(|f| f.message.as_str())
+            .collect();
+        format!(
+            "{} ({}) score {}{}{}",
+            self.symbol,
+            self.mint_address,
+            self.score,
+            if reasons.is_empty() { "" } else { ": " },
+            reasons.join("; ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> TokenProfile {
+        serde_json::from_value(serde_json::json!({
+            "tokenAddress": "So11111111111111111111111111111111111111112",
+            "tokenTicker": "TEST",
+            "liquiditySol": 50.0,
+            "mintAuthority": null,
+            "freezeAuthority": null,
+            "lpBurned": 100.0,
+            "creatorRiskLevel": "Low",
+            "creatorRugCount": 0,
+            "creatorTokenCount": 3
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_clean_token_passes() {
+        let report = RiskConfig::default().evaluate_profile(&profile());
+        assert!(report.passed);
+        assert_eq!(report.score, 100);
+        assert!(report.ensure_passed().is_ok());
+    }
+
+    #[test]
+    fn test_critical_finding_fails_regardless_of_score() {
+        let mut token = profile();
+        token.mint_authority = Some(Some("Authority1111".to_string()));
+        let report = RiskConfig { min_score: 0, ..RiskConfig::default() }.evaluate_profile(&token);
+
+        assert!(!report.passed);
+        assert_eq!(report.worst_severity(), Some(Severity::Critical));
+        assert!(matches!(report.ensure_passed(), Err(RiskError::Rejected(_))));
+    }
+
+    #[test]
+    fn test_score_accumulates_penalties() {
+        let mut token = profile();
+        token.liquidity_sol = 5.0;
+        let report = RiskConfig::default().evaluate_profile(&token);
+        assert_eq!(report.score, 85);
+        assert!(report.passed);
+
+        token.lp_burned = Some(0.0);
+        token.creator_rug_count = Some(2);
+        token.creator_risk_level = Some("HIGH".to_string());
+        let report = RiskConfig::default().evaluate_profile(&token);
+
+        assert_eq!(report.findings.len(), 5);
+        assert_eq!(report.score, 0);
+        assert!(!report.passed);
+    }
+
+    #[test]
+    fn test_profile_without_creator_data_reports_creator_rules() {
+        let mut token = profile();
+        token.creator_risk_level = None;
+        token.creator_rug_count = None;
+        let config = RiskConfig {
+            rules: vec![
+                RiskRule::MaxCreatorRugCount { max: 0, severity: Severity::High },
+                RiskRule::CreatorRiskLevel { levels: vec![String::new()], severity: Severity::Medium },
+            ],
+            ..RiskConfig::default()
+        };
+
+        let report = config.evaluate_profile(&token);
+        let findings: Vec<_> = report.findings.iter().map(|f| (f.rule.as_str(), f.severity, f.message.as_str())).collect();
+        assert_eq!(
+            findings,
+            vec![
+                ("max_creator_rug_count", Severity::High, "Creator data was not reported"),
+                ("creator_risk_level", Severity::Medium, "Creator data was not reported"),
+            ]
+        );
+        assert_eq!(report.score, 55);
+    }
+
+    #[test]
+    fn test_config_roundtrip() {
+        let config = RiskConfig::default();
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains("\"rule\":\"min_lp_burned_percent\""));
+        assert_eq!(serde_json::from_str::<RiskConfig>(&json).unwrap(), config);
+
+        let partial: RiskConfig =
+            serde_json::from_str(r#"{"rules":[{"rule":"mint_authority","severity":"high"}]}"#).unwrap();
+        assert_eq!(partial.min_score, 50);
+        assert_eq!(partial.fail_on, Severity::Critical);
+    }
+}
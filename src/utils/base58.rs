@@ -0,0 +1,112 @@
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const INVALID: u8 = 0xff;
+
+const fn build_decode_table() -> [u8; 128] {
+    let mut table = [INVALID; 128];
+    let mut i = 0;
+    while i < ALPHABET.len() {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+const DECODE_TABLE: [u8; 128] = build_decode_table();
+
+/// Encodes bytes using the Bitcoin/Solana base58 alphabet
+///
+/// # Arguments
+///
+/// * `input` - &[u8] - The bytes to encode
+///
+/// # Returns
+///
+/// String - The base58 encoded string
+pub fn encode(input: &[u8]) -> String {
+    let zeros = input.iter().take_while(|&&b| b == 0).count();
+    let mut digits: Vec<u8> = Vec::with_capacity(input.len() * 138 / 100 + 1);
+
+    for &byte in &input[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut output = String::with_capacity(zeros + digits.len());
+    output.extend(std::iter::repeat_n('1', zeros));
+    output.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    output
+}
+
+/// Decodes a base58 string using the Bitcoin/Solana alphabet
+///
+/// # Arguments
+///
+/// * `input` - &str - The base58 string to decode
+///
+/// # Returns
+///
+/// Option<Vec<u8>> - The decoded bytes, or None if the input contains a character outside the alphabet
+pub fn decode(input: &str) -> Option<Vec<u8>> {
+    let zeros = input.bytes().take_while(|&b| b == b'1').count();
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 733 / 1000 + 1);
+
+    for c in input.bytes().skip(zeros) {
+        let value = *DECODE_TABLE.get(c as usize)?;
+        if value == INVALID {
+            return None;
+        }
+
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut output = vec![0u8; zeros];
+    output.extend(bytes.iter().rev());
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let data = [0u8, 0, 1, 2, 3, 255, 128, 64];
+        let encoded = encode(&data);
+        assert!(encoded.starts_with("11"));
+        assert_eq!(decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_known_vectors() {
+        assert_eq!(encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(decode("StV1DL6CwTryKyV").unwrap(), b"hello world");
+        assert_eq!(encode(&[]), "");
+    }
+
+    #[test]
+    fn test_rejects_invalid_characters() {
+        assert!(decode("0abc").is_none());
+        assert!(decode("Oabc").is_none());
+        assert!(decode("Iabc").is_none());
+        assert!(decode("labc").is_none());
+        assert!(decode("ab c").is_none());
+    }
+}
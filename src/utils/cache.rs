@@ -0,0 +1,402 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+type CachedValue = Arc<dyn Any + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    namespace: &'static str,
+    type_id: TypeId,
+    key: String,
+}
+
+struct CacheEntry {
+    value: CachedValue,
+    expires_at: Instant,
+}
+
+/// Hit, miss and coalescing counters for a cache or one of its namespaces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests served from a fresh entry
+    pub hits: u64,
+    /// Requests that triggered a fetch
+    pub misses: u64,
+    /// Requests that waited on another caller's in-flight fetch
+    pub coalesced: u64,
+    /// Entries removed by explicit invalidation
+    pub invalidations: u64,
+    /// Entries removed because they expired or the cache was full
+    pub evictions: u64,
+    /// Entries currently stored
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Gets the share of requests that did not trigger a fetch
+    ///
+    /// # Returns
+    ///
+    /// f64 - Ratio between 0.0 and 1.0
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses + self.coalesced;
+        if total == 0 {
+            return 0.0;
+        }
+        (self.hits + self.coalesced) as f64 / total as f64
+    }
+
+    fn merge(&mut self, other: &CacheStats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.coalesced += other.coalesced;
+        self.invalidations += other.invalidations;
+        self.evictions += other.evictions;
+        self.entries += other.entries;
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    in_flight: HashMap<CacheKey, Arc<OnceCell<CachedValue>>>,
+    stats: HashMap<&'static str, CacheStats>,
+}
+
+impl CacheState {
+    fn stats_mut(&mut self, namespace: &'static str) -> &mut CacheStats {
+        self.stats.entry(namespace).or_default()
+    }
+
+    fn remove_where<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(&CacheKey) -> bool,
+    {
+        let removed: Vec<CacheKey> = self.entries.keys().filter(|k| predicate(k)).cloned().collect();
+        for key in &removed {
+            self.entries.remove(key);
+            self.stats_mut(key.namespace).invalidations += 1;
+        }
+        self.in_flight.retain(|k, _| !predicate(k));
+        removed.len()
+    }
+
+    fn purge_expired(&mut self, now: Instant) {
+        let expired: Vec<CacheKey> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.expires_at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            self.entries.remove(&key);
+            self.stats_mut(key.namespace).evictions += 1;
+        }
+    }
+}
+
+/// TTL response cache that coalesces concurrent fetches for the same key.
+///
+/// Entries are grouped by a static namespace (typically the endpoint name) and keyed by a
+/// string (typically the mint). The cache is cheap to share behind an `Arc`, so several
+/// clients can use one cache and concurrent callers for the same key share a single request.
+pub struct ResponseCache {
+    state: Mutex<CacheState>,
+    max_entries: usize,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl ResponseCache {
+    /// Creates a new response cache
+    ///
+    /// # Arguments
+    ///
+    /// * `max_entries` - usize - Maximum number of stored entries before eviction
+    ///
+    /// # Returns
+    ///
+    /// ResponseCache - A new, empty cache
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            state: Mutex::new(CacheState::default()),
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Returns a fresh cached value or runs `fetch`, sharing the result with concurrent callers
+    ///
+    /// A zero `ttl` bypasses the cache. Errors are never cached; if the fetching caller fails,
+    /// the next waiting caller runs its own fetch.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - &'static str - Group the entry belongs to, usually the endpoint
+    /// * `key` - &str - Entry key within the namespace
+    /// * `ttl` - Duration - How long a fetched value stays fresh
+    /// * `fetch` - Future - Produces the value on a miss; not polled on a hit
+    ///
+    /// # Returns
+    ///
+    /// Result<T, E> - The cached or freshly fetched value
+    pub async fn get_or_fetch<T, E, F>(
+        &self,
+        namespace: &'static str,
+        key: &str,
+        ttl: Duration,
+        fetch: F,
+    ) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, E>>,
+    {
+        if ttl.is_zero() {
+            return fetch.await;
+        }
+
+        let cache_key = CacheKey {
+            namespace,
+            type_id: TypeId::of::<T>(),
+            key: key.to_string(),
+        };
+
+        let cell = {
+            let mut state = self.lock();
+            if let Some(value) = Self::fresh(&state, &cache_key) {
+                state.stats_mut(namespace).hits += 1;
+                return Ok(value);
+            }
+            match state.in_flight.get(&cache_key).cloned() {
+                Some(cell) => {
+                    state.stats_mut(namespace).coalesced += 1;
+                    cell
+                }
+                None => {
+                    let cell = Arc::new(OnceCell::new());
+                    state.in_flight.insert(cache_key.clone(), cell.clone());
+                    state.stats_mut(namespace).misses += 1;
+                    cell
+                }
+            }
+        };
+
+        let result = cell
+            .get_or_try_init(|| async { fetch.await.map(|value| Arc::new(value) as CachedValue) })
+            .await
+            .cloned();
+
+        let mut state = self.lock();
+        let owns_flight = state
+            .in_flight
+            .get(&cache_key)
+            .is_some_and(|current| Arc::ptr_eq(current, &cell));
+        if owns_flight {
+            state.in_flight.remove(&cache_key);
+        }
+
+        let value = result?;
+        if owns_flight {
+            let now = Instant::now();
+            if state.entries.len() >= self.max_entries {
+                self.make_room(&mut state, now);
+            }
+            state.entries.insert(
+                cache_key,
+                CacheEntry {
+                    value: value.clone(),
+                    expires_at: now + ttl,
+                },
+            );
+        }
+
+        Ok(value
+            .downcast_ref::<T>()
+            .cloned()
+            .expect("cache keys include the value type"))
+    }
+
+    fn fresh<T: Clone + 'static>(state: &CacheState, key: &CacheKey) -> Option<T> {
+        state
+            .entries
+            .get(key)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .and_then(|entry| entry.value.downcast_ref::<T>().cloned())
+    }
+
+    fn make_room(&self, state: &mut CacheState, now: Instant) {
+        state.purge_expired(now);
+        while state.entries.len() >= self.max_entries {
+            let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            state.entries.remove(&oldest);
+            state.stats_mut(oldest.namespace).evictions += 1;
+        }
+    }
+
+    /// Removes a single key from a namespace
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - &str - The entry's namespace
+    /// * `key` - &str - The entry key
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed
+    pub fn invalidate(&self, namespace: &str, key: &str) -> usize {
+        self.lock()
+            .remove_where(|k| k.namespace == namespace && k.key == key)
+    }
+
+    /// Removes a key from every namespace, e.g. all cached data for one mint
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - &str - The entry key
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed
+    pub fn invalidate_key(&self, key: &str) -> usize {
+        self.lock().remove_where(|k| k.key == key)
+    }
+
+    /// Removes every entry in a namespace
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - &str - The namespace to clear
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed
+    pub fn invalidate_namespace(&self, namespace: &str) -> usize {
+        self.lock().remove_where(|k| k.namespace == namespace)
+    }
+
+    /// Removes every entry
+    ///
+    /// # Returns
+    ///
+    /// usize - Number of entries removed
+    pub fn clear(&self) -> usize {
+        self.lock().remove_where(|_| true)
+    }
+
+    /// Drops expired entries
+    pub fn purge_expired(&self) {
+        self.lock().purge_expired(Instant::now());
+    }
+
+    /// Gets statistics across all namespaces
+    ///
+    /// # Returns
+    ///
+    /// CacheStats - Aggregated counters
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        let mut total = CacheStats::default();
+        for stats in state.stats.values() {
+            total.merge(stats);
+        }
+        total.entries = state.entries.len();
+        total
+    }
+
+    /// Gets statistics for one namespace
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - &str - The namespace
+    ///
+    /// # Returns
+    ///
+    /// CacheStats - Counters for the namespace
+    pub fn namespace_stats(&self, namespace: &str) -> CacheStats {
+        let state = self.lock();
+        let mut stats = state.stats.get(namespace).copied().unwrap_or_default();
+        stats.entries = state.entries.keys().filter(|k| k.namespace == namespace).count();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn counted(counter: &AtomicUsize, value: u32) -> Result<u32, String> {
+        counter.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn test_hit_after_miss_and_expiry() {
+        let cache = ResponseCache::default();
+        let fetches = AtomicUsize::new(0);
+        let ttl = Duration::from_millis(50);
+
+        assert_eq!(cache.get_or_fetch("price", "a", ttl, counted(&fetches, 1)).await, Ok(1));
+        assert_eq!(cache.get_or_fetch("price", "a", ttl, counted(&fetches, 2)).await, Ok(1));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(cache.get_or_fetch("price", "a", ttl, counted(&fetches, 3)).await, Ok(3));
+
+        let stats = cache.namespace_stats("price");
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_are_coalesced() {
+        let cache = ResponseCache::default();
+        let fetches = AtomicUsize::new(0);
+        let ttl = Duration::from_secs(10);
+
+        let (a, b, c) = tokio::join!(
+            cache.get_or_fetch("info", "mint", ttl, counted(&fetches, 7)),
+            cache.get_or_fetch("info", "mint", ttl, counted(&fetches, 8)),
+            cache.get_or_fetch("info", "mint", ttl, counted(&fetches, 9)),
+        );
+
+        assert_eq!((a, b, c), (Ok(7), Ok(7), Ok(7)));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.stats().coalesced, 2);
+    }
+
+    #[tokio::test]
+    async fn test_errors_not_cached_and_invalidate() {
+        let cache = ResponseCache::default();
+        let ttl = Duration::from_secs(10);
+
+        let failed: Result<u32, String> =
+            cache.get_or_fetch("info", "mint", ttl, async { Err("boom".to_string()) }).await;
+        assert!(failed.is_err());
+        assert_eq!(cache.stats().entries, 0);
+
+        let _ = cache.get_or_fetch::<u32, String, _>("info", "mint", ttl, async { Ok(1) }).await;
+        let _ = cache.get_or_fetch::<u32, String, _>("price", "mint", ttl, async { Ok(2) }).await;
+        assert_eq!(cache.invalidate_key("mint"), 2);
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().invalidations, 2);
+    }
+}
@@ -3,4 +3,6 @@ pub mod password;
 pub mod p256_crypto;
 pub mod rate_limiter;
 pub mod retry;
-pub mod user_agents;
\ No newline at end of file
+pub mod user_agents;
+pub mod base58;
+pub mod cache;
\ No newline at end of file
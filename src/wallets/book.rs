@@ -0,0 +1,474 @@
+use crate::models::portfolio::BatchBalanceResponse;
+use crate::models::solana::{Pubkey, PubkeyError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Prefix that selects wallets by tag instead of group in `WalletBook::resolve`
+const TAG_PREFIX: &str = "tag:";
+
+#[derive(Error, Debug)]
+pub enum WalletBookError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Invalid wallet address: {0}")]
+    InvalidAddress(#[from] PubkeyError),
+
+    #[error("Duplicate wallet label: {0}")]
+    DuplicateLabel(String),
+
+    #[error("Wallet not found: {0}")]
+    WalletNotFound(String),
+
+    #[error("No wallets match: {0}")]
+    EmptySelection(String),
+}
+
+/// A wallet entry in the address book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabeledWallet {
+    pub address: Pubkey,
+    pub label: String,
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    #[serde(default)]
+    pub groups: BTreeSet<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+impl LabeledWallet {
+    /// Creates a wallet entry with no tags or groups
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Pubkey - The wallet address
+    /// * `label` - &str - Human readable label
+    ///
+    /// # Returns
+    ///
+    /// LabeledWallet - A new wallet entry
+    pub fn new(address: Pubkey, label: &str) -> Self {
+        Self {
+            address,
+            label: label.to_string(),
+            tags: BTreeSet::new(),
+            groups: BTreeSet::new(),
+            notes: None,
+        }
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.insert(tag.to_string());
+        self
+    }
+
+    pub fn with_group(mut self, group: &str) -> Self {
+        self.groups.insert(group.to_string());
+        self
+    }
+}
+
+/// Aggregated balances for a group or tag
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WalletRollup {
+    pub wallet_count: usize,
+    pub sol_balance: f64,
+    pub total_value_usd: f64,
+    pub token_positions: usize,
+}
+
+impl WalletRollup {
+    fn add(&mut self, sol_balance: f64, total_value_usd: f64, token_positions: usize) {
+        self.wallet_count += 1;
+        self.sol_balance += sol_balance;
+        self.total_value_usd += total_value_usd;
+        self.token_positions += token_positions;
+    }
+}
+
+/// Persisted address book of labeled wallets organised into groups and tags
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletBook {
+    wallets: BTreeMap<Pubkey, LabeledWallet>,
+    #[serde(skip)]
+    storage_path: Option<PathBuf>,
+}
+
+impl WalletBook {
+    /// Creates an empty in-memory wallet book
+    ///
+    /// # Returns
+    ///
+    /// WalletBook - An empty book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a wallet book from a JSON file, creating an empty one if it does not exist
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - impl AsRef<Path> - Location of the book file
+    ///
+    /// # Returns
+    ///
+    /// Result<WalletBook, WalletBookError> - The loaded book, bound to `path` for `save`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, WalletBookError> {
+        let path = path.as_ref().to_path_buf();
+        let mut book = if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            serde_json::from_str::<WalletBook>(&content)?
+        } else {
+            WalletBook::new()
+        };
+        book.storage_path = Some(path);
+        Ok(book)
+    }
+
+    /// Saves the book to the file it was opened from (no-op for in-memory books)
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WalletBookError> - Ok if saved
+    pub fn save(&self) -> Result<(), WalletBookError> {
+        if let Some(path) = &self.storage_path {
+            self.save_to(path)?;
+        }
+        Ok(())
+    }
+
+    /// Saves the book to a specific file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - impl AsRef<Path> - Destination file
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WalletBookError> - Ok if saved
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), WalletBookError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Adds or replaces a wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `wallet` - LabeledWallet - The wallet entry
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WalletBookError> - Error if another wallet already uses the label
+    pub fn add(&mut self, wallet: LabeledWallet) -> Result<(), WalletBookError> {
+        if self
+            .wallets
+            .values()
+            .any(|w| w.label == wallet.label && w.address != wallet.address)
+        {
+            return Err(WalletBookError::DuplicateLabel(wallet.label));
+        }
+        self.wallets.insert(wallet.address, wallet);
+        Ok(())
+    }
+
+    /// Adds a wallet from a base58 address
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &str - The wallet address
+    /// * `label` - &str - Human readable label
+    /// * `groups` - &[&str] - Groups the wallet belongs to
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WalletBookError> - Error if the address is invalid or the label is taken
+    pub fn add_address(&mut self, address: &str, label: &str, groups: &[&str]) -> Result<(), WalletBookError> {
+        let mut wallet = LabeledWallet::new(address.parse()?, label);
+        wallet.groups.extend(groups.iter().map(|g| g.to_string()));
+        self.add(wallet)
+    }
+
+    pub fn remove(&mut self, address: &Pubkey) -> Option<LabeledWallet> {
+        self.wallets.remove(address)
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<&LabeledWallet> {
+        self.wallets.get(address)
+    }
+
+    pub fn get_by_label(&self, label: &str) -> Option<&LabeledWallet> {
+        self.wallets.values().find(|w| w.label == label)
+    }
+
+    pub fn wallets(&self) -> impl Iterator<Item = &LabeledWallet> {
+        self.wallets.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.wallets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+    }
+
+    /// Adds a wallet to a group
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &Pubkey - The wallet address
+    /// * `group` - &str - The group name
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WalletBookError> - Error if the wallet is not in the book
+    pub fn assign_group(&mut self, address: &Pubkey, group: &str) -> Result<(), WalletBookError> {
+        self.wallets
+            .get_mut(address)
+            .ok_or_else(|| WalletBookError::WalletNotFound(address.to_string()))?
+            .groups
+            .insert(group.to_string());
+        Ok(())
+    }
+
+    /// Adds a tag to a wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - &Pubkey - The wallet address
+    /// * `tag` - &str - The tag
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WalletBookError> - Error if the wallet is not in the book
+    pub fn add_tag(&mut self, address: &Pubkey, tag: &str) -> Result<(), WalletBookError> {
+        self.wallets
+            .get_mut(address)
+            .ok_or_else(|| WalletBookError::WalletNotFound(address.to_string()))?
+            .tags
+            .insert(tag.to_string());
+        Ok(())
+    }
+
+    /// Gets all group names in use
+    ///
+    /// # Returns
+    ///
+    /// BTreeSet<String> - Sorted group names
+    pub fn groups(&self) -> BTreeSet<String> {
+        self.wallets.values().flat_map(|w| w.groups.iter().cloned()).collect()
+    }
+
+    /// Gets all tags in use
+    ///
+    /// # Returns
+    ///
+    /// BTreeSet<String> - Sorted tags
+    pub fn tags(&self) -> BTreeSet<String> {
+        self.wallets.values().flat_map(|w| w.tags.iter().cloned()).collect()
+    }
+
+    pub fn group_members(&self, group: &str) -> Vec<Pubkey> {
+        self.wallets
+            .values()
+            .filter(|w| w.groups.contains(group))
+            .map(|w| w.address)
+            .collect()
+    }
+
+    pub fn tagged(&self, tag: &str) -> Vec<Pubkey> {
+        self.wallets
+            .values()
+            .filter(|w| w.tags.contains(tag))
+            .map(|w| w.address)
+            .collect()
+    }
+
+    /// Resolves a selector to wallet addresses.
+    ///
+    /// A selector is a group name, `tag:<tag>`, a wallet label, or a raw address.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - &str - What to resolve
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<Pubkey>, WalletBookError> - Matching addresses (never empty)
+    pub fn resolve(&self, selector: &str) -> Result<Vec<Pubkey>, WalletBookError> {
+        let addresses = if let Some(tag) = selector.strip_prefix(TAG_PREFIX) {
+            self.tagged(tag)
+        } else {
+            let members = self.group_members(selector);
+            if !members.is_empty() {
+                members
+            } else if let Some(wallet) = self.get_by_label(selector) {
+                vec![wallet.address]
+            } else if let Ok(address) = selector.parse::<Pubkey>() {
+                vec![address]
+            } else {
+                Vec::new()
+            }
+        };
+
+        if addresses.is_empty() {
+            return Err(WalletBookError::EmptySelection(selector.to_string()));
+        }
+        Ok(addresses)
+    }
+
+    /// Resolves a selector to base58 address strings, as taken by the API clients
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - &str - Group name, `tag:<tag>`, label or address
+    ///
+    /// # Returns
+    ///
+    /// Result<Vec<String>, WalletBookError> - Matching addresses
+    pub fn resolve_strings(&self, selector: &str) -> Result<Vec<String>, WalletBookError> {
+        Ok(self.resolve(selector)?.iter().map(|a| a.to_string()).collect())
+    }
+
+    /// Rolls balances up per group
+    ///
+    /// # Arguments
+    ///
+    /// * `balances` - &BatchBalanceResponse - Balances for some or all wallets in the book
+    ///
+    /// # Returns
+    ///
+    /// BTreeMap<String, WalletRollup> - Totals keyed by group name
+    pub fn group_totals(&self, balances: &BatchBalanceResponse) -> BTreeMap<String, WalletRollup> {
+        self.rollup(balances, |w| &w.groups)
+    }
+
+    /// Rolls balances up per tag
+    ///
+    /// # Arguments
+    ///
+    /// * `balances` - &BatchBalanceResponse - Balances for some or all wallets in the book
+    ///
+    /// # Returns
+    ///
+    /// BTreeMap<String, WalletRollup> - Totals keyed by tag
+    pub fn tag_totals(&self, balances: &BatchBalanceResponse) -> BTreeMap<String, WalletRollup> {
+        self.rollup(balances, |w| &w.tags)
+    }
+
+    fn rollup<F>(&self, balances: &BatchBalanceResponse, keys: F) -> BTreeMap<String, WalletRollup>
+    where
+        F: Fn(&LabeledWallet) -> &BTreeSet<String>,
+    {
+        let by_address: HashMap<Pubkey, _> = balances
+            .balances
+            .iter()
+            .filter_map(|(address, balance)| address.parse::<Pubkey>().ok().map(|a| (a, balance)))
+            .collect();
+
+        let mut totals: BTreeMap<String, WalletRollup> = BTreeMap::new();
+        for wallet in self.wallets.values() {
+            let Some(balance) = by_address.get(&wallet.address) else {
+                continue;
+            };
+            for key in keys(wallet) {
+                totals.entry(key.clone()).or_default().add(
+                    balance.sol_balance,
+                    balance.total_value_usd,
+                    balance.token_balances.len(),
+                );
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::portfolio::WalletBalance;
+
+    fn address(seed: u8) -> Pubkey {
+        Pubkey::new([seed; 32])
+    }
+
+    fn book() -> WalletBook {
+        let mut book = WalletBook::new();
+        book.add(LabeledWallet::new(address(1), "sniper-1").with_group("snipers").with_tag("hot"))
+            .unwrap();
+        book.add(LabeledWallet::new(address(2), "sniper-2").with_group("snipers").with_tag("hot"))
+            .unwrap();
+        book.add(LabeledWallet::new(address(3), "vault").with_group("cold")).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_resolve_selectors() {
+        let book = book();
+        assert_eq!(book.resolve("snipers").unwrap(), vec![address(1), address(2)]);
+        assert_eq!(book.resolve("tag:hot").unwrap().len(), 2);
+        assert_eq!(book.resolve("vault").unwrap(), vec![address(3)]);
+        assert_eq!(book.resolve(&address(9).to_string()).unwrap(), vec![address(9)]);
+        assert!(matches!(book.resolve("missing"), Err(WalletBookError::EmptySelection(_))));
+    }
+
+    #[test]
+    fn test_duplicate_label_rejected() {
+        let mut book = book();
+        let result = book.add(LabeledWallet::new(address(4), "vault"));
+        assert!(matches!(result, Err(WalletBookError::DuplicateLabel(_))));
+    }
+
+    #[test]
+    fn test_group_and_tag_totals() {
+        let book = book();
+        let balance = |sol: f64, usd: f64| WalletBalance {
+            sol_balance: sol,
+            token_balances: HashMap::new(),
+            total_value_usd: usd,
+        };
+        let balances = BatchBalanceResponse {
+            balances: HashMap::from([
+                (address(1).to_string(), balance(1.0, 150.0)),
+                (address(2).to_string(), balance(2.0, 300.0)),
+                (address(3).to_string(), balance(10.0, 1500.0)),
+            ]),
+            timestamp: 0,
+            unpriced: Vec::new(),
+        };
+
+        let groups = book.group_totals(&balances);
+        assert_eq!(groups["snipers"].wallet_count, 2);
+        assert_eq!(groups["snipers"].sol_balance, 3.0);
+        assert_eq!(groups["cold"].total_value_usd, 1500.0);
+
+        let tags = book.tag_totals(&balances);
+        assert_eq!(tags["hot"].total_value_usd, 450.0);
+        assert!(!tags.contains_key("cold"));
+    }
+
+    #[test]
+    fn test_persistence_roundtrip() {
+        let path = std::env::temp_dir().join(format!("axiom_wallet_book_{}.json", std::process::id()));
+        let mut book = WalletBook::open(&path).unwrap();
+        book.add_address(&address(5).to_string(), "main", &["cold"]).unwrap();
+        book.save().unwrap();
+
+        let reopened = WalletBook::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened.group_members("cold"), vec![address(5)]);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod book;
+
+pub use book::{LabeledWallet, WalletBook, WalletBookError, WalletRollup};
\ No newline at end of file
@@ -0,0 +1,436 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+use crate::websocket::handler::MessageHandler;
+use crate::websocket::messages::WebSocketMessage;
+
+/// What the read loop does when the dispatch queue is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Stop reading until the handler catches up
+    #[default]
+    Block,
+    /// Discard the oldest queued message to make room
+    DropOldest,
+    /// Discard the incoming message
+    DropNewest,
+    /// Replace a queued token price, SOL price or pair stats update from the same room with the
+    /// newer one. Other messages block when the queue is full.
+    CoalesceByKey,
+}
+
+/// Details passed to the overflow callback
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverflowWarning {
+    pub policy: OverflowPolicy,
+    pub capacity: usize,
+    /// Totals since the client was created
+    pub stats: DispatchStats,
+}
+
+pub type OverflowCallback = Arc<dyn Fn(&OverflowWarning) + Send + Sync>;
+
+/// Settings for the queue between the socket reader and the message handler
+#[derive(Clone)]
+pub struct DispatchConfig {
+    /// Messages held for the handler before the overflow policy applies
+    pub capacity: usize,
+    pub policy: OverflowPolicy,
+    /// Called when messages are dropped or the reader blocks, at most once per `warn_interval`
+    pub on_overflow: Option<OverflowCallback>,
+    pub warn_interval: Duration,
+}
+
+impl Default for DispatchConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            policy: OverflowPolicy::default(),
+            on_overflow: None,
+            warn_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl fmt::Debug for DispatchConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DispatchConfig")
+            .field("capacity", &self.capacity)
+            .field("policy", &self.policy)
+            .field("on_overflow", &self.on_overflow.is_some())
+            .field("warn_interval", &self.warn_interval)
+            .finish()
+    }
+}
+
+/// Counts of what happened to messages on their way to the handler
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DispatchStats {
+    /// Messages waiting for the handler now
+    pub queued: usize,
+    /// Deepest the queue has been
+    pub high_water: usize,
+    pub delivered: u64,
+    pub dropped_oldest: u64,
+    pub dropped_newest: u64,
+    /// Updates replaced by a newer one from the same room before delivery
+    pub coalesced: u64,
+    /// Times the reader waited for room in the queue
+    pub blocked: u64,
+}
+
+impl DispatchStats {
+    pub fn dropped(&self) -> u64 {
+        self.dropped_oldest + self.dropped_newest
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DispatchMetrics {
+    queued: AtomicUsize,
+    high_water: AtomicUsize,
+    delivered: AtomicU64,
+    dropped_oldest: AtomicU64,
+    dropped_newest: AtomicU64,
+    coalesced: AtomicU64,
+    blocked: AtomicU64,
+    last_warning: Mutex<Option<Instant>>,
+}
+
+impl DispatchMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> DispatchStats {
+        DispatchStats {
+            queued: self.queued.load(Ordering::Relaxed),
+            high_water: self.high_water.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped_oldest: self.dropped_oldest.load(Ordering::Relaxed),
+            dropped_newest: self.dropped_newest.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            blocked: self.blocked.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A handler call waiting in the queue
+pub(crate) enum Delivery {
+    Message(WebSocketMessage),
+    Connected(String),
+    Disconnected(String),
+    Error(String),
+}
+
+impl Delivery {
+    pub async fn deliver(self, handler: &dyn MessageHandler) {
+        match self {
+            Delivery::Message(message) => handler.handle_message(message).await,
+            Delivery::Connected(session_id) => handler.on_connected(session_id).await,
+            Delivery::Disconnected(reason) => handler.on_disconnected(reason).await,
+            Delivery::Error(error) => handler.on_error(error).await,
+        }
+    }
+}
+
+enum Entry {
+    Message(Delivery),
+    /// A coalescable update; the latest value lives in `QueueState::latest`
+    Keyed(String),
+    /// Connection events and errors, never dropped and not limited by capacity
+    Control(Delivery),
+}
+
+#[derive(Default)]
+struct QueueState {
+    entries: VecDeque<Entry>,
+    /// Entries other than control events, which are what capacity limits
+    messages: usize,
+    latest: HashMap<String, Delivery>,
+    closed: bool,
+}
+
+/// Bounded single-producer, single-consumer queue between a connection's read loop and the
+/// task that calls its handler.
+pub(crate) struct DispatchQueue {
+    config: DispatchConfig,
+    state: Mutex<QueueState>,
+    metrics: Arc<DispatchMetrics>,
+    not_empty: Notify,
+    not_full: Notify,
+}
+
+impl DispatchQueue {
+    pub fn new(config: DispatchConfig, metrics: Arc<DispatchMetrics>) -> Self {
+        metrics.queued.store(0, Ordering::Relaxed);
+        Self {
+            config,
+            state: Mutex::new(QueueState::default()),
+            metrics,
+            not_empty: Notify::new(),
+            not_full: Notify::new(),
+        }
+    }
+
+    /// Queues a message, applying the overflow policy if the queue is full
+    ///
+    /// # Arguments
+    ///
+    /// * `delivery` - Delivery - The handler call
+    /// * `key` - Option<&str> - Room of an update that only matters in its latest form
+    pub async fn push(&self, delivery: Delivery, key: Option<&str>) {
+        let key = key.filter(|_| self.config.policy == OverflowPolicy::CoalesceByKey);
+        let mut delivery = Some(delivery);
+        let mut counted_block = false;
+        loop {
+            {
+                let mut state = self.state.lock().expect("dispatch queue lock poisoned");
+                if state.closed {
+                    return;
+                }
+                let incoming = delivery.take().expect("delivery is queued at most once");
+
+                if let Some(key) = key
+                    && let Some(latest) = state.latest.get_mut(key)
+                {
+                    *latest = incoming;
+                    self.metrics.coalesced.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+
+                if state.messages >= self.config.capacity.max(1) {
+                    match self.config.policy {
+                        OverflowPolicy::DropNewest => {
+                            drop(state);
+                            self.metrics.dropped_newest.fetch_add(1, Ordering::Relaxed);
+                            self.warn();
+                            return;
+                        }
+                        OverflowPolicy::DropOldest => {
+                            if let Some(index) =
+                                state.entries.iter().position(|entry| !matches!(entry, Entry::Control(_)))
+                            {
+                                if let Some(Entry::Keyed(old)) = state.entries.remove(index) {
+                                    state.latest.remove(&old);
+                                }
+                                state.messages -= 1;
+                                self.metrics.dropped_oldest.fetch_add(1, Ordering::Relaxed);
+                            }
+                            self.enqueue(&mut state, incoming, key);
+                            drop(state);
+                            self.warn();
+                            return;
+                        }
+                        OverflowPolicy::Block | OverflowPolicy::CoalesceByKey => {
+                            delivery = Some(incoming);
+                        }
+                    }
+                } else {
+                    self.enqueue(&mut state, incoming, key);
+                    return;
+                }
+            }
+
+            if !counted_block {
+                counted_block = true;
+                self.metrics.blocked.fetch_add(1, Ordering::Relaxed);
+                self.warn();
+            }
+            self.not_full.notified().await;
+        }
+    }
+
+    /// Queues a connection event or error regardless of capacity
+    pub fn push_control(&self, delivery: Delivery) {
+        let mut state = self.state.lock().expect("dispatch queue lock poisoned");
+        if !state.closed {
+            state.entries.push_back(Entry::Control(delivery));
+            self.queued(&state);
+            self.not_empty.notify_one();
+        }
+    }
+
+    fn enqueue(&self, state: &mut QueueState, delivery: Delivery, key: Option<&str>) {
+        match key {
+            Some(key) => {
+                state.latest.insert(key.to_string(), delivery);
+                state.entries.push_back(Entry::Keyed(key.to_string()));
+            }
+            None => state.entries.push_back(Entry::Message(delivery)),
+        }
+        state.messages += 1;
+        self.queued(state);
+        self.not_empty.notify_one();
+    }
+
+    fn queued(&self, state: &QueueState) {
+        let depth = state.entries.len();
+        self.metrics.queued.store(depth, Ordering::Relaxed);
+        self.metrics.high_water.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// Takes the next handler call, waiting for one
+    ///
+    /// # Returns
+    ///
+    /// Option<Delivery> - None once the queue is closed and drained
+    pub async fn pop(&self) -> Option<Delivery> {
+        loop {
+            {
+                let mut state = self.state.lock().expect("dispatch queue lock poisoned");
+                if let Some(entry) = state.entries.pop_front() {
+                    if !matches!(entry, Entry::Control(_)) {
+                        state.messages -= 1;
+                    }
+                    self.queued(&state);
+                    self.not_full.notify_one();
+                    return Some(match entry {
+                        Entry::Message(delivery) | Entry::Control(delivery) => delivery,
+                        Entry::Keyed(key) => state.latest.remove(&key).expect("keyed entry has a value"),
+                    });
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.not_empty.notified().await;
+        }
+    }
+
+    /// Stops accepting messages; the consumer finishes what is queued
+    pub fn close(&self) {
+        self.state.lock().expect("dispatch queue lock poisoned").closed = true;
+        self.not_empty.notify_one();
+        self.not_full.notify_one();
+    }
+
+    /// Calls the handler for each queued message until the queue is closed and drained
+    pub async fn run(&self, handler: Arc<dyn MessageHandler>) {
+        while let Some(delivery) = self.pop().await {
+            delivery.deliver(handler.as_ref()).await;
+            self.metrics.delivered.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn warn(&self) {
+        let Some(callback) = &self.config.on_overflow else {
+            return;
+        };
+        {
+            let mut last = self.metrics.last_warning.lock().expect("dispatch metrics lock poisoned");
+            let now = Instant::now();
+            if last.is_some_and(|at| now.duration_since(at) < self.config.warn_interval) {
+                return;
+            }
+            *last = Some(now);
+        }
+        callback(&OverflowWarning {
+            policy: self.config.policy,
+            capacity: self.config.capacity,
+            stats: self.metrics.snapshot(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::messages::SolPriceUpdate;
+
+    fn queue(capacity: usize, policy: OverflowPolicy) -> DispatchQueue {
+        let config = DispatchConfig {
+            capacity,
+            policy,
+            ..Default::default()
+        };
+        DispatchQueue::new(config, Arc::new(DispatchMetrics::new()))
+    }
+
+    fn error(text: &str) -> Delivery {
+        Delivery::Error(text.to_string())
+    }
+
+    fn price(price_usd: f64) -> Delivery {
+        Delivery::Message(WebSocketMessage::SolPrice(SolPriceUpdate { price_usd, timestamp: 0 }))
+    }
+
+    async fn drain(queue: &DispatchQueue) -> Vec<String> {
+        queue.close();
+        let mut out = Vec::new();
+        while let Some(delivery) = queue.pop().await {
+            out.push(match delivery {
+                Delivery::Message(WebSocketMessage::SolPrice(p)) => p.price_usd.to_string(),
+                Delivery::Error(e) | Delivery::Connected(e) | Delivery::Disconnected(e) => e,
+                Delivery::Message(_) => "other".to_string(),
+            });
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn test_drop_policies_keep_control_events() {
+        let oldest = queue(2, OverflowPolicy::DropOldest);
+        oldest.push_control(Delivery::Disconnected("bye".to_string()));
+        for text in ["a", "b", "c"] {
+            oldest.push(error(text), None).await;
+        }
+        assert_eq!(drain(&oldest).await, vec!["bye", "b", "c"]);
+        assert_eq!(oldest.metrics.snapshot().dropped_oldest, 1);
+
+        let newest = queue(2, OverflowPolicy::DropNewest);
+        newest.push_control(Delivery::Connected("hello".to_string()));
+        for text in ["a", "b", "c"] {
+            newest.push(error(text), None).await;
+        }
+        assert_eq!(drain(&newest).await, vec!["hello", "a", "b"]);
+        assert_eq!(newest.metrics.snapshot().dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_keeps_position_and_latest_value() {
+        let queue = queue(8, OverflowPolicy::CoalesceByKey);
+        queue.push(price(1.0), Some("sol_price")).await;
+        queue.push(error("new pair"), None).await;
+        queue.push(price(2.0), Some("sol_price")).await;
+        queue.push(price(3.0), Some("sol_price")).await;
+
+        assert_eq!(drain(&queue).await, vec!["3", "new pair"]);
+        let stats = queue.metrics.snapshot();
+        assert_eq!((stats.coalesced, stats.high_water, stats.queued), (2, 2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_consumer_and_warns() {
+        let warnings = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&warnings);
+        let config = DispatchConfig {
+            capacity: 1,
+            on_overflow: Some(Arc::new(move |warning: &OverflowWarning| {
+                assert_eq!(warning.policy, OverflowPolicy::Block);
+                counter.fetch_add(1, Ordering::Relaxed);
+            })),
+            ..Default::default()
+        };
+        let queue = Arc::new(DispatchQueue::new(config, Arc::new(DispatchMetrics::new())));
+
+        queue.push(error("a"), None).await;
+        let producer = tokio::spawn({
+            let queue = Arc::clone(&queue);
+            async move { queue.push(error("b"), None).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!producer.is_finished());
+
+        assert!(matches!(queue.pop().await, Some(Delivery::Error(e)) if e == "a"));
+        producer.await.unwrap();
+        assert_eq!(drain(&queue).await, vec!["b"]);
+        assert_eq!(queue.metrics.snapshot().blocked, 1);
+        assert_eq!(warnings.load(Ordering::Relaxed), 1);
+    }
+}
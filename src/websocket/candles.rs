@@ -0,0 +1,570 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::api::market_data::{MarketDataClient, MarketDataError};
+use crate::models::market::{Candle, ChartTimeframe};
+use crate::websocket::messages::{MarketUpdate, TradeUpdate, WebSocketMessage};
+
+/// Timestamps above this are treated as milliseconds
+const MILLISECOND_THRESHOLD: i64 = 100_000_000_000;
+
+/// Which `MarketUpdate` price feeds the bars
+///
+/// `TradeUpdate` prices are quoted in USD, so trades only feed `Usd` bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceSource {
+    #[default]
+    Sol,
+    Usd,
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregatorConfig {
+    pub price_source: PriceSource,
+    /// Emit flat, zero-volume bars for intervals without ticks
+    pub fill_gaps: bool,
+    /// How far back (seconds) a late tick may still revise a closed bar
+    pub max_late_secs: i64,
+    /// Closed bars kept per timeframe
+    pub history_len: usize,
+}
+
+impl Default for AggregatorConfig {
+    fn default() -> Self {
+        Self {
+            price_source: PriceSource::Sol,
+            fill_gaps: true,
+            max_late_secs: 60,
+            history_len: 1_000,
+        }
+    }
+}
+
+/// A change to a bar caused by a tick or by time advancing
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandleEvent {
+    /// The in-progress bar changed
+    Updated { timeframe: ChartTimeframe, candle: Candle },
+    /// A bar finished; it will only change again through `Revised`
+    Closed { timeframe: ChartTimeframe, candle: Candle },
+    /// A late tick amended an already closed bar
+    Revised { timeframe: ChartTimeframe, candle: Candle },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AggregatorStats {
+    pub ticks: u64,
+    pub late_revisions: u64,
+    pub late_dropped: u64,
+    pub gap_bars: u64,
+    /// Trades skipped because their USD price does not match the configured source
+    pub skipped_trades: u64,
+}
+
+/// A bar plus the times of its first and last tick, so late ticks set open and close correctly
+#[derive(Debug, Clone)]
+struct Bar {
+    candle: Candle,
+    first_tick: i64,
+    last_tick: i64,
+}
+
+impl Bar {
+    fn new(start: i64, timestamp: i64, price: f64, volume: f64) -> Self {
+        Self {
+            candle: Candle {
+                timestamp: start,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume,
+            },
+            first_tick: timestamp,
+            last_tick: timestamp,
+        }
+    }
+
+    fn flat(start: i64, price: f64) -> Self {
+        Self::new(start, start, price, 0.0)
+    }
+
+    fn seeded(candle: &Candle) -> Self {
+        Self {
+            candle: candle.clone(),
+            first_tick: candle.timestamp,
+            last_tick: candle.timestamp,
+        }
+    }
+
+    fn apply(&mut self, timestamp: i64, price: f64, volume: f64) {
+        let candle = &mut self.candle;
+        candle.high = candle.high.max(price);
+        candle.low = candle.low.min(price);
+        candle.volume += volume;
+        if timestamp < self.first_tick {
+            candle.open = price;
+            self.first_tick = timestamp;
+        }
+        if timestamp >= self.last_tick {
+            candle.close = price;
+            self.last_tick = timestamp;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Series {
+    closed: VecDeque<Bar>,
+    current: Option<Bar>,
+}
+
+/// Builds OHLCV bars for one token across several timeframes from WebSocket ticks.
+///
+/// Trade updates contribute price and volume; market updates contribute price only.
+/// Timestamps are Unix seconds; millisecond timestamps are detected and converted.
+pub struct CandleAggregator {
+    token_mint: String,
+    config: AggregatorConfig,
+    series: HashMap<ChartTimeframe, Series>,
+    timeframes: Vec<ChartTimeframe>,
+    stats: AggregatorStats,
+}
+
+impl CandleAggregator {
+    /// Creates an aggregator for the given timeframes
+    ///
+    /// # Arguments
+    ///
+    /// * `token_mint` - &str - Token whose ticks are aggregated; other tokens are ignored
+    /// * `timeframes` - &[ChartTimeframe] - Bar sizes to build
+    ///
+    /// # Returns
+    ///
+    /// CandleAggregator - A new aggregator with the default configuration
+    pub fn new(token_mint: &str, timeframes: &[ChartTimeframe]) -> Self {
+        Self::with_config(token_mint, timeframes, AggregatorConfig::default())
+    }
+
+    /// Creates an aggregator that builds every `ChartTimeframe`
+    pub fn all_timeframes(token_mint: &str) -> Self {
+        Self::new(token_mint, &ChartTimeframe::ALL)
+    }
+
+    /// Creates an aggregator with a custom configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `token_mint` - &str - Token whose ticks are aggregated
+    /// * `timeframes` - &[ChartTimeframe] - Bar sizes to build
+    /// * `config` - AggregatorConfig - Price source, gap and late tick handling
+    ///
+    /// # Returns
+    ///
+    /// CandleAggregator - A new aggregator
+    pub fn with_config(token_mint: &str, timeframes: &[ChartTimeframe], config: AggregatorConfig) -> Self {
+        let mut unique = timeframes.to_vec();
+        unique.sort_by_key(|tf| tf.as_secs());
+        unique.dedup();
+        Self {
+            token_mint: token_mint.to_string(),
+            config,
+            series: unique.iter().map(|tf| (*tf, Series::default())).collect(),
+            timeframes: unique,
+            stats: AggregatorStats::default(),
+        }
+    }
+
+    pub fn token_mint(&self) -> &str {
+        &self.token_mint
+    }
+
+    pub fn timeframes(&self) -> &[ChartTimeframe] {
+        &self.timeframes
+    }
+
+    pub fn stats(&self) -> AggregatorStats {
+        self.stats
+    }
+
+    /// Seeds a timeframe with historical bars so live bars continue the same series.
+    ///
+    /// The newest historical bar becomes the in-progress bar, so live ticks in the same
+    /// interval extend it instead of starting a duplicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeframe` - ChartTimeframe - The timeframe the candles belong to
+    /// * `candles` - &[Candle] - Historical candles in any order
+    pub fn seed(&mut self, timeframe: ChartTimeframe, candles: &[Candle]) {
+        let Some(series) = self.series.get_mut(&timeframe) else {
+            return;
+        };
+
+        let mut history: Vec<&Candle> = candles.iter().collect();
+        history.sort_by_key(|c| c.timestamp);
+        history.dedup_by_key(|c| c.timestamp);
+
+        series.closed = history.iter().map(|c| Bar::seeded(c)).collect();
+        series.current = series.closed.pop_back();
+        while series.closed.len() > self.config.history_len {
+            series.closed.pop_front();
+        }
+    }
+
+    /// Seeds every timeframe from `get_token_chart`
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - &mut MarketDataClient - Client used to fetch history
+    /// * `limit` - Option<usize> - Maximum candles per timeframe
+    ///
+    /// # Returns
+    ///
+    /// Result<(), MarketDataError> - Ok once every timeframe is seeded
+    pub async fn seed_from_chart(
+        &mut self,
+        client: &mut MarketDataClient,
+        limit: Option<usize>,
+    ) -> Result<(), MarketDataError> {
+        for timeframe in self.timeframes.clone() {
+            let chart = client.get_token_chart(&self.token_mint, timeframe, limit).await?;
+            self.seed(timeframe, &chart.candles);
+        }
+        Ok(())
+    }
+
+    /// Feeds any WebSocket message; messages that are not ticks for this token are ignored
+    pub fn on_message(&mut self, message: &WebSocketMessage) -> Vec<CandleEvent> {
+        match message {
+            WebSocketMessage::MarketUpdate(update) => self.on_market_update(update),
+            WebSocketMessage::TradeUpdate(trade) => self.on_trade_update(trade),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Feeds a price update. Contributes price but no volume.
+    pub fn on_market_update(&mut self, update: &MarketUpdate) -> Vec<CandleEvent> {
+        if update.token_mint != self.token_mint {
+            return Vec::new();
+        }
+        let price = match self.config.price_source {
+            PriceSource::Sol => update.price_sol,
+            PriceSource::Usd => update.price_usd,
+        };
+        self.on_tick(update.timestamp, price, 0.0)
+    }
+
+    /// Feeds a trade. Contributes price and traded amount as volume.
+    ///
+    /// Trade prices are in USD, so trades are skipped unless the price source is `Usd`.
+    pub fn on_trade_update(&mut self, trade: &TradeUpdate) -> Vec<CandleEvent> {
+        if trade.token_mint != self.token_mint {
+            return Vec::new();
+        }
+        if self.config.price_source != PriceSource::Usd {
+            self.stats.skipped_trades += 1;
+            return Vec::new();
+        }
+        self.on_tick(trade.timestamp, trade.price, trade.amount)
+    }
+
+    /// Feeds a raw tick into every timeframe
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - i64 - Tick time in seconds or milliseconds
+    /// * `price` - f64 - Tick price
+    /// * `volume` - f64 - Volume traded at the tick
+    ///
+    /// # Returns
+    ///
+    /// Vec<CandleEvent> - Bars closed, revised or updated by the tick
+    pub fn on_tick(&mut self, timestamp: i64, price: f64, volume: f64) -> Vec<CandleEvent> {
+        if !price.is_finite() || price <= 0.0 {
+            return Vec::new();
+        }
+        let timestamp = normalize_timestamp(timestamp);
+        self.stats.ticks += 1;
+
+        let mut events = Vec::new();
+        for timeframe in self.timeframes.clone() {
+            self.tick_timeframe(timeframe, timestamp, price, volume, &mut events);
+        }
+        events
+    }
+
+    /// Closes bars whose interval ended before `now`, for quiet tokens with no new ticks
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - i64 - Current time in seconds or milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Vec<CandleEvent> - Closed bars, including gap bars if enabled
+    pub fn advance_to(&mut self, now: i64) -> Vec<CandleEvent> {
+        let now = normalize_timestamp(now);
+        let mut events = Vec::new();
+        for timeframe in self.timeframes.clone() {
+            let bucket = timeframe.bucket_start(now);
+            let series = self.series.get_mut(&timeframe).expect("series exists for timeframe");
+            let Some(current) = &series.current else {
+                continue;
+            };
+            if current.candle.timestamp >= bucket {
+                continue;
+            }
+            let last_close = current.candle.close;
+            let closed = series.current.take().expect("checked above");
+            self.close_bar(timeframe, closed, &mut events);
+            self.fill_gaps(timeframe, last_close, bucket, &mut events);
+        }
+        events
+    }
+
+    /// Gets the in-progress bar
+    pub fn current(&self, timeframe: ChartTimeframe) -> Option<&Candle> {
+        self.series.get(&timeframe)?.current.as_ref().map(|bar| &bar.candle)
+    }
+
+    /// Gets closed bars, oldest first
+    pub fn closed(&self, timeframe: ChartTimeframe) -> Vec<Candle> {
+        self.series
+            .get(&timeframe)
+            .map(|series| series.closed.iter().map(|bar| bar.candle.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets closed bars followed by the in-progress bar
+    pub fn candles(&self, timeframe: ChartTimeframe) -> Vec<Candle> {
+        let mut candles = self.closed(timeframe);
+        candles.extend(self.current(timeframe).cloned());
+        candles
+    }
+
+    fn tick_timeframe(
+        &mut self,
+        timeframe: ChartTimeframe,
+        timestamp: i64,
+        price: f64,
+        volume: f64,
+        events: &mut Vec<CandleEvent>,
+    ) {
+        let bucket = timeframe.bucket_start(timestamp);
+        let series = self.series.get_mut(&timeframe).expect("series exists for timeframe");
+
+        let current_start = series.current.as_ref().map(|bar| bar.candle.timestamp);
+        match current_start {
+            Some(start) if bucket == start => {
+                let bar = series.current.as_mut().expect("checked above");
+                bar.apply(timestamp, price, volume);
+                events.push(CandleEvent::Updated {
+                    timeframe,
+                    candle: bar.candle.clone(),
+                });
+            }
+            Some(start) if bucket < start => self.late_tick(timeframe, bucket, timestamp, price, volume, events),
+            _ => {
+                let previous_close = match series.current.take() {
+                    Some(bar) => {
+                        let close = bar.candle.close;
+                        self.close_bar(timeframe, bar, events);
+                        Some(close)
+                    }
+                    None => {
+                        let series = &self.series[&timeframe];
+                        match series.closed.back() {
+                            Some(last) if last.candle.timestamp >= bucket => {
+                                return self.late_tick(timeframe, bucket, timestamp, price, volume, events);
+                            }
+                            Some(last) => Some(last.candle.close),
+                            None => None,
+                        }
+                    }
+                };
+                if let Some(close) = previous_close {
+                    self.fill_gaps(timeframe, close, bucket, events);
+                }
+
+                let bar = Bar::new(bucket, timestamp, price, volume);
+                events.push(CandleEvent::Updated {
+                    timeframe,
+                    candle: bar.candle.clone(),
+                });
+                self.series.get_mut(&timeframe).expect("series exists for timeframe").current = Some(bar);
+            }
+        }
+    }
+
+    fn late_tick(
+        &mut self,
+        timeframe: ChartTimeframe,
+        bucket: i64,
+        timestamp: i64,
+        price: f64,
+        volume: f64,
+        events: &mut Vec<CandleEvent>,
+    ) {
+        let max_late_secs = self.config.max_late_secs;
+        let series = self.series.get_mut(&timeframe).expect("series exists for timeframe");
+        let newest = series
+            .current
+            .as_ref()
+            .or(series.closed.back())
+            .map(|bar| bar.last_tick.max(bar.candle.timestamp))
+            .unwrap_or(timestamp);
+
+        let target = series.closed.iter_mut().rev().find(|bar| bar.candle.timestamp == bucket);
+        match target {
+            Some(bar) if newest - timestamp <= max_late_secs => {
+                bar.apply(timestamp, price, volume);
+                self.stats.late_revisions += 1;
+                events.push(CandleEvent::Revised {
+                    timeframe,
+                    candle: bar.candle.clone(),
+                });
+            }
+            _ => self.stats.late_dropped += 1,
+        }
+    }
+
+    fn close_bar(&mut self, timeframe: ChartTimeframe, bar: Bar, events: &mut Vec<CandleEvent>) {
+        events.push(CandleEvent::Closed {
+            timeframe,
+            candle: bar.candle.clone(),
+        });
+        let history_len = self.config.history_len;
+        let series = self.series.get_mut(&timeframe).expect("series exists for timeframe");
+        series.closed.push_back(bar);
+        while series.closed.len() > history_len {
+            series.closed.pop_front();
+        }
+    }
+
+    /// Emits flat bars between the last closed bar and `until` (exclusive)
+    fn fill_gaps(&mut self, timeframe: ChartTimeframe, close: f64, until: i64, events: &mut Vec<CandleEvent>) {
+        if !self.config.fill_gaps {
+            return;
+        }
+        let Some(last_start) = self.series[&timeframe].closed.back().map(|bar| bar.candle.timestamp) else {
+            return;
+        };
+
+        let mut start = timeframe.bucket_start(last_start + timeframe.as_secs());
+        // Bound the work after long outages to the retained history
+        let max_gap_bars = self.config.history_len as i64;
+        let skip = ((until - start) / timeframe.as_secs() - max_gap_bars).max(0);
+        start += skip * timeframe.as_secs();
+
+        while start < until {
+            self.stats.gap_bars += 1;
+            self.close_bar(timeframe, Bar::flat(start, close), events);
+            start = timeframe.bucket_start(start + timeframe.as_secs());
+        }
+    }
+}
+
+fn normalize_timestamp(timestamp: i64) -> i64 {
+    if timestamp > MILLISECOND_THRESHOLD {
+        timestamp / 1_000
+    } else {
+        timestamp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closed_count(events: &[CandleEvent]) -> usize {
+        events
+            .iter()
+            .filter(|e| matches!(e, CandleEvent::Closed { .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_builds_bars_across_timeframes() {
+        let mut aggregator = CandleAggregator::new(
+            "mint",
+            &[ChartTimeframe::OneMinute, ChartTimeframe::FiveMinutes],
+        );
+        aggregator.on_tick(0, 1.0, 1.0);
+        aggregator.on_tick(30, 3.0, 2.0);
+        aggregator.on_tick(45, 0.5, 1.0);
+        let events = aggregator.on_tick(60, 2.0, 1.0);
+
+        assert_eq!(closed_count(&events), 1);
+        let minute = aggregator.closed(ChartTimeframe::OneMinute);
+        assert_eq!(minute.len(), 1);
+        let bar = &minute[0];
+        assert_eq!((bar.open, bar.high, bar.low, bar.close, bar.volume), (1.0, 3.0, 0.5, 0.5, 4.0));
+
+        let five = aggregator.current(ChartTimeframe::FiveMinutes).unwrap();
+        assert_eq!((five.open, five.close, five.volume), (1.0, 2.0, 5.0));
+    }
+
+    #[test]
+    fn test_gap_bars_and_late_ticks() {
+        let mut aggregator = CandleAggregator::new("mint", &[ChartTimeframe::OneMinute]);
+        aggregator.on_tick(10, 1.0, 1.0);
+        let events = aggregator.on_tick(190, 2.0, 1.0);
+        // The 0s bar closes and 60s / 120s are filled flat
+        assert_eq!(closed_count(&events), 3);
+        assert_eq!(aggregator.closed(ChartTimeframe::OneMinute)[2].volume, 0.0);
+
+        // A late tick inside the tolerance revises the 120s bar; an old one is dropped
+        let events = aggregator.on_tick(150, 5.0, 1.0);
+        assert!(matches!(events[0], CandleEvent::Revised { .. }));
+        assert_eq!(aggregator.closed(ChartTimeframe::OneMinute)[2].high, 5.0);
+        assert!(aggregator.on_tick(20, 9.0, 1.0).is_empty());
+        assert_eq!(aggregator.stats().late_dropped, 1);
+    }
+
+    #[test]
+    fn test_trades_only_feed_usd_bars() {
+        let trade = TradeUpdate {
+            token_mint: "mint".to_string(),
+            side: crate::websocket::messages::OrderSide::Buy,
+            price: 0.25,
+            amount: 100.0,
+            total_value: 25.0,
+            timestamp: 30,
+            transaction_hash: "sig".to_string(),
+        };
+
+        let mut sol = CandleAggregator::new("mint", &[ChartTimeframe::OneMinute]);
+        assert!(sol.on_trade_update(&trade).is_empty());
+        assert_eq!(sol.stats().skipped_trades, 1);
+        assert!(sol.current(ChartTimeframe::OneMinute).is_none());
+
+        let config = AggregatorConfig { price_source: PriceSource::Usd, ..AggregatorConfig::default() };
+        let mut usd = CandleAggregator::with_config("mint", &[ChartTimeframe::OneMinute], config);
+        usd.on_trade_update(&trade);
+        let bar = usd.current(ChartTimeframe::OneMinute).unwrap();
+        assert_eq!((bar.close, bar.volume), (0.25, 100.0));
+    }
+
+    #[test]
+    fn test_seeded_history_continues_live() {
+        let history: Vec<Candle> = [0, 60, 120]
+            .iter()
+            .map(|&timestamp| Candle {
+                timestamp,
+                open: 1.0,
+                high: 1.5,
+                low: 0.5,
+                close: 1.0,
+                volume: 10.0,
+            })
+            .collect();
+
+        let mut aggregator = CandleAggregator::new("mint", &[ChartTimeframe::OneMinute]);
+        aggregator.seed(ChartTimeframe::OneMinute, &history);
+        aggregator.on_tick(150, 2.0, 1.0);
+
+        let candles = aggregator.candles(ChartTimeframe::OneMinute);
+        assert_eq!(candles.len(), 3);
+        assert_eq!((candles[2].high, candles[2].close, candles[2].volume), (2.0, 2.0, 11.0));
+    }
+}
@@ -1,14 +1,26 @@
 use crate::auth::{AuthClient, AuthError};
 use crate::websocket::handler::MessageHandler;
-use crate::websocket::messages::{WebSocketMessage, SubscriptionType};
-use futures_util::{SinkExt, StreamExt};
+use crate::websocket::heartbeat::{ConnectionHealth, HealthMonitor, HeartbeatConfig};
+use crate::websocket::reconnect::ReconnectConfig;
+use crate::websocket::selector::{HostLatency, RegionSelector, SelectorConfig};
+use crate::websocket::backpressure::{Delivery, DispatchConfig, DispatchMetrics, DispatchQueue, DispatchStats};
+use crate::websocket::dispatch::FrameDispatcher;
+use crate::websocket::recorder::SessionRecorder;
+use crate::websocket::rooms::{DecodeMetrics, DecodeStats};
+use crate::websocket::messages::{
+    NewPairEvent, SubscriptionType, TokenPriceTick, WalletTransactionEvent,
+};
+use crate::websocket::stream::{StreamStats, Subscription, SubscriptionRegistry, DEFAULT_STREAM_BUFFER};
+use crate::websocket::transport::{InboundFrame, TransportError, TransportKind};
 use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::RwLock;
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
-use serde_json::json;
-use rand::Rng;
+use std::time::Instant;
+use tokio::sync::{mpsc, Notify, RwLock};
+use tokio_tungstenite::tungstenite::protocol::Message;
 use tokio::time::{interval, Duration};
 
 #[derive(Error, Debug)]
@@ -36,6 +48,12 @@ pub enum WebSocketError {
     
     #[error("HTTP error: {0}")]
     HttpError(#[from] http::Error),
+    
+    #[error("Transport error: {0}")]
+    TransportError(#[from] TransportError),
+    
+    #[error("Subscription conflict: {0}")]
+    SubscriptionConflict(String),
 }
 
 #[derive(Clone, Debug)]
@@ -49,11 +67,25 @@ pub enum Region {
     Asia,
     Australia,
     Global,
+    /// Every cluster host, ranked by latency
+    Auto,
 }
 
 impl Region {
-    /// Gets WebSocket URLs for the region
-    fn get_urls(&self) -> Vec<&'static str> {
+    const FIXED: [Region; 9] = [
+        Region::USWest,
+        Region::USCentral,
+        Region::USEast,
+        Region::EUWest,
+        Region::EUCentral,
+        Region::EUEast,
+        Region::Asia,
+        Region::Australia,
+        Region::Global,
+    ];
+    
+    /// Gets WebSocket hosts for the region
+    pub fn get_urls(&self) -> Vec<&'static str> {
         match self {
             Region::USWest => vec!["socket8.axiom.trade", "cluster-usw2.axiom.trade"],
             Region::USCentral => vec!["cluster3.axiom.trade", "cluster-usc2.axiom.trade"],
@@ -64,29 +96,61 @@ impl Region {
             Region::Asia => vec!["cluster4.axiom.trade"],
             Region::Australia => vec!["cluster7.axiom.trade"],
             Region::Global => vec!["cluster9.axiom.trade"],
+            Region::Auto => Self::FIXED.iter().flat_map(Region::get_urls).collect(),
         }
     }
-    
-    /// Randomly selects a URL from the region's available URLs
-    fn get_random_url(&self) -> &'static str {
-        let urls = self.get_urls();
-        let mut rng = rand::thread_rng();
-        let index = rng.gen_range(0..urls.len());
-        urls[index]
+}
+
+/// Builds the WebSocket upgrade request the web app sends
+/// 
+/// # Arguments
+/// 
+/// * `host` - &str - Cluster host
+/// * `cookie` - Option<&str> - Auth cookie header value; probes connect without one
+/// 
+/// # Returns
+/// 
+/// Result<http::Request<()>, http::Error> - The upgrade request
+pub(crate) fn handshake_request(host: &str, cookie: Option<&str>) -> Result<http::Request<()>, http::Error> {
+    let mut builder = http::Request::builder()
+        .method("GET")
+        .uri(format!("wss://{}/", host))
+        .header("Host", host)
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key());
+    if let Some(cookie) = cookie {
+        builder = builder.header("Cookie", cookie);
     }
+    builder
+        .header("Origin", "https://axiom.trade")
+        .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+        .header("Cache-Control", "no-cache")
+        .header("Pragma", "no-cache")
+        .header("Accept-Language", "en-US,en;q=0.9")
+        .body(())
 }
 
 pub struct WebSocketClient {
     auth_client: Arc<RwLock<AuthClient>>,
     region: Region,
+    selector: Arc<RegionSelector>,
     handler: Arc<dyn MessageHandler>,
     subscriptions: Arc<RwLock<HashSet<SubscriptionType>>>,
-    sender: Option<futures_util::stream::SplitSink<
-        tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
-        Message
-    >>,
+    registry: Arc<SubscriptionRegistry>,
+    stream_buffer: usize,
     is_connected: Arc<RwLock<bool>>,
+    generation: Arc<AtomicU64>,
     reconnect_on_expire: bool,
+    reconnect_config: ReconnectConfig,
+    heartbeat_config: HeartbeatConfig,
+    health: Arc<HealthMonitor>,
+    decode_metrics: Arc<DecodeMetrics>,
+    dispatch_config: DispatchConfig,
+    dispatch_metrics: Arc<DispatchMetrics>,
+    recorder: Option<(Arc<SessionRecorder>, String)>,
+    transport: TransportKind,
     is_token_price: bool,
 }
 
@@ -104,11 +168,22 @@ impl WebSocketClient {
         Ok(Self {
             auth_client: Arc::new(RwLock::new(AuthClient::new()?)),
             region: Region::Global,
+            selector: Arc::new(RegionSelector::new(&Region::Global, SelectorConfig::default())),
             handler,
             subscriptions: Arc::new(RwLock::new(HashSet::new())),
-            sender: None,
+            registry: Arc::new(SubscriptionRegistry::new()),
+            stream_buffer: DEFAULT_STREAM_BUFFER,
             is_connected: Arc::new(RwLock::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
             reconnect_on_expire: true,
+            reconnect_config: ReconnectConfig::default(),
+            heartbeat_config: HeartbeatConfig::default(),
+            health: Arc::new(HealthMonitor::new()),
+            decode_metrics: Arc::new(DecodeMetrics::new()),
+            dispatch_config: DispatchConfig::default(),
+            dispatch_metrics: Arc::new(DispatchMetrics::new()),
+            recorder: None,
+            transport: TransportKind::default(),
             is_token_price: false,
         })
     }
@@ -129,12 +204,23 @@ impl WebSocketClient {
     ) -> Result<Self, WebSocketError> {
         Ok(Self {
             auth_client: Arc::new(RwLock::new(AuthClient::new()?)),
+            selector: Arc::new(RegionSelector::new(&region, SelectorConfig::default())),
             region,
             handler,
             subscriptions: Arc::new(RwLock::new(HashSet::new())),
-            sender: None,
+            registry: Arc::new(SubscriptionRegistry::new()),
+            stream_buffer: DEFAULT_STREAM_BUFFER,
             is_connected: Arc::new(RwLock::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
             reconnect_on_expire: true,
+            reconnect_config: ReconnectConfig::default(),
+            heartbeat_config: HeartbeatConfig::default(),
+            health: Arc::new(HealthMonitor::new()),
+            decode_metrics: Arc::new(DecodeMetrics::new()),
+            dispatch_config: DispatchConfig::default(),
+            dispatch_metrics: Arc::new(DispatchMetrics::new()),
+            recorder: None,
+            transport: TransportKind::default(),
             is_token_price: false,
         })
     }
@@ -161,135 +247,54 @@ impl WebSocketClient {
     async fn connect_with_token_price(&mut self, is_token_price: bool) -> Result<(), WebSocketError> {
         self.is_token_price = is_token_price;
         
-        let auth_tokens = self.auth_client.write().await.ensure_valid_authentication().await
-            .map_err(WebSocketError::AuthError)?;
-        
-        // Get URL for connection
-        let host = if is_token_price {
-            "socket8.axiom.trade"
-        } else {
-            self.region.get_random_url()
-        };
-        
-        let url = format!("wss://{}/", host);
+        let context = self.context();
+        let url = context.open().await?;
+        self.handler.on_connected(url).await;
         
-        // Build the HTTP request with custom headers
-        let request = http::Request::builder()
-            .method("GET")
-            .uri(&url)
-            .header("Host", host)
-            .header("Connection", "Upgrade")
-            .header("Upgrade", "websocket")
-            .header("Sec-WebSocket-Version", "13")
-            .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key())
-            // Add custom headers for authentication
-            .header("Cookie", format!("auth-access-token={}; auth-refresh-token={}", 
-                auth_tokens.access_token, auth_tokens.refresh_token))
-            .header("Origin", "https://axiom.trade")
-            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-            .header("Cache-Control", "no-cache")
-            .header("Pragma", "no-cache")
-            .header("Accept-Language", "en-US,en;q=0.9")
-            .body(())?;
-        
-        // Connect to WebSocket
-        match connect_async(request).await {
-            Ok((ws_stream, _response)) => {
-                *self.is_connected.write().await = true;
-                
-                let (write, mut read) = ws_stream.split();
-                self.sender = Some(write);
-                
-                let handler = Arc::clone(&self.handler);
-                let is_connected = Arc::clone(&self.is_connected);
-                
-                // Spawn read task
-                tokio::spawn(async move {
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(Message::Text(text)) => {
-                                // Try to parse as WebSocketMessage first
-                                if let Ok(ws_msg) = serde_json::from_str::<WebSocketMessage>(&text) {
-                                    match &ws_msg {
-                                        WebSocketMessage::Connected { session_id } => {
-                                            handler.on_connected(session_id.clone()).await;
-                                        }
-                                        WebSocketMessage::Disconnected { reason } => {
-                                            *is_connected.write().await = false;
-                                            handler.on_disconnected(reason.clone()).await;
-                                        }
-                                        _ => {}
-                                    }
-                                    handler.handle_message(ws_msg).await;
-                                } else if let Ok(data) = serde_json::from_str::<serde_json::Value>(&text) {
-                                    // Handle raw JSON messages from the server
-                                    if let Some(room) = data.get("room").and_then(|r| r.as_str()) {
-                                        if room == "new_pairs" {
-                                            if let Some(content) = data.get("content") {
-                                                // Convert to MarketUpdate
-                                                let update = crate::websocket::messages::MarketUpdate {
-                                                    token_mint: content.get("token_address")
-                                                        .and_then(|a| a.as_str())
-                                                        .unwrap_or("")
-                                                        .to_string(),
-                                                    symbol: content.get("token_ticker")
-                                                        .and_then(|s| s.as_str())
-                                                        .unwrap_or("")
-                                                        .to_string(),
-                                                    price_usd: content.get("initial_liquidity_sol")
-                                                        .and_then(|p| p.as_f64())
-                                                        .unwrap_or(0.0),
-                                                    price_sol: 0.0,
-                                                    price_change_24h: 0.0,
-                                                    volume_24h: 0.0,
-                                                    market_cap: content.get("supply")
-                                                        .and_then(|s| s.as_f64())
-                                                        .unwrap_or(0.0),
-                                                    timestamp: chrono::Utc::now().timestamp(),
-                                                };
-                                                handler.handle_message(WebSocketMessage::MarketUpdate(update)).await;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            Ok(Message::Close(_)) => {
-                                *is_connected.write().await = false;
-                                handler.on_disconnected("Connection closed".to_string()).await;
-                                break;
-                            }
-                            Err(e) => {
-                                *is_connected.write().await = false;
-                                handler.on_error(format!("WebSocket error: {}", e)).await;
-                                break;
-                            }
-                            _ => {}
-                        }
-                    }
-                });
-                
-                if self.reconnect_on_expire {
-                    self.spawn_token_refresh_task();
-                }
-                
-                self.handler.on_connected(url).await;
-                Ok(())
-            }
-            Err(e) => {
-                Err(WebSocketError::WebSocketError(e))
-            }
+        // Streams that outlived a previous connection pick up where they left off
+        context.resubscribe().await;
+        Ok(())
+    }
+    
+    /// Snapshot of the state a connection and its tasks need
+    fn context(&self) -> ConnectionContext {
+        ConnectionContext {
+            auth_client: Arc::clone(&self.auth_client),
+            selector: Arc::clone(&self.selector),
+            handler: Arc::clone(&self.handler),
+            registry: Arc::clone(&self.registry),
+            is_connected: Arc::clone(&self.is_connected),
+            generation: Arc::clone(&self.generation),
+            is_token_price: self.is_token_price,
+            refresh_tokens: self.reconnect_on_expire,
+            reconnect: self.reconnect_config.clone(),
+            heartbeat: self.heartbeat_config.clone(),
+            health: Arc::clone(&self.health),
+            decode_metrics: Arc::clone(&self.decode_metrics),
+            dispatch: self.dispatch_config.clone(),
+            dispatch_metrics: Arc::clone(&self.dispatch_metrics),
+            recorder: self.recorder.clone(),
+            transport: self.transport,
         }
     }
     
-    /// Disconnects from the WebSocket server
-    pub async fn disconnect(&mut self) {
+    /// Closes the socket without forgetting subscriptions
+    async fn close(&mut self) {
+        // Moving to a new generation stops the old read task from reconnecting
+        self.generation.fetch_add(1, Ordering::SeqCst);
         *self.is_connected.write().await = false;
         
-        if let Some(mut sender) = self.sender.take() {
-            let _ = sender.send(Message::Close(None)).await;
-            let _ = sender.close().await;
-        }
+        // The writer task sends the close frame, then closes the sink once the channel is dropped
+        let _ = self.registry.send(Message::Close(None));
+        self.registry.set_outbound(None);
+    }
+    
+    /// Disconnects from the WebSocket server
+    pub async fn disconnect(&mut self) {
+        self.close().await;
         
+        self.registry.forget_joined();
+        self.registry.close_all();
         self.subscriptions.write().await.clear();
         self.handler.on_disconnected("Manual disconnect".to_string()).await;
     }
@@ -304,12 +309,7 @@ impl WebSocketClient {
             return Err(WebSocketError::NotConnected);
         }
         
-        let msg = json!({
-            "action": "join",
-            "room": "new_pairs"
-        });
-        
-        self.send_message(msg).await?;
+        self.registry.join("new_pairs")?;
         self.subscriptions.write().await.insert(SubscriptionType::MarketData);
         
         Ok(())
@@ -329,12 +329,7 @@ impl WebSocketClient {
             return Err(WebSocketError::NotConnected);
         }
         
-        let msg = json!({
-            "action": "join",
-            "room": token_address
-        });
-        
-        self.send_message(msg).await?;
+        self.registry.join(token_address)?;
         self.subscriptions.write().await.insert(SubscriptionType::PriceAlerts);
         
         Ok(())
@@ -354,38 +349,112 @@ impl WebSocketClient {
             return Err(WebSocketError::NotConnected);
         }
         
-        let msg = json!({
-            "action": "join",
-            "room": format!("v:{}", wallet_address)
-        });
-        
-        self.send_message(msg).await?;
+        self.registry.join(&format!("v:{}", wallet_address))?;
         self.subscriptions.write().await.insert(SubscriptionType::Portfolio);
         
         Ok(())
     }
     
-    /// Sends a message to the WebSocket
+    /// Joins any room for the message handler, e.g. `sol_price` or `p:<pair>`
     /// 
     /// # Arguments
     /// 
-    /// * `message` - serde_json::Value - The message to send
+    /// * `room` - &str - The room to join
     /// 
     /// # Returns
     /// 
-    /// Result<(), WebSocketError> - Ok if sent successfully
-    async fn send_message(&mut self, message: serde_json::Value) -> Result<(), WebSocketError> {
-        if let Some(sender) = &mut self.sender {
-            let json = serde_json::to_string(&message)
-                .map_err(|e| WebSocketError::SerializationError(e.to_string()))?;
-            
-            sender.send(Message::Text(json)).await
-                .map_err(|e| WebSocketError::SendError(e.to_string()))?;
-            
-            Ok(())
-        } else {
-            Err(WebSocketError::NotConnected)
+    /// Result<(), WebSocketError> - Ok if subscribed successfully
+    pub async fn subscribe_room(&mut self, room: &str) -> Result<(), WebSocketError> {
+        if !*self.is_connected.read().await {
+            return Err(WebSocketError::NotConnected);
+        }
+        
+        self.registry.join(room)
+    }
+    
+    /// Leaves a room joined for the message handler; stream subscriptions keep it joined
+    /// 
+    /// # Arguments
+    /// 
+    /// * `room` - &str - The room to leave
+    /// 
+    /// # Returns
+    /// 
+    /// Result<(), WebSocketError> - Ok if unsubscribed successfully
+    pub async fn unsubscribe_room(&mut self, room: &str) -> Result<(), WebSocketError> {
+        self.registry.leave(room)
+    }
+    
+    /// Subscribes to new pairs as a typed stream
+    /// 
+    /// # Returns
+    /// 
+    /// Result<Subscription<NewPairEvent>, WebSocketError> - Stream of new pairs; dropping it leaves the room
+    pub async fn subscribe_new_pairs_stream(&self) -> Result<Subscription<NewPairEvent>, WebSocketError> {
+        self.subscribe_stream("new_pairs", SubscriptionType::MarketData).await
+    }
+    
+    /// Subscribes to a token's price ticks as a typed stream
+    /// 
+    /// # Arguments
+    /// 
+    /// * `token_address` - &str - The token address to monitor
+    /// 
+    /// # Returns
+    /// 
+    /// Result<Subscription<TokenPriceTick>, WebSocketError> - Stream of ticks; dropping it leaves the room
+    pub async fn subscribe_token_price_stream(
+        &self,
+        token_address: &str,
+    ) -> Result<Subscription<TokenPriceTick>, WebSocketError> {
+        self.subscribe_stream(token_address, SubscriptionType::PriceAlerts).await
+    }
+    
+    /// Subscribes to a wallet's transactions as a typed stream
+    /// 
+    /// # Arguments
+    /// 
+    /// * `wallet_address` - &str - The wallet address to monitor
+    /// 
+    /// # Returns
+    /// 
+    /// Result<Subscription<WalletTransactionEvent>, WebSocketError> - Stream of transactions; dropping it leaves the room
+    pub async fn subscribe_wallet_transactions_stream(
+        &self,
+        wallet_address: &str,
+    ) -> Result<Subscription<WalletTransactionEvent>, WebSocketError> {
+        self.subscribe_stream(&format!("v:{}", wallet_address), SubscriptionType::Portfolio).await
+    }
+    
+    async fn subscribe_stream<T: crate::websocket::stream::RoomEvent>(
+        &self,
+        room: &str,
+        kind: SubscriptionType,
+    ) -> Result<Subscription<T>, WebSocketError> {
+        if !*self.is_connected.read().await {
+            return Err(WebSocketError::NotConnected);
         }
+        let subscription = self.registry.subscribe(room, self.stream_buffer)?;
+        self.subscriptions.write().await.insert(kind);
+        Ok(subscription)
+    }
+    
+    /// Sets the buffer size for stream subscriptions created afterwards
+    /// 
+    /// # Arguments
+    /// 
+    /// * `buffer` - usize - Events buffered per subscription before new ones are dropped
+    pub fn set_stream_buffer(&mut self, buffer: usize) {
+        self.stream_buffer = buffer.max(1);
+    }
+    
+    /// Gets delivery counters for stream subscriptions
+    /// 
+    /// # Returns
+    /// 
+    /// StreamStats - Delivered, dropped and undecodable event counts
+    pub fn stream_stats(&self) -> StreamStats {
+        self.registry.stats()
     }
     
     /// Checks if the client is connected
@@ -406,20 +475,436 @@ impl WebSocketClient {
         self.subscriptions.read().await.clone()
     }
     
-    /// Set whether to automatically reconnect when tokens expire
+    /// Gets every room the client is in, including stream subscriptions
+    /// 
+    /// # Returns
+    /// 
+    /// Vec<String> - Room names, e.g. `new_pairs`, a token address or `v:{wallet}`
+    pub fn get_subscribed_rooms(&self) -> Vec<String> {
+        self.registry.rooms()
+    }
+    
+    /// Set whether to automatically reconnect when tokens expire or the connection drops
     /// 
     /// # Arguments
     /// 
     /// * `enabled` - bool - Whether to enable auto-reconnect
     pub fn set_auto_reconnect(&mut self, enabled: bool) {
         self.reconnect_on_expire = enabled;
+        self.reconnect_config.enabled = enabled;
+    }
+    
+    /// Sets the backoff used when reconnecting after the connection drops
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - ReconnectConfig - Delays, jitter and attempt limit; applies to later connections
+    pub fn set_reconnect_config(&mut self, config: ReconnectConfig) {
+        self.reconnect_config = config;
+    }
+    
+    /// Sets the keepalive used by later connections
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - HeartbeatConfig - Ping interval and stale timeout
+    pub fn set_heartbeat_config(&mut self, config: HeartbeatConfig) {
+        self.heartbeat_config = config;
+    }
+    
+    /// Gets feed health for the current connection
+    /// 
+    /// # Returns
+    /// 
+    /// ConnectionHealth - Ping round trips, time since the last message and stale disconnects
+    pub fn connection_health(&self) -> ConnectionHealth {
+        self.health.snapshot(Instant::now())
+    }
+    
+    /// Sets how cluster hosts are probed and re-evaluated; resets the current ranking
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - SelectorConfig - Probe timeout and re-evaluation interval
+    pub fn set_selector_config(&mut self, config: SelectorConfig) {
+        self.selector = Arc::new(RegionSelector::new(&self.region, config));
+    }
+    
+    /// Gets the latest host ranking for the client's region
+    /// 
+    /// # Returns
+    /// 
+    /// Vec<HostLatency> - Hosts fastest first; empty until the first connect or probe
+    pub fn host_rankings(&self) -> Vec<HostLatency> {
+        self.selector.rankings()
+    }
+    
+    /// Probes every host in the client's region now
+    /// 
+    /// # Returns
+    /// 
+    /// Vec<HostLatency> - Hosts ranked fastest first, unreachable hosts last
+    pub async fn probe_hosts(&self) -> Vec<HostLatency> {
+        self.selector.probe().await
+    }
+    
+    /// Records every inbound text frame of later connections
+    /// 
+    /// # Arguments
+    /// 
+    /// * `recorder` - Arc<SessionRecorder> - Destination file, shareable between connections
+    /// * `connection_id` - impl Into<String> - Id written with each frame to tell connections apart
+    pub fn set_recorder(&mut self, recorder: Arc<SessionRecorder>, connection_id: impl Into<String>) {
+        self.recorder = Some((recorder, connection_id.into()));
+    }
+    
+    /// Uses a shared authentication client for later connections
+    /// 
+    /// Clients on one session should share its auth client, so a token refresh by one is seen
+    /// by all instead of each rotating the refresh token on its own.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `auth_client` - Arc<RwLock<AuthClient>> - The shared authentication client
+    pub fn set_auth_client(&mut self, auth_client: Arc<RwLock<AuthClient>>) {
+        self.auth_client = auth_client;
+    }
+    
+    /// Gets the authentication client, e.g. to share it with another client
+    pub fn auth_client(&self) -> Arc<RwLock<AuthClient>> {
+        Arc::clone(&self.auth_client)
+    }
+    
+    /// Chooses the WebSocket backend for later connections
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transport` - TransportKind - tungstenite (default) or zero-copy fastwebsockets
+    pub fn set_transport(&mut self, transport: TransportKind) {
+        self.transport = transport;
+    }
+    
+    /// Gets counts of decoded, raw, failed and unrecognized inbound messages
+    /// 
+    /// # Returns
+    /// 
+    /// DecodeStats - Totals since the client was created
+    pub fn decode_stats(&self) -> DecodeStats {
+        self.decode_metrics.snapshot()
+    }
+    
+    /// Sets the queue size and overflow policy between the socket reader and the handler
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - DispatchConfig - Applies from the next connection
+    pub fn set_dispatch_config(&mut self, config: DispatchConfig) {
+        self.dispatch_config = config;
+    }
+    
+    /// Gets queue depth and counts of delivered, dropped, coalesced and blocked messages
+    /// 
+    /// # Returns
+    /// 
+    /// DispatchStats - Totals since the client was created
+    pub fn dispatch_stats(&self) -> DispatchStats {
+        self.dispatch_metrics.snapshot()
+    }
+    
+    /// Reconnect with fresh tokens and rejoin every room
+    /// 
+    /// # Returns
+    /// 
+    /// Result<(), WebSocketError> - Ok if reconnected successfully
+    pub async fn reconnect(&mut self) -> Result<(), WebSocketError> {
+        self.close().await;
+        
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        
+        if self.is_token_price {
+            self.connect_token_price().await?
+        } else {
+            self.connect().await?
+        }
+        
+        self.handler.on_reconnected(1).await;
+        Ok(())
+    }
+}
+
+/// Shared connection state, cloned into the read task so it can reconnect on its own
+#[derive(Clone)]
+struct ConnectionContext {
+    auth_client: Arc<RwLock<AuthClient>>,
+    selector: Arc<RegionSelector>,
+    handler: Arc<dyn MessageHandler>,
+    registry: Arc<SubscriptionRegistry>,
+    is_connected: Arc<RwLock<bool>>,
+    /// Bumped by every connect and manual close; tasks of older connections stand down
+    generation: Arc<AtomicU64>,
+    is_token_price: bool,
+    refresh_tokens: bool,
+    reconnect: ReconnectConfig,
+    heartbeat: HeartbeatConfig,
+    health: Arc<HealthMonitor>,
+    decode_metrics: Arc<DecodeMetrics>,
+    dispatch: DispatchConfig,
+    dispatch_metrics: Arc<DispatchMetrics>,
+    /// Recorder and the connection id written with each frame
+    recorder: Option<(Arc<SessionRecorder>, String)>,
+    transport: TransportKind,
+}
+
+impl ConnectionContext {
+    /// Opens a socket and starts its writer, read and token refresh tasks
+    /// 
+    /// # Returns
+    /// 
+    /// Result<String, WebSocketError> - The connected URL
+    async fn open(&self) -> Result<String, WebSocketError> {
+        let auth_tokens = self.auth_client.write().await.ensure_valid_authentication().await
+            .map_err(WebSocketError::AuthError)?;
+        
+        let cookie = format!("auth-access-token={}; auth-refresh-token={}", 
+            auth_tokens.access_token, auth_tokens.refresh_token);
+        
+        // Try hosts fastest first, failing over to the next on error
+        let hosts = if self.is_token_price {
+            vec!["socket8.axiom.trade".to_string()]
+        } else {
+            self.selector.candidates().await
+        };
+        
+        let mut connected = None;
+        let mut last_error = None;
+        for host in hosts {
+            let request = handshake_request(&host, Some(&cookie))?;
+            match self.transport.connect(request).await {
+                Ok(halves) => {
+                    connected = Some((host, halves));
+                    break;
+                }
+                Err(e) => {
+                    self.selector.report_failure(&host, e.to_string());
+                    self.handler.on_error(format!("Connecting to {} failed: {}", host, e)).await;
+                    last_error = Some(e);
+                }
+            }
+        }
+        let Some((host, (mut read, mut write))) = connected else {
+            return Err(last_error.map_or_else(
+                || WebSocketError::ConnectionError("No hosts to connect to".to_string()),
+                WebSocketError::TransportError,
+            ));
+        };
+        let url = format!("wss://{}/", host);
+        
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        *self.is_connected.write().await = true;
+        self.health.reset(Instant::now());
+        
+        // Writes go through a channel so stream subscriptions can leave rooms on drop
+        let (outbound_tx, mut outbound_rx) = mpsc::unbounded_channel::<Message>();
+        self.registry.set_outbound(Some(outbound_tx));
+        tokio::spawn(async move {
+            while let Some(message) = outbound_rx.recv().await {
+                if write.send(message).await.is_err() {
+                    break;
+                }
+            }
+            write.close().await;
+        });
+        
+        // The handler runs on its own task so a slow one cannot stall socket reads
+        let queue = Arc::new(DispatchQueue::new(self.dispatch.clone(), Arc::clone(&self.dispatch_metrics)));
+        tokio::spawn({
+            let queue = Arc::clone(&queue);
+            let handler = Arc::clone(&self.handler);
+            async move { queue.run(handler).await }
+        });
+        
+        let context = self.clone();
+        let stale = Arc::new(Notify::new());
+        let stale_signal = Arc::clone(&stale);
+        
+        // Spawn read task
+        tokio::spawn(async move {
+            let registry = Arc::clone(&context.registry);
+            let is_connected = Arc::clone(&context.is_connected);
+            let dispatcher = FrameDispatcher {
+                handler: Arc::clone(&context.handler),
+                registry: Arc::clone(&registry),
+                metrics: Arc::clone(&context.decode_metrics),
+                queue: Some(Arc::clone(&queue)),
+            };
+            
+            loop {
+                let msg = tokio::select! {
+                    msg = read.read_frame() => msg,
+                    _ = stale_signal.notified() => {
+                        queue.push_control(Delivery::Disconnected("Heartbeat timeout".to_string()));
+                        break;
+                    }
+                };
+                context.health.record_inbound(Instant::now());
+                
+                match msg {
+                    Ok(InboundFrame::Text(text)) => {
+                        if let Some((recorder, connection_id)) = &context.recorder
+                            && let Err(e) = recorder.record(connection_id, text)
+                        {
+                            queue.push_control(Delivery::Error(format!("Recording frame failed: {}", e)));
+                        }
+                        if dispatcher.dispatch_text(text).await {
+                            *is_connected.write().await = false;
+                        }
+                    }
+                    Ok(InboundFrame::Pong(payload)) => {
+                        context.health.pong_received(payload, Instant::now());
+                    }
+                    Ok(InboundFrame::Close) => {
+                        queue.push_control(Delivery::Disconnected("Connection closed".to_string()));
+                        break;
+                    }
+                    Ok(InboundFrame::Other) => {}
+                    Err(TransportError::Closed) => break,
+                    Err(e) => {
+                        queue.push_control(Delivery::Error(format!("WebSocket error: {}", e)));
+                        break;
+                    }
+                }
+            }
+            queue.close();
+            
+            // A manual close or newer connection owns the shared state by now
+            if context.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            registry.set_outbound(None);
+            *is_connected.write().await = false;
+            if context.reconnect.enabled {
+                context.reconnect_loop(generation).await;
+            } else {
+                registry.close_all();
+            }
+        });
+        
+        if self.refresh_tokens {
+            self.spawn_token_refresh_task(generation);
+        }
+        
+        if self.heartbeat.enabled {
+            self.spawn_heartbeat_task(generation, stale);
+        }
+        
+        if !self.is_token_price {
+            self.spawn_reevaluation_task(generation);
+        }
+        
+        Ok(url)
+    }
+    
+    /// Reconnects with backoff until a connection opens, attempts run out or a manual
+    /// connect/disconnect takes over. Boxed because it is spawned from `open`.
+    fn reconnect_loop(self, generation: u64) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            let mut attempts = 0;
+            while self.reconnect.allows(attempts) {
+                tokio::time::sleep(self.reconnect.delay(attempts)).await;
+                attempts += 1;
+                
+                if self.generation.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                
+                match self.open().await {
+                    Ok(url) => {
+                        self.handler.on_connected(url).await;
+                        self.handler.on_reconnected(attempts).await;
+                        self.resubscribe().await;
+                        return;
+                    }
+                    Err(e) => {
+                        self.handler.on_error(format!("Reconnect attempt {} failed: {}", attempts, e)).await;
+                    }
+                }
+            }
+            if self.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            // Nothing will feed the streams again
+            self.registry.close_all();
+            self.handler.on_error(format!("Giving up reconnecting after {} attempts", attempts)).await;
+        })
+    }
+    
+    /// Rejoins every tracked room, reporting rooms that fail
+    async fn resubscribe(&self) {
+        for (room, error) in self.registry.rejoin_all() {
+            self.handler.on_resubscribe_failed(room, error.to_string()).await;
+        }
+    }
+    
+    /// Spawn a task that pings the server and signals the read task once the connection goes
+    /// quiet for longer than the heartbeat timeout
+    fn spawn_heartbeat_task(&self, generation: u64, stale: Arc<Notify>) {
+        let registry = Arc::clone(&self.registry);
+        let health = Arc::clone(&self.health);
+        let current = Arc::clone(&self.generation);
+        let config = self.heartbeat.clone();
+        
+        tokio::spawn(async move {
+            let mut ping_interval = interval(config.interval);
+            ping_interval.tick().await;
+            
+            loop {
+                ping_interval.tick().await;
+                
+                if current.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                
+                let now = Instant::now();
+                if health.is_stale(now, config.timeout) {
+                    health.record_stale();
+                    stale.notify_one();
+                    break;
+                }
+                
+                if registry.send(Message::Ping(health.ping_sent(now))).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    
+    /// Spawn a task that re-probes hosts so the next connect or reconnect uses a fresh ranking
+    fn spawn_reevaluation_task(&self, generation: u64) {
+        let selector = Arc::clone(&self.selector);
+        let current = Arc::clone(&self.generation);
+        
+        tokio::spawn(async move {
+            let mut probe_interval = interval(selector.config().reevaluate_every);
+            probe_interval.tick().await;
+            
+            loop {
+                probe_interval.tick().await;
+                
+                if current.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                
+                selector.probe().await;
+            }
+        });
     }
     
     /// Spawn a task to periodically refresh tokens
-    fn spawn_token_refresh_task(&self) {
+    fn spawn_token_refresh_task(&self, generation: u64) {
         let auth_client = Arc::clone(&self.auth_client);
         let is_connected = Arc::clone(&self.is_connected);
         let handler = Arc::clone(&self.handler);
+        let current = Arc::clone(&self.generation);
         
         tokio::spawn(async move {
             let mut refresh_interval = interval(Duration::from_secs(600));
@@ -427,7 +912,7 @@ impl WebSocketClient {
             loop {
                 refresh_interval.tick().await;
                 
-                if !*is_connected.read().await {
+                if !*is_connected.read().await || current.load(Ordering::SeqCst) != generation {
                     break;
                 }
                 
@@ -445,40 +930,4 @@ impl WebSocketClient {
             }
         });
     }
-    
-    /// Reconnect with fresh tokens
-    /// 
-    /// # Returns
-    /// 
-    /// Result<(), WebSocketError> - Ok if reconnected successfully
-    pub async fn reconnect(&mut self) -> Result<(), WebSocketError> {
-        self.disconnect().await;
-        
-        tokio::time::sleep(Duration::from_secs(1)).await;
-        
-        self.auth_client.write().await.ensure_valid_authentication().await
-            .map_err(WebSocketError::AuthError)?;
-        
-        if self.is_token_price {
-            self.connect_token_price().await?
-        } else {
-            self.connect().await?
-        }
-        
-        let subs = self.subscriptions.read().await.clone();
-        for sub in subs {
-            match sub {
-                SubscriptionType::MarketData => {
-                    self.subscribe_new_tokens().await?;
-                }
-                SubscriptionType::PriceAlerts => {
-                }
-                SubscriptionType::Portfolio => {
-                }
-                _ => {}
-            }
-        }
-        
-        Ok(())
-    }
 }
\ No newline at end of file
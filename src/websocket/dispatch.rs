@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::websocket::backpressure::{Delivery, DispatchQueue};
+use crate::websocket::handler::MessageHandler;
+use crate::websocket::messages::WebSocketMessage;
+use crate::websocket::rooms::{decode_room_message, DecodeMetrics};
+use crate::websocket::stream::SubscriptionRegistry;
+
+/// Decodes inbound text frames and delivers them to stream subscriptions and the handler.
+///
+/// Shared by the live read loop and session replay so both take exactly the same path.
+pub(crate) struct FrameDispatcher {
+    pub handler: Arc<dyn MessageHandler>,
+    pub registry: Arc<SubscriptionRegistry>,
+    pub metrics: Arc<DecodeMetrics>,
+    /// Hands handler calls to a separate task; without one the handler is awaited inline
+    pub queue: Option<Arc<DispatchQueue>>,
+}
+
+impl FrameDispatcher {
+    /// Handles one text frame
+    ///
+    /// # Returns
+    ///
+    /// bool - True if the server announced a disconnect
+    pub async fn dispatch_text(&self, text: &str) -> bool {
+        let Ok(data) = serde_json::from_str::<serde_json::Value>(text) else {
+            self.metrics.record_unrecognized();
+            return false;
+        };
+
+        // Room messages are decoded by room name; only the rest are control messages
+        let room = data.get("room").and_then(|r| r.as_str());
+        if let (Some(room), Some(content)) = (room, data.get("content")) {
+            self.registry.dispatch(room, content);
+
+            let decoded = decode_room_message(room, content);
+            self.metrics.record(&decoded);
+            match decoded {
+                Ok(ws_msg) => {
+                    // Only the latest price or stats of a room is worth delivering late
+                    let key = matches!(
+                        ws_msg,
+                        WebSocketMessage::TokenPrice(_) | WebSocketMessage::SolPrice(_) | WebSocketMessage::PairStats(_)
+                    )
+                    .then_some(room);
+                    self.deliver(Delivery::Message(ws_msg), key).await;
+                }
+                Err(e) => self.deliver_control(Delivery::Error(e.to_string())).await,
+            }
+            return false;
+        }
+
+        let Ok(ws_msg) = serde_json::from_value::<WebSocketMessage>(data) else {
+            self.metrics.record_unrecognized();
+            return false;
+        };
+        let mut disconnected = false;
+        match &ws_msg {
+            WebSocketMessage::Connected { session_id } => {
+                self.deliver_control(Delivery::Connected(session_id.clone())).await;
+            }
+            WebSocketMessage::Disconnected { reason } => {
+                disconnected = true;
+                self.deliver_control(Delivery::Disconnected(reason.clone())).await;
+            }
+            _ => {}
+        }
+        self.deliver(Delivery::Message(ws_msg), None).await;
+        disconnected
+    }
+
+    async fn deliver(&self, delivery: Delivery, key: Option<&str>) {
+        match &self.queue {
+            Some(queue) => queue.push(delivery, key).await,
+            None => delivery.deliver(self.handler.as_ref()).await,
+        }
+    }
+
+    /// Delivers a connection event or error, which the queue never drops
+    pub async fn deliver_control(&self, delivery: Delivery) {
+        match &self.queue {
+            Some(queue) => queue.push_control(delivery),
+            None => delivery.deliver(self.handler.as_ref()).await,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::websocket::messages::{
-    WebSocketMessage, MarketUpdate, OrderUpdate, TradeUpdate, BalanceUpdate,
+    WebSocketMessage, MarketUpdate, OrderUpdate, TradeUpdate, BalanceUpdate, NewPairEvent,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -34,6 +34,25 @@ pub trait MessageHandler: Send + Sync {
     /// 
     /// * `error` - String - The error message
     async fn on_error(&self, error: String);
+    
+    /// Called after the client reconnected on its own and rejoined its rooms
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attempts` - u32 - Connection attempts it took, starting at 1
+    async fn on_reconnected(&self, attempts: u32) {
+        let _ = attempts;
+    }
+    
+    /// Called when a room could not be rejoined after reconnecting
+    /// 
+    /// # Arguments
+    /// 
+    /// * `room` - String - The room that was not rejoined
+    /// * `error` - String - The failure reason
+    async fn on_resubscribe_failed(&self, room: String, error: String) {
+        let _ = (room, error);
+    }
 }
 
 pub struct DefaultMessageHandler {
@@ -41,6 +60,7 @@ pub struct DefaultMessageHandler {
     order_updates: Arc<RwLock<Vec<OrderUpdate>>>,
     trade_updates: Arc<RwLock<Vec<TradeUpdate>>>,
     balance_updates: Arc<RwLock<Vec<BalanceUpdate>>>,
+    new_pairs: Arc<RwLock<Vec<NewPairEvent>>>,
 }
 
 impl DefaultMessageHandler {
@@ -55,6 +75,7 @@ impl DefaultMessageHandler {
             order_updates: Arc::new(RwLock::new(Vec::new())),
             trade_updates: Arc::new(RwLock::new(Vec::new())),
             balance_updates: Arc::new(RwLock::new(Vec::new())),
+            new_pairs: Arc::new(RwLock::new(Vec::new())),
         }
     }
     
@@ -94,12 +115,22 @@ impl DefaultMessageHandler {
         self.balance_updates.read().await.clone()
     }
     
+    /// Gets stored new pair events
+    /// 
+    /// # Returns
+    /// 
+    /// Vec<NewPairEvent> - List of new pairs
+    pub async fn get_new_pairs(&self) -> Vec<NewPairEvent> {
+        self.new_pairs.read().await.clone()
+    }
+    
     /// Clears all stored updates
     pub async fn clear_all(&self) {
         self.market_updates.write().await.clear();
         self.order_updates.write().await.clear();
         self.trade_updates.write().await.clear();
         self.balance_updates.write().await.clear();
+        self.new_pairs.write().await.clear();
     }
 }
 
@@ -135,6 +166,13 @@ impl MessageHandler for DefaultMessageHandler {
                     updates.drain(0..25);
                 }
             }
+            WebSocketMessage::NewPair(pair) => {
+                let mut pairs = self.new_pairs.write().await;
+                pairs.push(*pair);
+                if pairs.len() > 500 {
+                    pairs.drain(0..250);
+                }
+            }
             WebSocketMessage::Error { code, message } => {
                 println!("WebSocket error {}: {}", code, message);
             }
@@ -153,4 +191,12 @@ impl MessageHandler for DefaultMessageHandler {
     async fn on_error(&self, error: String) {
         println!("WebSocket error: {}", error);
     }
+    
+    async fn on_reconnected(&self, attempts: u32) {
+        println!("WebSocket reconnected after {} attempt(s)", attempts);
+    }
+    
+    async fn on_resubscribe_failed(&self, room: String, error: String) {
+        println!("WebSocket failed to rejoin {}: {}", room, error);
+    }
 }
\ No newline at end of file
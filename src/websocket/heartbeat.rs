@@ -0,0 +1,210 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Keepalive settings for a WebSocket connection
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    /// Send pings and watch for stale connections
+    pub enabled: bool,
+    /// Time between ping frames
+    pub interval: Duration,
+    /// Silence after which the connection is considered stale and dropped
+    pub timeout: Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(15),
+            timeout: Duration::from_secs(45),
+        }
+    }
+}
+
+/// Point-in-time view of a connection's health
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionHealth {
+    /// Time since the current connection opened; None while never connected
+    pub connected_for: Option<Duration>,
+    /// Time since any frame was received
+    pub since_last_inbound: Option<Duration>,
+    /// Time since the last pong was received
+    pub since_last_pong: Option<Duration>,
+    /// Round trip of the most recent ping
+    pub last_rtt: Option<Duration>,
+    pub min_rtt: Option<Duration>,
+    pub max_rtt: Option<Duration>,
+    /// Mean round trip over the current connection
+    pub avg_rtt: Option<Duration>,
+    pub pings_sent: u64,
+    pub pongs_received: u64,
+    /// Connections dropped for missing the heartbeat timeout, across reconnects
+    pub stale_disconnects: u64,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    connected_at: Option<Instant>,
+    last_inbound: Option<Instant>,
+    last_pong: Option<Instant>,
+    /// Payload and send time of the ping awaiting its pong
+    pending_ping: Option<(u64, Instant)>,
+    next_nonce: u64,
+    last_rtt: Option<Duration>,
+    min_rtt: Option<Duration>,
+    max_rtt: Option<Duration>,
+    rtt_total: Duration,
+    rtt_samples: u32,
+    pings_sent: u64,
+    pongs_received: u64,
+    stale_disconnects: u64,
+}
+
+/// Tracks inbound traffic and ping round trips for the current connection
+#[derive(Debug, Default)]
+pub(crate) struct HealthMonitor {
+    state: Mutex<HealthState>,
+}
+
+impl HealthMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts measuring a new connection; the stale counter carries over
+    pub fn reset(&self, now: Instant) {
+        let mut state = self.state.lock().expect("health lock poisoned");
+        *state = HealthState {
+            connected_at: Some(now),
+            last_inbound: Some(now),
+            next_nonce: state.next_nonce,
+            stale_disconnects: state.stale_disconnects,
+            ..Default::default()
+        };
+    }
+
+    pub fn record_inbound(&self, now: Instant) {
+        self.state.lock().expect("health lock poisoned").last_inbound = Some(now);
+    }
+
+    /// Registers an outgoing ping
+    ///
+    /// # Returns
+    ///
+    /// Vec<u8> - Payload to send with the ping frame
+    pub fn ping_sent(&self, now: Instant) -> Vec<u8> {
+        let mut state = self.state.lock().expect("health lock poisoned");
+        state.next_nonce = state.next_nonce.wrapping_add(1);
+        let nonce = state.next_nonce;
+        // An unanswered ping is superseded; its pong will no longer match
+        state.pending_ping = Some((nonce, now));
+        state.pings_sent += 1;
+        nonce.to_be_bytes().to_vec()
+    }
+
+    /// Matches a pong to the pending ping
+    ///
+    /// # Returns
+    ///
+    /// Option<Duration> - The round trip, if the payload answers the pending ping
+    pub fn pong_received(&self, payload: &[u8], now: Instant) -> Option<Duration> {
+        let mut state = self.state.lock().expect("health lock poisoned");
+        state.last_inbound = Some(now);
+        state.last_pong = Some(now);
+        state.pongs_received += 1;
+
+        let nonce = u64::from_be_bytes(payload.try_into().ok()?);
+        let (pending, sent_at) = state.pending_ping?;
+        if pending != nonce {
+            return None;
+        }
+        state.pending_ping = None;
+
+        let rtt = now.saturating_duration_since(sent_at);
+        state.last_rtt = Some(rtt);
+        state.min_rtt = Some(state.min_rtt.map_or(rtt, |min| min.min(rtt)));
+        state.max_rtt = Some(state.max_rtt.map_or(rtt, |max| max.max(rtt)));
+        state.rtt_total += rtt;
+        state.rtt_samples += 1;
+        Some(rtt)
+    }
+
+    /// Returns true if nothing was received within `timeout`
+    pub fn is_stale(&self, now: Instant, timeout: Duration) -> bool {
+        let state = self.state.lock().expect("health lock poisoned");
+        state
+            .last_inbound
+            .is_some_and(|last| now.saturating_duration_since(last) > timeout)
+    }
+
+    pub fn record_stale(&self) {
+        self.state.lock().expect("health lock poisoned").stale_disconnects += 1;
+    }
+
+    pub fn snapshot(&self, now: Instant) -> ConnectionHealth {
+        let state = self.state.lock().expect("health lock poisoned");
+        let since = |at: Option<Instant>| at.map(|at| now.saturating_duration_since(at));
+
+        ConnectionHealth {
+            connected_for: since(state.connected_at),
+            since_last_inbound: since(state.last_inbound),
+            since_last_pong: since(state.last_pong),
+            last_rtt: state.last_rtt,
+            min_rtt: state.min_rtt,
+            max_rtt: state.max_rtt,
+            avg_rtt: (state.rtt_samples > 0).then(|| state.rtt_total / state.rtt_samples),
+            pings_sent: state.pings_sent,
+            pongs_received: state.pongs_received,
+            stale_disconnects: state.stale_disconnects,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtt_from_matching_pongs() {
+        let monitor = HealthMonitor::new();
+        let start = Instant::now();
+        monitor.reset(start);
+
+        let first = monitor.ping_sent(start);
+        assert_eq!(monitor.pong_received(&first, start + Duration::from_millis(40)), Some(Duration::from_millis(40)));
+        let second = monitor.ping_sent(start + Duration::from_secs(1));
+        assert_eq!(monitor.pong_received(b"unsolicited", start + Duration::from_secs(1)), None);
+        assert_eq!(
+            monitor.pong_received(&second, start + Duration::from_millis(1_080)),
+            Some(Duration::from_millis(80))
+        );
+
+        let health = monitor.snapshot(start + Duration::from_secs(2));
+        assert_eq!(health.last_rtt, Some(Duration::from_millis(80)));
+        assert_eq!(health.min_rtt, Some(Duration::from_millis(40)));
+        assert_eq!(health.max_rtt, Some(Duration::from_millis(80)));
+        assert_eq!(health.avg_rtt, Some(Duration::from_millis(60)));
+        assert_eq!((health.pings_sent, health.pongs_received), (2, 3));
+        assert_eq!(health.since_last_pong, Some(Duration::from_millis(920)));
+    }
+
+    #[test]
+    fn test_stale_after_silence() {
+        let monitor = HealthMonitor::new();
+        let start = Instant::now();
+        let timeout = Duration::from_secs(45);
+        assert!(!monitor.is_stale(start + timeout * 2, timeout));
+
+        monitor.reset(start);
+        monitor.record_inbound(start + Duration::from_secs(30));
+        assert!(!monitor.is_stale(start + Duration::from_secs(70), timeout));
+        assert!(monitor.is_stale(start + Duration::from_secs(76), timeout));
+
+        monitor.record_stale();
+        monitor.reset(start + Duration::from_secs(80));
+        let health = monitor.snapshot(start + Duration::from_secs(80));
+        assert_eq!(health.stale_disconnects, 1);
+        assert_eq!(health.pings_sent, 0);
+    }
+}
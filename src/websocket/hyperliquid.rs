@@ -0,0 +1,1091 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use crate::models::decimal::Decimal;
+use crate::models::hyperliquid::{AllMids, HyperliquidMessage, HyperliquidSubscription, Orderbook, OrderbookLevel};
+use crate::websocket::client::WebSocketError;
+use crate::websocket::reconnect::ReconnectConfig;
+use crate::websocket::stream::DEFAULT_STREAM_BUFFER;
+use crate::websocket::transport::{InboundFrame, TransportHalves, TransportKind};
+
+pub const HYPERLIQUID_WS_URL: &str = "wss://api.hyperliquid.xyz/ws";
+
+/// Settings for `HyperliquidWsClient`
+#[derive(Debug, Clone)]
+pub struct HyperliquidWsConfig {
+    pub url: String,
+    pub reconnect: ReconnectConfig,
+    /// Hyperliquid closes connections that send nothing for a minute
+    pub ping_interval: Duration,
+    /// A connection with no inbound message for this long is dropped and reconnected
+    pub stale_after: Duration,
+    /// Events buffered per stream before new ones are dropped
+    pub stream_buffer: usize,
+    pub transport: TransportKind,
+}
+
+impl Default for HyperliquidWsConfig {
+    fn default() -> Self {
+        Self {
+            url: HYPERLIQUID_WS_URL.to_string(),
+            reconnect: ReconnectConfig::default(),
+            ping_interval: Duration::from_secs(30),
+            stale_after: Duration::from_secs(90),
+            stream_buffer: DEFAULT_STREAM_BUFFER,
+            transport: TransportKind::default(),
+        }
+    }
+}
+
+/// A trade from the `trades` channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WsTrade {
+    pub coin: String,
+    /// "B" for a buy, "A" for a sell
+    pub side: String,
+    pub px: Decimal,
+    pub sz: Decimal,
+    pub hash: String,
+    pub time: u64,
+    #[serde(default)]
+    pub tid: u64,
+}
+
+/// A candle from the `candle` channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WsCandle {
+    #[serde(rename = "t")]
+    pub open_time: u64,
+    #[serde(rename = "T")]
+    pub close_time: u64,
+    #[serde(rename = "s")]
+    pub coin: String,
+    #[serde(rename = "i")]
+    pub interval: String,
+    #[serde(rename = "o")]
+    pub open: Decimal,
+    #[serde(rename = "h")]
+    pub high: Decimal,
+    #[serde(rename = "l")]
+    pub low: Decimal,
+    #[serde(rename = "c")]
+    pub close: Decimal,
+    #[serde(rename = "v")]
+    pub volume: Decimal,
+    #[serde(rename = "n")]
+    pub trades: u64,
+}
+
+/// One of the user's fills
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsFill {
+    pub coin: String,
+    pub px: Decimal,
+    pub sz: Decimal,
+    pub side: String,
+    pub time: u64,
+    pub start_position: Decimal,
+    pub dir: String,
+    pub closed_pnl: Decimal,
+    pub hash: String,
+    pub oid: u64,
+    pub crossed: bool,
+    pub fee: Decimal,
+    #[serde(default)]
+    pub tid: u64,
+    pub fee_token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsUserFills {
+    /// True for the history sent right after subscribing
+    #[serde(default)]
+    pub is_snapshot: bool,
+    pub user: String,
+    pub fills: Vec<WsFill>,
+}
+
+/// A funding payment on one of the user's positions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsFunding {
+    pub time: u64,
+    pub coin: String,
+    pub usdc: Decimal,
+    pub szi: Decimal,
+    pub funding_rate: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsUserFundings {
+    #[serde(default)]
+    pub is_snapshot: bool,
+    pub user: String,
+    pub fundings: Vec<WsFunding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WsNonUserCancel {
+    pub coin: String,
+    pub oid: u64,
+}
+
+/// An event from the `userEvents` subscription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WsUserEvent {
+    Fills(Vec<WsFill>),
+    Funding(WsFunding),
+    Liquidation(Value),
+    NonUserCancel(Vec<WsNonUserCancel>),
+}
+
+/// An order book built from `l2Book` snapshots; bids best first, asks best first
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2Book {
+    pub coin: String,
+    pub time: u64,
+    pub bids: Vec<OrderbookLevel>,
+    pub asks: Vec<OrderbookLevel>,
+}
+
+impl L2Book {
+    /// Builds a book from a snapshot, dropping empty levels and sorting each side best first
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - Orderbook - `levels` holds bids then asks
+    ///
+    /// # Returns
+    ///
+    /// Option<L2Book> - None if the snapshot does not have two sides
+    pub fn from_snapshot(snapshot: Orderbook) -> Option<Self> {
+        let mut sides = snapshot.levels.into_iter();
+        let (Some(mut bids), Some(mut asks), None) = (sides.next(), sides.next(), sides.next()) else {
+            return None;
+        };
+        bids.retain(|level| !level.sz.is_zero());
+        asks.retain(|level| !level.sz.is_zero());
+        bids.sort_by_key(|level| std::cmp::Reverse(level.px));
+        asks.sort_by_key(|level| level.px);
+        Some(Self {
+            coin: snapshot.coin,
+            time: snapshot.time,
+            bids,
+            asks,
+        })
+    }
+
+    pub fn best_bid(&self) -> Option<&OrderbookLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&OrderbookLevel> {
+        self.asks.first()
+    }
+
+    pub fn mid(&self) -> Option<Decimal> {
+        let sum = self.best_bid()?.px.checked_add(self.best_ask()?.px)?;
+        sum.checked_div(Decimal::from(2u32))
+    }
+
+    pub fn spread(&self) -> Option<Decimal> {
+        self.best_ask()?.px.checked_sub(self.best_bid()?.px)
+    }
+
+    /// Total size on each side within the best `levels` levels
+    ///
+    /// # Returns
+    ///
+    /// Option<(Decimal, Decimal)> - Bid size, ask size; None if a total overflows
+    pub fn depth(&self, levels: usize) -> Option<(Decimal, Decimal)> {
+        let total = |side: &[OrderbookLevel]| {
+            side.iter()
+                .take(levels)
+                .try_fold(Decimal::ZERO, |sum, level| sum.checked_add(level.sz))
+        };
+        Some((total(&self.bids)?, total(&self.asks)?))
+    }
+}
+
+/// A decoded Hyperliquid channel message
+#[derive(Debug, Clone, PartialEq)]
+pub enum HyperliquidEvent {
+    AllMids(AllMids),
+    L2Book(L2Book),
+    Trades(Vec<WsTrade>),
+    Candle(WsCandle),
+    UserFills(WsUserFills),
+    UserFundings(WsUserFundings),
+    UserEvent(WsUserEvent),
+    Notification(String),
+    /// The `webData2` account overview, left undecoded
+    WebData(Value),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Invalid {channel} message: {message}")]
+pub struct HyperliquidDecodeError {
+    pub channel: String,
+    pub message: String,
+}
+
+/// Counters for a `HyperliquidWsClient`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HyperliquidWsStats {
+    pub reconnects: u64,
+    /// Events not delivered because a stream's buffer was full
+    pub dropped_events: u64,
+    pub decode_errors: u64,
+    /// Errors the server sent on the `error` channel
+    pub server_errors: u64,
+    pub last_error: Option<String>,
+}
+
+/// What an inbound frame turned out to be
+#[derive(Debug, PartialEq)]
+pub(crate) enum Inbound {
+    /// An event for the subscription with this key
+    Event(String, Box<HyperliquidEvent>),
+    /// Pongs, subscription acknowledgements and empty updates
+    Control,
+    ServerError(String),
+}
+
+impl Inbound {
+    fn event(key: String, event: HyperliquidEvent) -> Self {
+        Inbound::Event(key, Box::new(event))
+    }
+}
+
+/// Routing key shared by a subscription and the messages it receives
+fn subscription_key(subscription: &HyperliquidSubscription) -> String {
+    match subscription {
+        HyperliquidSubscription::AllMids => "allMids".to_string(),
+        HyperliquidSubscription::L2Book { coin } => format!("l2Book:{}", coin),
+        HyperliquidSubscription::Trades { coin } => format!("trades:{}", coin),
+        HyperliquidSubscription::Candle { coin, interval } => format!("candle:{}:{}", coin, interval),
+        HyperliquidSubscription::UserFills { user } => format!("userFills:{}", user.to_lowercase()),
+        HyperliquidSubscription::UserFundings { user } => format!("userFundings:{}", user.to_lowercase()),
+        // These channels do not say which user they are for; the server allows one per connection
+        HyperliquidSubscription::UserEvents { .. } => "user".to_string(),
+        HyperliquidSubscription::Notification { .. } => "notification".to_string(),
+        HyperliquidSubscription::WebData { .. } => "webData2".to_string(),
+    }
+}
+
+/// User of a channel that carries no user in its messages, and so is keyed without one
+fn unrouted_user(subscription: &HyperliquidSubscription) -> Option<&str> {
+    match subscription {
+        HyperliquidSubscription::UserEvents { user }
+        | HyperliquidSubscription::Notification { user }
+        | HyperliquidSubscription::WebData { user } => Some(user),
+        _ => None,
+    }
+}
+
+fn request(method: &str, subscription: &HyperliquidSubscription) -> Message {
+    Message::Text(json!({ "method": method, "subscription": subscription }).to_string())
+}
+
+/// Decodes one text frame
+///
+/// # Arguments
+///
+/// * `text` - &str - The frame, `{"channel": ..., "data": ...}`
+///
+/// # Returns
+///
+/// Result<Inbound, HyperliquidDecodeError> - The routed event, a control message or a server error
+pub(crate) fn decode_frame(text: &str) -> Result<Inbound, HyperliquidDecodeError> {
+    let message: HyperliquidMessage = serde_json::from_str(text).map_err(|e| HyperliquidDecodeError {
+        channel: "unknown".to_string(),
+        message: e.to_string(),
+    })?;
+    let channel = message.channel.as_str();
+    let error = |message: String| HyperliquidDecodeError {
+        channel: channel.to_string(),
+        message,
+    };
+    fn parse<T: DeserializeOwned>(data: Value) -> Result<T, String> {
+        serde_json::from_value(data).map_err(|e| e.to_string())
+    }
+
+    #[derive(Deserialize)]
+    struct Mids {
+        mids: AllMids,
+    }
+    #[derive(Deserialize)]
+    struct Notification {
+        notification: String,
+    }
+
+    let inbound = match channel {
+        "pong" | "subscriptionResponse" => Inbound::Control,
+        "error" => Inbound::ServerError(match message.data {
+            Value::String(text) => text,
+            other => other.to_string(),
+        }),
+        "allMids" => {
+            let mids: Mids = parse(message.data).map_err(error)?;
+            Inbound::event("allMids".to_string(), HyperliquidEvent::AllMids(mids.mids))
+        }
+        "l2Book" => {
+            let snapshot: Orderbook = parse(message.data).map_err(error)?;
+            let book = L2Book::from_snapshot(snapshot).ok_or_else(|| error("expected bid and ask levels".to_string()))?;
+            Inbound::event(format!("l2Book:{}", book.coin), HyperliquidEvent::L2Book(book))
+        }
+        "trades" => {
+            let trades: Vec<WsTrade> = parse(message.data).map_err(error)?;
+            match trades.first() {
+                Some(first) => Inbound::event(format!("trades:{}", first.coin), HyperliquidEvent::Trades(trades)),
+                None => Inbound::Control,
+            }
+        }
+        "candle" => {
+            let candle: WsCandle = parse(message.data).map_err(error)?;
+            Inbound::event(format!("candle:{}:{}", candle.coin, candle.interval), HyperliquidEvent::Candle(candle))
+        }
+        "userFills" => {
+            let fills: WsUserFills = parse(message.data).map_err(error)?;
+            Inbound::event(format!("userFills:{}", fills.user.to_lowercase()), HyperliquidEvent::UserFills(fills))
+        }
+        "userFundings" => {
+            let fundings: WsUserFundings = parse(message.data).map_err(error)?;
+            Inbound::event(
+                format!("userFundings:{}", fundings.user.to_lowercase()),
+                HyperliquidEvent::UserFundings(fundings),
+            )
+        }
+        "user" => Inbound::event("user".to_string(), HyperliquidEvent::UserEvent(parse(message.data).map_err(error)?)),
+        "notification" => {
+            let notification: Notification = parse(message.data).map_err(error)?;
+            Inbound::event("notification".to_string(), HyperliquidEvent::Notification(notification.notification))
+        }
+        "webData2" => Inbound::event("webData2".to_string(), HyperliquidEvent::WebData(message.data)),
+        _ => return Err(error("unknown channel".to_string())),
+    };
+    Ok(inbound)
+}
+
+struct Channel {
+    subscription: HyperliquidSubscription,
+    senders: Vec<(u64, mpsc::Sender<HyperliquidEvent>)>,
+}
+
+#[derive(Default)]
+struct SharedState {
+    channels: HashMap<String, Channel>,
+    outbound: Option<mpsc::UnboundedSender<Message>>,
+    /// Generation of the session that owns `outbound`
+    session: u64,
+}
+
+/// State shared by the client, its streams and the connection task
+#[derive(Default)]
+struct Shared {
+    state: Mutex<SharedState>,
+    books: RwLock<HashMap<String, L2Book>>,
+    connected: AtomicBool,
+    next_id: AtomicU64,
+    reconnects: AtomicU64,
+    dropped: AtomicU64,
+    decode_errors: AtomicU64,
+    server_errors: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Shared {
+    fn record_error(&self, error: String) {
+        *self.last_error.lock().expect("hyperliquid error lock poisoned") = Some(error);
+    }
+
+    /// Decodes a frame, updates the local book and fans the event out to its streams
+    fn dispatch(&self, text: &str) {
+        let (key, event) = match decode_frame(text) {
+            Ok(Inbound::Event(key, event)) => (key, *event),
+            Ok(Inbound::Control) => return,
+            Ok(Inbound::ServerError(error)) => {
+                self.server_errors.fetch_add(1, Ordering::Relaxed);
+                self.record_error(error);
+                return;
+            }
+            Err(e) => {
+                self.decode_errors.fetch_add(1, Ordering::Relaxed);
+                self.record_error(e.to_string());
+                return;
+            }
+        };
+
+        // Held while the book is updated so a stream subscribing meanwhile gets the cached book
+        // and later snapshots in order, and a channel being removed takes its book with it
+        let state = self.state.lock().expect("hyperliquid state lock poisoned");
+        let Some(channel) = state.channels.get(&key) else {
+            return;
+        };
+
+        if let HyperliquidEvent::L2Book(book) = &event {
+            let mut books = self.books.write().expect("hyperliquid book lock poisoned");
+            // Snapshots can arrive out of order across a reconnect; keep the newest
+            if books.get(&book.coin).is_some_and(|current| current.time > book.time) {
+                return;
+            }
+            books.insert(book.coin.clone(), book.clone());
+        }
+
+        for (_, sender) in &channel.senders {
+            if let Err(mpsc::error::TrySendError::Full(_)) = sender.try_send(event.clone()) {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Ends every stream; subscriptions are forgotten
+    fn close_all(&self) {
+        let mut state = self.state.lock().expect("hyperliquid state lock poisoned");
+        state.channels.clear();
+        self.books.write().expect("hyperliquid book lock poisoned").clear();
+    }
+
+    fn remove(&self, key: &str, id: u64) {
+        let mut state = self.state.lock().expect("hyperliquid state lock poisoned");
+        let Some(channel) = state.channels.get_mut(key) else {
+            return;
+        };
+        channel.senders.retain(|(sender_id, _)| *sender_id != id);
+        if channel.senders.is_empty() {
+            let channel = state.channels.remove(key).expect("channel exists");
+            if let Some(outbound) = &state.outbound {
+                let _ = outbound.send(request("unsubscribe", &channel.subscription));
+            }
+            // Without the subscription the book would stop updating
+            if let HyperliquidSubscription::L2Book { coin } = &channel.subscription {
+                self.books.write().expect("hyperliquid book lock poisoned").remove(coin);
+            }
+        }
+    }
+}
+
+/// A typed stream of one Hyperliquid subscription.
+///
+/// Events are buffered up to `HyperliquidWsConfig::stream_buffer`; when the buffer is full new
+/// events are dropped and counted. Dropping the stream unsubscribes once no other stream uses the
+/// subscription. The stream ends when the client stops reconnecting.
+pub struct HyperliquidStream<T> {
+    key: String,
+    id: u64,
+    rx: mpsc::Receiver<HyperliquidEvent>,
+    extract: fn(HyperliquidEvent) -> Option<T>,
+    shared: Arc<Shared>,
+}
+
+impl<T> HyperliquidStream<T> {
+    /// Waits for the next event
+    ///
+    /// # Returns
+    ///
+    /// Option<T> - The next event, or None once the client is dropped or gave up reconnecting
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = (self.extract)(self.rx.recv().await?) {
+                return Some(item);
+            }
+        }
+    }
+}
+
+impl<T> Stream for HyperliquidStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            match this.rx.poll_recv(cx) {
+                Poll::Ready(Some(event)) => {
+                    if let Some(item) = (this.extract)(event) {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T> Drop for HyperliquidStream<T> {
+    fn drop(&mut self) {
+        self.shared.remove(&self.key, self.id);
+    }
+}
+
+/// WebSocket client for Hyperliquid's public and per-user channels.
+///
+/// Subscriptions are kept across reconnects and re-sent on every new connection. `l2Book`
+/// snapshots also update a local book per coin, readable with `book`.
+pub struct HyperliquidWsClient {
+    config: HyperliquidWsConfig,
+    shared: Arc<Shared>,
+    /// Bumped to stop the running connection task
+    stop: watch::Sender<u64>,
+}
+
+impl Default for HyperliquidWsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// WebSocketError carries tungstenite's error inline, as for the Axiom stream registry
+#[allow(clippy::result_large_err)]
+impl HyperliquidWsClient {
+    /// Creates a client for the public Hyperliquid endpoint
+    pub fn new() -> Self {
+        Self::with_config(HyperliquidWsConfig::default())
+    }
+
+    pub fn with_config(config: HyperliquidWsConfig) -> Self {
+        Self {
+            config,
+            shared: Arc::new(Shared::default()),
+            stop: watch::channel(0).0,
+        }
+    }
+
+    /// Connects and re-sends existing subscriptions; later drops reconnect automatically
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WebSocketError> - Ok once the first connection is open
+    pub async fn connect(&mut self) -> Result<(), WebSocketError> {
+        let halves = open(&self.config).await?;
+        self.stop.send_modify(|generation| *generation += 1);
+        tokio::spawn(supervise(
+            Arc::clone(&self.shared),
+            self.config.clone(),
+            halves,
+            self.stop.subscribe(),
+        ));
+        Ok(())
+    }
+
+    /// Closes the connection without reconnecting; subscriptions are kept for the next `connect`
+    pub fn disconnect(&mut self) {
+        self.stop.send_modify(|generation| *generation += 1);
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.shared.connected.load(Ordering::SeqCst)
+    }
+
+    /// Gets the latest local book for a coin subscribed with `subscribe_l2_book`
+    ///
+    /// # Arguments
+    ///
+    /// * `coin` - &str - Coin symbol, e.g. "BTC"
+    ///
+    /// # Returns
+    ///
+    /// Option<L2Book> - None until the first snapshot, and again after a disconnect or once the
+    /// last stream of the book is dropped
+    pub fn book(&self, coin: &str) -> Option<L2Book> {
+        self.shared.books.read().expect("hyperliquid book lock poisoned").get(coin).cloned()
+    }
+
+    /// Gets every subscription with at least one open stream
+    pub fn subscriptions(&self) -> Vec<HyperliquidSubscription> {
+        let state = self.shared.state.lock().expect("hyperliquid state lock poisoned");
+        state.channels.values().map(|channel| channel.subscription.clone()).collect()
+    }
+
+    pub fn stats(&self) -> HyperliquidWsStats {
+        HyperliquidWsStats {
+            reconnects: self.shared.reconnects.load(Ordering::Relaxed),
+            dropped_events: self.shared.dropped.load(Ordering::Relaxed),
+            decode_errors: self.shared.decode_errors.load(Ordering::Relaxed),
+            server_errors: self.shared.server_errors.load(Ordering::Relaxed),
+            last_error: self.shared.last_error.lock().expect("hyperliquid error lock poisoned").clone(),
+        }
+    }
+
+    /// Subscribes to any channel, receiving every decoded event
+    ///
+    /// # Arguments
+    ///
+    /// * `subscription` - HyperliquidSubscription - The channel; sent now if connected, otherwise on connect
+    ///
+    /// # Returns
+    ///
+    /// Result<HyperliquidStream<HyperliquidEvent>, WebSocketError> - Stream of the channel's
+    /// events, or a conflict if the channel is already subscribed for another user
+    pub fn subscribe(
+        &self,
+        subscription: HyperliquidSubscription,
+    ) -> Result<HyperliquidStream<HyperliquidEvent>, WebSocketError> {
+        self.try_subscribe_with(subscription, Some)
+    }
+
+    pub fn subscribe_all_mids(&self) -> HyperliquidStream<AllMids> {
+        self.subscribe_with(HyperliquidSubscription::AllMids, |event| match event {
+            HyperliquidEvent::AllMids(mids) => Some(mids),
+            _ => None,
+        })
+    }
+
+    /// Subscribes to a coin's order book; the current local book, if any, is delivered first
+    pub fn subscribe_l2_book(&self, coin: &str) -> HyperliquidStream<L2Book> {
+        let subscription = HyperliquidSubscription::L2Book { coin: coin.to_string() };
+        self.subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::L2Book(book) => Some(book),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_trades(&self, coin: &str) -> HyperliquidStream<Vec<WsTrade>> {
+        let subscription = HyperliquidSubscription::Trades { coin: coin.to_string() };
+        self.subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::Trades(trades) => Some(trades),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_candles(&self, coin: &str, interval: &str) -> HyperliquidStream<WsCandle> {
+        let subscription = HyperliquidSubscription::Candle {
+            coin: coin.to_string(),
+            interval: interval.to_string(),
+        };
+        self.subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::Candle(candle) => Some(candle),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_user_fills(&self, user: &str) -> HyperliquidStream<WsUserFills> {
+        let subscription = HyperliquidSubscription::UserFills { user: user.to_string() };
+        self.subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::UserFills(fills) => Some(fills),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_user_fundings(&self, user: &str) -> HyperliquidStream<WsUserFundings> {
+        let subscription = HyperliquidSubscription::UserFundings { user: user.to_string() };
+        self.subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::UserFundings(fundings) => Some(fundings),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_user_events(&self, user: &str) -> Result<HyperliquidStream<WsUserEvent>, WebSocketError> {
+        let subscription = HyperliquidSubscription::UserEvents { user: user.to_string() };
+        self.try_subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::UserEvent(event) => Some(event),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_notifications(&self, user: &str) -> Result<HyperliquidStream<String>, WebSocketError> {
+        let subscription = HyperliquidSubscription::Notification { user: user.to_string() };
+        self.try_subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::Notification(text) => Some(text),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_web_data(&self, user: &str) -> Result<HyperliquidStream<Value>, WebSocketError> {
+        let subscription = HyperliquidSubscription::WebData { user: user.to_string() };
+        self.try_subscribe_with(subscription, |event| match event {
+            HyperliquidEvent::WebData(data) => Some(data),
+            _ => None,
+        })
+    }
+
+    fn subscribe_with<T>(
+        &self,
+        subscription: HyperliquidSubscription,
+        extract: fn(HyperliquidEvent) -> Option<T>,
+    ) -> HyperliquidStream<T> {
+        let mut state = self.shared.state.lock().expect("hyperliquid state lock poisoned");
+        self.register(&mut state, subscription, extract)
+    }
+
+    /// Subscribes unless a channel keyed without its user is already open for another user,
+    /// whose events could not be told apart on one connection
+    fn try_subscribe_with<T>(
+        &self,
+        subscription: HyperliquidSubscription,
+        extract: fn(HyperliquidEvent) -> Option<T>,
+    ) -> Result<HyperliquidStream<T>, WebSocketError> {
+        let mut state = self.shared.state.lock().expect("hyperliquid state lock poisoned");
+        if let Some(user) = unrouted_user(&subscription)
+            && let Some(current) = state
+                .channels
+                .get(&subscription_key(&subscription))
+                .and_then(|channel| unrouted_user(&channel.subscription))
+            && !current.eq_ignore_ascii_case(user)
+        {
+            return Err(WebSocketError::SubscriptionConflict(format!(
+                "{} is already subscribed for {}",
+                subscription_key(&subscription),
+                current
+            )));
+        }
+        Ok(self.register(&mut state, subscription, extract))
+    }
+
+    fn register<T>(
+        &self,
+        state: &mut SharedState,
+        subscription: HyperliquidSubscription,
+        extract: fn(HyperliquidEvent) -> Option<T>,
+    ) -> HyperliquidStream<T> {
+        let key = subscription_key(&subscription);
+        let id = self.shared.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel(self.config.stream_buffer.max(1));
+
+        // Snapshots are dispatched under the same lock, so nothing newer can reach the stream first
+        if let HyperliquidSubscription::L2Book { coin } = &subscription
+            && let Some(book) = self.book(coin)
+        {
+            let _ = tx.try_send(HyperliquidEvent::L2Book(book));
+        }
+
+        let SharedState { channels, outbound, .. } = state;
+        let channel = channels.entry(key.clone()).or_insert_with(|| {
+            // First stream for this channel; later ones share the server subscription
+            if let Some(outbound) = outbound {
+                let _ = outbound.send(request("subscribe", &subscription));
+            }
+            Channel {
+                subscription,
+                senders: Vec::new(),
+            }
+        });
+        channel.senders.push((id, tx));
+
+        HyperliquidStream {
+            key,
+            id,
+            rx,
+            extract,
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+async fn open(config: &HyperliquidWsConfig) -> Result<TransportHalves, WebSocketError> {
+    let request = config.url.as_str().into_client_request()?;
+    Ok(config.transport.connect(request).await?)
+}
+
+/// Runs connections until the client stops it, reconnecting with backoff when one drops
+async fn supervise(
+    shared: Arc<Shared>,
+    config: HyperliquidWsConfig,
+    mut halves: TransportHalves,
+    mut stop: watch::Receiver<u64>,
+) {
+    let generation = *stop.borrow_and_update();
+    loop {
+        if run_session(&shared, &config, halves, generation, &mut stop).await {
+            return;
+        }
+
+        let mut failures = 0;
+        halves = loop {
+            if !config.reconnect.enabled || !config.reconnect.allows(failures) {
+                // Nothing will feed the streams again, unless a newer connect took over
+                if *stop.borrow() == generation {
+                    shared.close_all();
+                }
+                return;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(config.reconnect.delay(failures)) => {}
+                _ = stop.changed() => return,
+            }
+            match open(&config).await {
+                Ok(halves) => break halves,
+                Err(e) => {
+                    shared.record_error(format!("Reconnecting failed: {}", e));
+                    failures += 1;
+                }
+            }
+        };
+        shared.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Serves one connection
+///
+/// # Returns
+///
+/// bool - True if the client stopped it, false if the connection dropped
+async fn run_session(
+    shared: &Shared,
+    config: &HyperliquidWsConfig,
+    (mut reader, mut writer): TransportHalves,
+    generation: u64,
+    stop: &mut watch::Receiver<u64>,
+) -> bool {
+    let (outbound_tx, mut outbound_rx) = mpsc::unbounded_channel::<Message>();
+    tokio::spawn(async move {
+        while let Some(message) = outbound_rx.recv().await {
+            if writer.send(message).await.is_err() {
+                break;
+            }
+        }
+        writer.close().await;
+    });
+
+    {
+        let mut state = shared.state.lock().expect("hyperliquid state lock poisoned");
+        if *stop.borrow() != generation {
+            return true;
+        }
+        for channel in state.channels.values() {
+            let _ = outbound_tx.send(request("subscribe", &channel.subscription));
+        }
+        state.outbound = Some(outbound_tx.clone());
+        state.session = generation;
+        shared.connected.store(true, Ordering::SeqCst);
+    }
+
+    let ping = tokio::spawn({
+        let outbound_tx = outbound_tx.clone();
+        let period = config.ping_interval;
+        async move {
+            let mut ticks = tokio::time::interval_at(Instant::now() + period, period);
+            loop {
+                ticks.tick().await;
+                if outbound_tx.send(Message::Text(json!({ "method": "ping" }).to_string())).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let stale = tokio::time::sleep(config.stale_after);
+    tokio::pin!(stale);
+    let stopped = loop {
+        tokio::select! {
+            frame = reader.read_frame() => match frame {
+                Ok(InboundFrame::Text(text)) => shared.dispatch(text),
+                Ok(InboundFrame::Pong(_)) | Ok(InboundFrame::Other) => {}
+                Ok(InboundFrame::Close) => break false,
+                Err(e) => {
+                    shared.record_error(format!("Connection lost: {}", e));
+                    break false;
+                }
+            },
+            _ = &mut stale => {
+                shared.record_error(format!("No message for {:?}", config.stale_after));
+                break false;
+            }
+            // A newer connect, a disconnect or a dropped client
+            _ = stop.changed() => break true,
+        }
+        stale.as_mut().reset(Instant::now() + config.stale_after);
+    };
+
+    ping.abort();
+    let mut state = shared.state.lock().expect("hyperliquid state lock poisoned");
+    if state.session == generation {
+        state.outbound = None;
+        shared.connected.store(false, Ordering::SeqCst);
+        // Books resume from the snapshot sent after resubscribing
+        shared.books.write().expect("hyperliquid book lock poisoned").clear();
+    }
+    stopped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+
+    fn level(px: &str, sz: &str) -> Value {
+        json!({ "px": px, "sz": sz, "n": 1 })
+    }
+
+    fn book_frame(coin: &str, time: u64, bid: &str, ask: &str) -> String {
+        json!({
+            "channel": "l2Book",
+            "data": { "coin": coin, "time": time, "levels": [[level(bid, "2"), level("99", "1")], [level(ask, "3")]] },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_decodes_channels_with_routing_keys() {
+        let event = |text: &str| match decode_frame(text).unwrap() {
+            Inbound::Event(key, event) => (key, *event),
+            other => panic!("{:?}", other),
+        };
+
+        let (key, mids) = event(r#"{"channel":"allMids","data":{"mids":{"BTC":"64000.5"}}}"#);
+        assert!(matches!(mids, HyperliquidEvent::AllMids(m) if key == "allMids" && m.get("BTC") == Some("64000.5".parse().unwrap())));
+
+        let (key, trades) = event(
+            r#"{"channel":"trades","data":[{"coin":"ETH","side":"B","px":"3100.1","sz":"0.5","hash":"0x1","time":1,"tid":7}]}"#,
+        );
+        assert!(matches!(trades, HyperliquidEvent::Trades(t) if key == "trades:ETH" && t[0].tid == 7));
+
+        let (key, candle) = event(
+            r#"{"channel":"candle","data":{"t":0,"T":59999,"s":"BTC","i":"1m","o":"1","c":"2","h":"3","l":"0.5","v":"10","n":4}}"#,
+        );
+        assert!(matches!(candle, HyperliquidEvent::Candle(c) if key == "candle:BTC:1m" && c.trades == 4));
+
+        let (key, fundings) = event(
+            r#"{"channel":"userFundings","data":{"isSnapshot":true,"user":"0xABC","fundings":[{"time":1,"coin":"BTC","usdc":"-0.4","szi":"1","fundingRate":"0.0001"}]}}"#,
+        );
+        assert!(matches!(fundings, HyperliquidEvent::UserFundings(f) if key == "userFundings:0xabc" && f.is_snapshot));
+
+        let (key, user) = event(r#"{"channel":"user","data":{"nonUserCancel":[{"coin":"SOL","oid":9}]}}"#);
+        assert!(matches!(user, HyperliquidEvent::UserEvent(WsUserEvent::NonUserCancel(c)) if key == "user" && c[0].oid == 9));
+
+        assert_eq!(decode_frame(r#"{"channel":"pong","data":null}"#).unwrap(), Inbound::Control);
+        assert_eq!(
+            decode_frame(r#"{"channel":"error","data":"Invalid subscription"}"#).unwrap(),
+            Inbound::ServerError("Invalid subscription".to_string())
+        );
+        assert_eq!(decode_frame(r#"{"channel":"candle","data":{}}"#).unwrap_err().channel, "candle");
+        assert_eq!(
+            subscription_key(&HyperliquidSubscription::UserFundings { user: "0xABC".to_string() }),
+            "userFundings:0xabc"
+        );
+        assert_eq!(
+            serde_json::to_value(HyperliquidSubscription::L2Book { coin: "BTC".to_string() }).unwrap(),
+            json!({ "type": "l2Book", "coin": "BTC" })
+        );
+    }
+
+    #[test]
+    fn test_local_book_keeps_newest_snapshot() {
+        let client = HyperliquidWsClient::new();
+        let mut stream = client.subscribe_l2_book("BTC");
+
+        client.shared.dispatch(&book_frame("BTC", 20, "100", "101"));
+        client.shared.dispatch(&book_frame("BTC", 10, "90", "91"));
+
+        let book = client.book("BTC").unwrap();
+        assert_eq!(book.time, 20);
+        assert_eq!(book.best_bid().unwrap().px, "100".parse().unwrap());
+        assert_eq!(book.mid(), Some("100.5".parse().unwrap()));
+        assert_eq!(book.spread(), Some("1".parse().unwrap()));
+        assert_eq!(book.depth(5), Some(("3".parse().unwrap(), "3".parse().unwrap())));
+
+        assert_eq!(stream.rx.try_recv().unwrap(), HyperliquidEvent::L2Book(book.clone()));
+        assert!(stream.rx.try_recv().is_err());
+
+        // A late subscriber starts from the local book
+        let mut late = client.subscribe_l2_book("BTC");
+        assert_eq!(late.rx.try_recv().unwrap(), HyperliquidEvent::L2Book(book));
+
+        // Dropping the last stream forgets the book, which would no longer update
+        drop(stream);
+        assert!(client.book("BTC").is_some());
+        drop(late);
+        assert_eq!(client.book("BTC"), None);
+        client.shared.dispatch(&book_frame("BTC", 30, "100", "101"));
+        assert_eq!(client.book("BTC"), None);
+    }
+
+    #[tokio::test]
+    async fn test_resubscribes_after_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        let (subscribed_tx, mut subscribed) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            // Each connection acknowledges one subscription, sends a snapshot and hangs up
+            for time in [1, 2] {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                let request = ws.next().await.unwrap().unwrap().into_text().unwrap();
+                subscribed_tx.send(request).unwrap();
+                ws.send(Message::Text(book_frame("ETH", time, "3000", "3001"))).await.unwrap();
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let mut client = HyperliquidWsClient::with_config(HyperliquidWsConfig {
+            url,
+            reconnect: ReconnectConfig {
+                initial_delay: Duration::from_millis(10),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut books = client.subscribe_l2_book("ETH");
+        client.connect().await.unwrap();
+
+        for time in [1, 2] {
+            let request: Value = serde_json::from_str(&subscribed.recv().await.unwrap()).unwrap();
+            assert_eq!(request, json!({ "method": "subscribe", "subscription": { "type": "l2Book", "coin": "ETH" } }));
+            assert_eq!(books.next().await.unwrap().time, time);
+        }
+        assert_eq!(client.stats().reconnects, 1);
+        client.disconnect();
+    }
+
+    #[test]
+    fn test_user_channels_reject_a_second_user() {
+        let client = HyperliquidWsClient::new();
+        let _events = client.subscribe_user_events("0xABC").unwrap();
+        assert!(client.subscribe_user_events("0xabc").is_ok());
+        assert!(matches!(
+            client.subscribe_user_events("0xDEF"),
+            Err(WebSocketError::SubscriptionConflict(_))
+        ));
+        assert!(matches!(
+            client.subscribe(HyperliquidSubscription::UserEvents { user: "0xDEF".to_string() }),
+            Err(WebSocketError::SubscriptionConflict(_))
+        ));
+        // Other channels are unaffected
+        assert!(client.subscribe_web_data("0xDEF").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_streams_end_when_reconnecting_gives_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.next().await.unwrap().unwrap();
+            ws.close(None).await.unwrap();
+        });
+
+        let mut client = HyperliquidWsClient::with_config(HyperliquidWsConfig {
+            url,
+            reconnect: ReconnectConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut trades = client.subscribe_trades("ETH");
+        client.connect().await.unwrap();
+
+        assert!(trades.next().await.is_none());
+        assert!(client.subscriptions().is_empty());
+    }
+}
@@ -17,6 +17,25 @@ pub enum WebSocketMessage {
     OrderUpdate(OrderUpdate),
     TradeUpdate(TradeUpdate),
     BalanceUpdate(BalanceUpdate),
+    // Room content is decoded by `websocket::rooms`, never matched against this untagged enum
+    #[serde(skip_deserializing)]
+    NewPair(Box<NewPairEvent>),
+    #[serde(skip_deserializing)]
+    TokenPrice(TokenPriceTick),
+    #[serde(skip_deserializing)]
+    WalletTransaction(Box<WalletTransactionEvent>),
+    #[serde(skip_deserializing)]
+    PairStats(Box<PairStatsUpdate>),
+    #[serde(skip_deserializing)]
+    Migration(Box<MigrationEvent>),
+    #[serde(skip_deserializing)]
+    SolPrice(SolPriceUpdate),
+    /// Content of a room without a typed decoder
+    #[serde(skip_deserializing)]
+    RoomUpdate {
+        room: String,
+        content: serde_json::Value,
+    },
     Error {
         code: i32,
         message: String,
@@ -53,6 +72,327 @@ pub struct MarketUpdate {
     pub timestamp: i64,
 }
 
+/// A newly created pair from the `new_pairs` room, with the full room content
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewPairEvent {
+    #[serde(default)]
+    pub pair_address: String,
+    pub token_address: String,
+    #[serde(default)]
+    pub token_name: String,
+    #[serde(default)]
+    pub token_ticker: String,
+    #[serde(default)]
+    pub token_image: Option<String>,
+    #[serde(default)]
+    pub token_uri: Option<String>,
+    #[serde(default)]
+    pub token_decimals: Option<u8>,
+    /// Launch venue as reported by the server, e.g. "Pump V1" or "Raydium CPMM"
+    #[serde(default)]
+    pub protocol: String,
+    #[serde(default, alias = "deployer_address", alias = "dev_address")]
+    pub creator: String,
+    #[serde(default)]
+    pub pair_sol_account: Option<String>,
+    #[serde(default)]
+    pub pair_token_account: Option<String>,
+    #[serde(default)]
+    pub initial_liquidity_sol: f64,
+    #[serde(default)]
+    pub initial_liquidity_token: f64,
+    #[serde(default)]
+    pub supply: f64,
+    #[serde(default)]
+    pub lp_burned: Option<f64>,
+    #[serde(default)]
+    pub top_10_holders: Option<f64>,
+    #[serde(default)]
+    pub website: Option<String>,
+    #[serde(default)]
+    pub twitter: Option<String>,
+    #[serde(default)]
+    pub telegram: Option<String>,
+    #[serde(default)]
+    pub discord: Option<String>,
+    /// None when not reported, Some(None) when the authority has been revoked
+    #[serde(default, deserialize_with = "crate::models::market::nullable", skip_serializing_if = "Option::is_none")]
+    pub mint_authority: Option<Option<String>>,
+    /// None when not reported, Some(None) when the authority has been revoked
+    #[serde(default, deserialize_with = "crate::models::market::nullable", skip_serializing_if = "Option::is_none")]
+    pub freeze_authority: Option<Option<String>>,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Any fields not modelled above
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl NewPairEvent {
+    /// Parses the `content` object of a `new_pairs` room message
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - &serde_json::Value - The room message content
+    ///
+    /// # Returns
+    ///
+    /// Result<NewPairEvent, serde_json::Error> - The parsed event
+    pub fn from_content(content: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        Self::deserialize(content)
+    }
+
+    /// Whether the mint authority is still set; None if the pair did not report it
+    pub fn has_mint_authority(&self) -> Option<bool> {
+        self.mint_authority
+            .as_ref()
+            .map(|a| a.as_deref().is_some_and(|a| !a.is_empty()))
+    }
+
+    /// Whether the freeze authority is still set; None if the pair did not report it
+    pub fn has_freeze_authority(&self) -> Option<bool> {
+        self.freeze_authority
+            .as_ref()
+            .map(|a| a.as_deref().is_some_and(|a| !a.is_empty()))
+    }
+
+    /// Returns true if any social link is set
+    pub fn has_socials(&self) -> bool {
+        [&self.website, &self.twitter, &self.telegram, &self.discord]
+            .iter()
+            .any(|link| link.as_deref().is_some_and(|l| !l.is_empty()))
+    }
+}
+
+/// A price tick from a token's room
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPriceTick {
+    pub token_address: String,
+    pub price_usd: Option<f64>,
+    pub price_sol: Option<f64>,
+    pub market_cap_sol: Option<f64>,
+    /// Server timestamp when present, otherwise the receive time (seconds)
+    pub timestamp: i64,
+    /// The room content as received
+    pub raw: serde_json::Value,
+}
+
+impl TokenPriceTick {
+    /// Parses the content of a token price room.
+    ///
+    /// The room sends either a bare price or an object; both are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_address` - &str - The room, which is the token mint
+    /// * `content` - &serde_json::Value - The room message content
+    ///
+    /// # Returns
+    ///
+    /// Option<TokenPriceTick> - The tick, or None if the content carries no price
+    pub fn from_content(token_address: &str, content: &serde_json::Value) -> Option<Self> {
+        let field = |names: &[&str]| names.iter().find_map(|name| content.get(*name).and_then(as_f64_lenient));
+
+        let (price_usd, price_sol, market_cap_sol, timestamp) = match content {
+            serde_json::Value::Number(_) | serde_json::Value::String(_) => (as_f64_lenient(content), None, None, None),
+            serde_json::Value::Object(_) => (
+                field(&["price_usd", "priceUsd", "price"]),
+                field(&["price_sol", "priceSol"]),
+                field(&["market_cap_sol", "marketCapSol"]),
+                field(&["timestamp", "ts", "time"]).map(|t| t as i64),
+            ),
+            _ => (None, None, None, None),
+        };
+        if price_usd.is_none() && price_sol.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            token_address: token_address.to_string(),
+            price_usd,
+            price_sol,
+            market_cap_sol,
+            timestamp: timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            raw: content.clone(),
+        })
+    }
+}
+
+/// A transaction from a wallet room (`v:<wallet>`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WalletTransactionEvent {
+    /// Filled from the room name
+    pub wallet_address: String,
+    #[serde(alias = "transaction_hash", alias = "tx")]
+    pub signature: String,
+    #[serde(alias = "tokenAddress", alias = "mint")]
+    pub token_address: String,
+    /// "buy", "sell" or whatever the server reports
+    #[serde(alias = "type", alias = "transactionType")]
+    pub transaction_type: String,
+    #[serde(alias = "solAmount")]
+    pub sol_amount: f64,
+    #[serde(alias = "tokenAmount")]
+    pub token_amount: f64,
+    #[serde(alias = "priceSol")]
+    pub price_sol: Option<f64>,
+    #[serde(alias = "ts", alias = "time")]
+    pub timestamp: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl WalletTransactionEvent {
+    /// Parses the content of a wallet room
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - &str - The room, `v:<wallet>`
+    /// * `content` - &serde_json::Value - The room message content
+    ///
+    /// # Returns
+    ///
+    /// Result<WalletTransactionEvent, serde_json::Error> - The parsed event
+    pub fn from_content(room: &str, content: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        let mut event = Self::deserialize(content)?;
+        event.wallet_address = room.strip_prefix("v:").unwrap_or(room).to_string();
+        Ok(event)
+    }
+}
+
+/// SOL/USD price from the `sol_price` room
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SolPriceUpdate {
+    pub price_usd: f64,
+    /// Server timestamp when present, otherwise the receive time (seconds)
+    pub timestamp: i64,
+}
+
+impl SolPriceUpdate {
+    /// Parses the content of the `sol_price` room, a bare price or an object
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - &serde_json::Value - The room message content
+    ///
+    /// # Returns
+    ///
+    /// Option<SolPriceUpdate> - The price, or None if the content carries none
+    pub fn from_content(content: &serde_json::Value) -> Option<Self> {
+        let field = |names: &[&str]| names.iter().find_map(|name| content.get(*name).and_then(as_f64_lenient));
+        let (price_usd, timestamp) = match content {
+            serde_json::Value::Object(_) => (
+                field(&["price_usd", "priceUsd", "price", "solPrice"]),
+                field(&["timestamp", "ts", "time"]).map(|t| t as i64),
+            ),
+            _ => (as_f64_lenient(content), None),
+        };
+
+        Some(Self {
+            price_usd: price_usd.filter(|price| *price > 0.0)?,
+            timestamp: timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        })
+    }
+}
+
+/// Rolling stats for a pair from its stats room (`p:<pair>`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PairStatsUpdate {
+    /// Filled from the room name
+    pub pair_address: String,
+    #[serde(alias = "priceSol")]
+    pub price_sol: Option<f64>,
+    #[serde(alias = "priceUsd")]
+    pub price_usd: Option<f64>,
+    #[serde(alias = "marketCapSol")]
+    pub market_cap_sol: Option<f64>,
+    #[serde(alias = "liquiditySol")]
+    pub liquidity_sol: Option<f64>,
+    #[serde(alias = "volumeSol")]
+    pub volume_sol: Option<f64>,
+    #[serde(alias = "buyCount", alias = "buys")]
+    pub buy_count: Option<u64>,
+    #[serde(alias = "sellCount", alias = "sells")]
+    pub sell_count: Option<u64>,
+    #[serde(alias = "numHolders")]
+    pub holders: Option<u64>,
+    #[serde(alias = "ts", alias = "time")]
+    pub timestamp: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl PairStatsUpdate {
+    /// Parses the content of a pair stats room
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - &str - The room, `p:<pair>`
+    /// * `content` - &serde_json::Value - The room message content
+    ///
+    /// # Returns
+    ///
+    /// Result<PairStatsUpdate, serde_json::Error> - The parsed update
+    pub fn from_content(room: &str, content: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        let mut update = Self::deserialize(content)?;
+        update.pair_address = room.strip_prefix("p:").unwrap_or(room).to_string();
+        Ok(update)
+    }
+}
+
+/// A token leaving its bonding curve for an AMM pool, from the `migrations` room
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    #[serde(alias = "tokenAddress", alias = "mint")]
+    pub token_address: String,
+    /// The new AMM pair
+    #[serde(default, alias = "pairAddress")]
+    pub pair_address: String,
+    /// The bonding curve pair the token migrated from
+    #[serde(default, alias = "oldPairAddress", alias = "bonding_curve")]
+    pub previous_pair_address: Option<String>,
+    #[serde(default, alias = "tokenTicker")]
+    pub token_ticker: String,
+    #[serde(default, alias = "tokenName")]
+    pub token_name: String,
+    /// Destination protocol, e.g. "Pump AMM" or "Raydium CPMM"
+    #[serde(default)]
+    pub protocol: String,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default, alias = "createdAt")]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl MigrationEvent {
+    /// Parses the content of the `migrations` room
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - &serde_json::Value - The room message content
+    ///
+    /// # Returns
+    ///
+    /// Result<MigrationEvent, serde_json::Error> - The parsed event
+    pub fn from_content(content: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        Self::deserialize(content)
+    }
+}
+
+fn as_f64_lenient(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderUpdate {
     pub order_id: String,
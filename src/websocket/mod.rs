@@ -1,7 +1,32 @@
 pub mod client;
 pub mod messages;
 pub mod handler;
+pub mod backpressure;
+pub mod candles;
+pub(crate) mod dispatch;
+pub mod heartbeat;
+pub mod hyperliquid;
+pub mod new_pairs;
+pub mod pool;
+pub mod reconnect;
+pub mod recorder;
+pub mod rooms;
+pub mod selector;
+pub mod stream;
+pub mod transport;
 
 pub use client::{WebSocketClient, Region, WebSocketError};
-pub use messages::{WebSocketMessage, SubscriptionType, MarketUpdate, OrderUpdate, TradeUpdate, BalanceUpdate};
-pub use handler::MessageHandler;
\ No newline at end of file
+pub use messages::{WebSocketMessage, SubscriptionType, MarketUpdate, OrderUpdate, TradeUpdate, BalanceUpdate, NewPairEvent, TokenPriceTick, WalletTransactionEvent, SolPriceUpdate, PairStatsUpdate, MigrationEvent};
+pub use handler::MessageHandler;
+pub use candles::{CandleAggregator, CandleEvent};
+pub use new_pairs::{NewPairHandler, NewPairPipeline, NewPairStream, PairFilter};
+pub use stream::{RoomEvent, StreamStats, Subscription};
+pub use reconnect::ReconnectConfig;
+pub use heartbeat::{ConnectionHealth, HeartbeatConfig};
+pub use rooms::{decode_room_message, DecodeStats, RoomDecodeError, RoomKind};
+pub use selector::{HostLatency, RegionSelector, SelectorConfig};
+pub use pool::{ConnectionEvent, ConnectionId, Feed, PoolConfig, PoolEvent, PoolEvents, WebSocketPool};
+pub use recorder::{RecordedFrame, ReplayError, ReplaySpeed, ReplayStats, SessionRecorder, SessionReplay};
+pub use transport::{FrameReader, FrameWriter, InboundFrame, TransportError, TransportKind};
+pub use backpressure::{DispatchConfig, DispatchStats, OverflowCallback, OverflowPolicy, OverflowWarning};
+pub use hyperliquid::{HyperliquidDecodeError, HyperliquidEvent, HyperliquidStream, HyperliquidWsClient, HyperliquidWsConfig, HyperliquidWsStats, L2Book, WsCandle, WsFill, WsFunding, WsTrade, WsUserEvent, WsUserFills, WsUserFundings};
\ No newline at end of file
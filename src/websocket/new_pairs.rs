@@ -0,0 +1,554 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use futures_util::Stream;
+use regex::Regex;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::risk::{RiskChecker, TokenRiskReport};
+use crate::websocket::handler::MessageHandler;
+use crate::websocket::messages::{NewPairEvent, WebSocketMessage};
+
+/// A single check applied to every new pair
+#[derive(Debug, Clone)]
+pub enum PairFilter {
+    /// Initial SOL liquidity must be at least this amount
+    MinLiquiditySol(f64),
+    /// Protocol must be one of these, compared case-insensitively
+    Protocols(HashSet<String>),
+    /// Ticker must match this pattern
+    TickerPattern(Regex),
+    /// Pairs deployed by these creators are dropped
+    CreatorBlocklist(HashSet<String>),
+    /// At least one social link must be set
+    RequireSocials,
+    /// Mint authority must be reported as revoked
+    NoMintAuthority,
+    /// Freeze authority must be reported as revoked
+    NoFreezeAuthority,
+}
+
+impl PairFilter {
+    /// Checks a pair against this filter
+    ///
+    /// # Arguments
+    ///
+    /// * `pair` - &NewPairEvent - The pair to check
+    ///
+    /// # Returns
+    ///
+    /// Result<(), PairRejection> - Ok if the pair passes
+    pub fn check(&self, pair: &NewPairEvent) -> Result<(), PairRejection> {
+        match self {
+            PairFilter::MinLiquiditySol(min) if pair.initial_liquidity_sol < *min => {
+                Err(PairRejection::LowLiquidity { liquidity_sol: pair.initial_liquidity_sol, min_sol: *min })
+            }
+            PairFilter::Protocols(allowed) if !allowed.iter().any(|p| p.eq_ignore_ascii_case(&pair.protocol)) => {
+                Err(PairRejection::Protocol(pair.protocol.clone()))
+            }
+            PairFilter::TickerPattern(pattern) if !pattern.is_match(&pair.token_ticker) => {
+                Err(PairRejection::Ticker(pair.token_ticker.clone()))
+            }
+            PairFilter::CreatorBlocklist(blocked) if blocked.contains(&pair.creator) => {
+                Err(PairRejection::BlockedCreator(pair.creator.clone()))
+            }
+            PairFilter::RequireSocials if !pair.has_socials() => Err(PairRejection::NoSocials),
+            PairFilter::NoMintAuthority => match pair.has_mint_authority() {
+                Some(false) => Ok(()),
+                Some(true) => Err(PairRejection::MintAuthority),
+                None => Err(PairRejection::MintAuthorityUnreported),
+            },
+            PairFilter::NoFreezeAuthority => match pair.has_freeze_authority() {
+                Some(false) => Ok(()),
+                Some(true) => Err(PairRejection::FreezeAuthority),
+                None => Err(PairRejection::FreezeAuthorityUnreported),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Why a pair was dropped by the pipeline
+#[derive(Debug, Clone, PartialEq)]
+pub enum PairRejection {
+    LowLiquidity { liquidity_sol: f64, min_sol: f64 },
+    Protocol(String),
+    Ticker(String),
+    BlockedCreator(String),
+    NoSocials,
+    MintAuthority,
+    FreezeAuthority,
+    /// The pair did not say whether its mint authority is revoked
+    MintAuthorityUnreported,
+    /// The pair did not say whether its freeze authority is revoked
+    FreezeAuthorityUnreported,
+    RiskScore { score: u8, min_score: u8 },
+    /// The risk report failed its own rule set, e.g. on a critical finding
+    RiskRejected(String),
+    RiskCheckFailed(String),
+}
+
+impl fmt::Display for PairRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PairRejection::LowLiquidity { liquidity_sol, min_sol } => {
+                write!(f, "liquidity {} SOL below {} SOL", liquidity_sol, min_sol)
+            }
+            PairRejection::Protocol(protocol) => write!(f, "protocol {} not allowed", protocol),
+            PairRejection::Ticker(ticker) => write!(f, "ticker {} does not match", ticker),
+            PairRejection::BlockedCreator(creator) => write!(f, "creator {} is blocked", creator),
+            PairRejection::NoSocials => write!(f, "no social links"),
+            PairRejection::MintAuthority => write!(f, "mint authority not revoked"),
+            PairRejection::FreezeAuthority => write!(f, "freeze authority not revoked"),
+            PairRejection::MintAuthorityUnreported => write!(f, "mint authority not reported"),
+            PairRejection::FreezeAuthorityUnreported => write!(f, "freeze authority not reported"),
+            PairRejection::RiskScore { score, min_score } => write!(f, "risk score {} below {}", score, min_score),
+            PairRejection::RiskRejected(summary) => write!(f, "rejected by risk check: {}", summary),
+            PairRejection::RiskCheckFailed(error) => write!(f, "risk check failed: {}", error),
+        }
+    }
+}
+
+/// A pair that passed every filter, with its risk report if risk scoring is enabled
+#[derive(Debug, Clone)]
+pub struct MatchedPair {
+    pub pair: NewPairEvent,
+    pub risk: Option<TokenRiskReport>,
+}
+
+struct RiskGate {
+    checker: RiskChecker,
+    min_score: u8,
+}
+
+impl RiskGate {
+    /// Rejects reports that failed their rule set or score below the pipeline's minimum
+    fn check(report: &TokenRiskReport, min_score: u8) -> Result<(), PairRejection> {
+        if !report.passed {
+            return Err(PairRejection::RiskRejected(report.summary()));
+        }
+        if report.score < min_score {
+            return Err(PairRejection::RiskScore { score: report.score, min_score });
+        }
+        Ok(())
+    }
+}
+
+/// Composable filter pipeline over new pairs.
+///
+/// Cheap field filters run first, in the order they were added; the risk check, which costs
+/// an API round-trip, only runs for pairs that passed them.
+#[derive(Default)]
+pub struct NewPairPipeline {
+    filters: Vec<PairFilter>,
+    risk: Option<RiskGate>,
+}
+
+impl NewPairPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_filter(mut self, filter: PairFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn min_liquidity_sol(self, min_sol: f64) -> Self {
+        self.with_filter(PairFilter::MinLiquiditySol(min_sol))
+    }
+
+    pub fn protocols<I, S>(self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_filter(PairFilter::Protocols(protocols.into_iter().map(Into::into).collect()))
+    }
+
+    /// Adds a ticker regex filter
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - &str - Regex the ticker must match
+    ///
+    /// # Returns
+    ///
+    /// Result<NewPairPipeline, regex::Error> - The pipeline, or an error if the pattern is invalid
+    pub fn ticker_pattern(self, pattern: &str) -> Result<Self, regex::Error> {
+        Ok(self.with_filter(PairFilter::TickerPattern(Regex::new(pattern)?)))
+    }
+
+    pub fn block_creators<I, S>(self, creators: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_filter(PairFilter::CreatorBlocklist(creators.into_iter().map(Into::into).collect()))
+    }
+
+    pub fn require_socials(self) -> Self {
+        self.with_filter(PairFilter::RequireSocials)
+    }
+
+    pub fn no_mint_authority(self) -> Self {
+        self.with_filter(PairFilter::NoMintAuthority)
+    }
+
+    pub fn no_freeze_authority(self) -> Self {
+        self.with_filter(PairFilter::NoFreezeAuthority)
+    }
+
+    /// Scores pairs that pass the field filters and drops those that fail the checker's rule set
+    /// or score below `min_score`
+    ///
+    /// # Arguments
+    ///
+    /// * `checker` - RiskChecker - Checker used to fetch and score token profiles
+    /// * `min_score` - u8 - Minimum risk score (0-100) to keep a pair
+    ///
+    /// # Returns
+    ///
+    /// NewPairPipeline - The pipeline with risk scoring enabled
+    pub fn with_risk_checker(mut self, checker: RiskChecker, min_score: u8) -> Self {
+        self.risk = Some(RiskGate { checker, min_score });
+        self
+    }
+
+    /// Runs the field filters only
+    ///
+    /// # Arguments
+    ///
+    /// * `pair` - &NewPairEvent - The pair to check
+    ///
+    /// # Returns
+    ///
+    /// Result<(), PairRejection> - The first failing filter, if any
+    pub fn check(&self, pair: &NewPairEvent) -> Result<(), PairRejection> {
+        self.filters.iter().try_for_each(|filter| filter.check(pair))
+    }
+
+    /// Runs the field filters, then the risk check if enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `pair` - NewPairEvent - The pair to evaluate
+    ///
+    /// # Returns
+    ///
+    /// Result<MatchedPair, PairRejection> - The matched pair or the reason it was dropped
+    pub async fn evaluate(&mut self, pair: NewPairEvent) -> Result<MatchedPair, PairRejection> {
+        self.check(&pair)?;
+
+        let risk = match &mut self.risk {
+            Some(gate) => {
+                let report = gate
+                    .checker
+                    .assess(&pair.token_address)
+                    .await
+                    .map_err(|e| PairRejection::RiskCheckFailed(e.to_string()))?;
+                RiskGate::check(&report, gate.min_score)?;
+                Some(report)
+            }
+            None => None,
+        };
+
+        Ok(MatchedPair { pair, risk })
+    }
+
+    /// Spawns the pipeline over a feed of pairs
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - mpsc::Receiver<NewPairEvent> - Incoming pairs, e.g. from `NewPairHandler`
+    /// * `buffer` - usize - Capacity of the output channel
+    ///
+    /// # Returns
+    ///
+    /// NewPairStream - Stream of matching pairs; dropping it stops the pipeline
+    pub fn spawn(mut self, mut pairs: mpsc::Receiver<NewPairEvent>, buffer: usize) -> NewPairStream {
+        let (tx, rx) = mpsc::channel(buffer.max(1));
+        let stats = Arc::new(PipelineCounters::default());
+        let counters = Arc::clone(&stats);
+
+        let task = tokio::spawn(async move {
+            while let Some(pair) = pairs.recv().await {
+                counters.received.fetch_add(1, Ordering::Relaxed);
+                match self.evaluate(pair).await {
+                    Ok(matched) => {
+                        counters.matched.fetch_add(1, Ordering::Relaxed);
+                        if tx.send(matched).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => {
+                        counters.rejected.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        });
+
+        NewPairStream { rx, task, stats }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PipelineCounters {
+    received: AtomicU64,
+    matched: AtomicU64,
+    rejected: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineStats {
+    pub received: u64,
+    pub matched: u64,
+    pub rejected: u64,
+}
+
+/// Stream of pairs that passed a `NewPairPipeline`
+pub struct NewPairStream {
+    rx: mpsc::Receiver<MatchedPair>,
+    task: JoinHandle<()>,
+    stats: Arc<PipelineCounters>,
+}
+
+impl NewPairStream {
+    /// Waits for the next matching pair
+    ///
+    /// # Returns
+    ///
+    /// Option<MatchedPair> - The next pair, or None once the feed has closed
+    pub async fn recv(&mut self) -> Option<MatchedPair> {
+        self.rx.recv().await
+    }
+
+    pub fn stats(&self) -> PipelineStats {
+        PipelineStats {
+            received: self.stats.received.load(Ordering::Relaxed),
+            matched: self.stats.matched.load(Ordering::Relaxed),
+            rejected: self.stats.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Stream for NewPairStream {
+    type Item = MatchedPair;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<MatchedPair>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for NewPairStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Message handler that forwards `new_pairs` events into a channel.
+///
+/// Other messages, and new pairs as well, are passed on to the inner handler if one is set.
+/// Forwarding never blocks the socket read loop: pairs arriving while the channel is full are
+/// dropped and counted.
+pub struct NewPairHandler {
+    tx: mpsc::Sender<NewPairEvent>,
+    inner: Option<Arc<dyn MessageHandler>>,
+    dropped: AtomicU64,
+}
+
+impl NewPairHandler {
+    /// Creates a handler and the receiving end of its pair feed
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - usize - Capacity of the pair channel
+    ///
+    /// # Returns
+    ///
+    /// (NewPairHandler, mpsc::Receiver<NewPairEvent>) - The handler and the pair feed
+    pub fn new(buffer: usize) -> (Self, mpsc::Receiver<NewPairEvent>) {
+        let (tx, rx) = mpsc::channel(buffer.max(1));
+        (Self { tx, inner: None, dropped: AtomicU64::new(0) }, rx)
+    }
+
+    pub fn with_inner(mut self, inner: Arc<dyn MessageHandler>) -> Self {
+        self.inner = Some(inner);
+        self
+    }
+
+    /// Number of pairs dropped because the channel was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl MessageHandler for NewPairHandler {
+    async fn handle_message(&self, message: WebSocketMessage) {
+        if let WebSocketMessage::NewPair(pair) = &message
+            && self.tx.try_send(pair.as_ref().clone()).is_err()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(inner) = &self.inner {
+            inner.handle_message(message).await;
+        }
+    }
+
+    async fn on_connected(&self, session_id: String) {
+        if let Some(inner) = &self.inner {
+            inner.on_connected(session_id).await;
+        }
+    }
+
+    async fn on_disconnected(&self, reason: String) {
+        if let Some(inner) = &self.inner {
+            inner.on_disconnected(reason).await;
+        }
+    }
+
+    async fn on_error(&self, error: String) {
+        if let Some(inner) = &self.inner {
+            inner.on_error(error).await;
+        }
+    }
+
+    async fn on_reconnected(&self, attempts: u32) {
+        if let Some(inner) = &self.inner {
+            inner.on_reconnected(attempts).await;
+        }
+    }
+
+    async fn on_resubscribe_failed(&self, room: String, error: String) {
+        if let Some(inner) = &self.inner {
+            inner.on_resubscribe_failed(room, error).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::TokenProfile;
+    use crate::risk::RiskConfig;
+    use serde_json::json;
+
+    fn pair(ticker: &str, liquidity_sol: f64, creator: &str) -> NewPairEvent {
+        NewPairEvent::from_content(&json!({
+            "pair_address": format!("pair_{}", ticker),
+            "token_address": format!("mint_{}", ticker),
+            "token_ticker": ticker,
+            "protocol": "Pump V1",
+            "deployer_address": creator,
+            "initial_liquidity_sol": liquidity_sol,
+            "twitter": "https://x.com/example",
+            "mint_authority": null,
+            "dev_holds_percent": 3.5
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_pair_content_parsing() {
+        let parsed = pair("CAT", 12.0, "dev1");
+        assert_eq!(parsed.creator, "dev1");
+        assert_eq!(parsed.pair_address, "pair_CAT");
+        assert!(parsed.has_socials());
+        assert_eq!(parsed.has_mint_authority(), Some(false));
+        assert_eq!(parsed.has_freeze_authority(), None);
+        assert_eq!(parsed.extra.get("dev_holds_percent"), Some(&json!(3.5)));
+    }
+
+    #[test]
+    fn test_filters_reject_in_order() {
+        let pipeline = NewPairPipeline::new()
+            .min_liquidity_sol(5.0)
+            .protocols(["pump v1", "Raydium CPMM"])
+            .ticker_pattern("^[A-Z]{3,5}$")
+            .unwrap()
+            .block_creators(["rugger"])
+            .no_mint_authority();
+
+        assert_eq!(pipeline.check(&pair("CAT", 12.0, "dev1")), Ok(()));
+        assert!(matches!(pipeline.check(&pair("CAT", 1.0, "dev1")), Err(PairRejection::LowLiquidity { .. })));
+        assert_eq!(pipeline.check(&pair("cat", 12.0, "dev1")), Err(PairRejection::Ticker("cat".to_string())));
+        assert_eq!(
+            pipeline.check(&pair("DOG", 12.0, "rugger")),
+            Err(PairRejection::BlockedCreator("rugger".to_string()))
+        );
+
+        let mut raydium_only = pair("CAT", 12.0, "dev1");
+        raydium_only.protocol = "Meteora DLMM".to_string();
+        assert!(matches!(pipeline.check(&raydium_only), Err(PairRejection::Protocol(_))));
+    }
+
+    #[test]
+    fn test_authority_filters_reject_unreported_authorities() {
+        let pipeline = NewPairPipeline::new().no_mint_authority().no_freeze_authority();
+
+        // `pair` reports a revoked mint authority but omits the freeze authority
+        let mut unreported = pair("CAT", 12.0, "dev1");
+        assert_eq!(pipeline.check(&unreported), Err(PairRejection::FreezeAuthorityUnreported));
+
+        unreported.freeze_authority = Some(None);
+        assert_eq!(pipeline.check(&unreported), Ok(()));
+
+        unreported.mint_authority = None;
+        assert_eq!(pipeline.check(&unreported), Err(PairRejection::MintAuthorityUnreported));
+
+        unreported.mint_authority = Some(Some("Authority1111".to_string()));
+        assert_eq!(pipeline.check(&unreported), Err(PairRejection::MintAuthority));
+    }
+
+    #[test]
+    fn test_risk_gate_rejects_failed_reports() {
+        let mut profile: TokenProfile = serde_json::from_value(json!({
+            "tokenAddress": "mint_CAT",
+            "tokenTicker": "CAT",
+            "liquiditySol": 50.0,
+            "mintAuthority": null,
+            "freezeAuthority": null,
+            "lpBurned": 100.0,
+            "creatorRiskLevel": "low",
+            "creatorRugCount": 0
+        }))
+        .unwrap();
+        let config = RiskConfig::default();
+        assert_eq!(RiskGate::check(&config.evaluate_profile(&profile), 50), Ok(()));
+
+        // A critical finding costs 60 points, so the score alone would still clear a low minimum
+        profile.mint_authority = Some(Some("Authority1111".to_string()));
+        let report = RiskConfig { min_score: 0, ..config }.evaluate_profile(&profile);
+        assert_eq!(report.score, 40);
+        assert!(!report.passed);
+        assert!(matches!(RiskGate::check(&report, 10), Err(PairRejection::RiskRejected(_))));
+        assert_eq!(
+            RiskGate::check(&TokenRiskReport { passed: true, ..report }, 50),
+            Err(PairRejection::RiskScore { score: 40, min_score: 50 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_only_matches() {
+        let (handler, feed) = NewPairHandler::new(16);
+        let mut stream = NewPairPipeline::new().min_liquidity_sol(5.0).spawn(feed, 16);
+
+        for candidate in [pair("AAA", 1.0, "a"), pair("BBB", 10.0, "b"), pair("CCC", 2.0, "c")] {
+            handler.handle_message(WebSocketMessage::NewPair(Box::new(candidate))).await;
+        }
+        drop(handler);
+
+        let matched = stream.recv().await.unwrap();
+        assert_eq!(matched.pair.token_ticker, "BBB");
+        assert!(matched.risk.is_none());
+        assert!(stream.recv().await.is_none());
+        assert_eq!(stream.stats(), PipelineStats { received: 3, matched: 1, rejected: 2 });
+    }
+}
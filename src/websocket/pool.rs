@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use futures_util::Stream;
+use tokio::sync::{mpsc, RwLock};
+
+use crate::auth::AuthClient;
+use crate::websocket::client::{Region, WebSocketClient, WebSocketError};
+use crate::websocket::handler::MessageHandler;
+use crate::websocket::messages::WebSocketMessage;
+use crate::websocket::recorder::SessionRecorder;
+use crate::websocket::rooms::RoomKind;
+use crate::websocket::transport::TransportKind;
+
+/// The two Axiom feeds: the regional cluster and the dedicated token price socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Feed {
+    Cluster,
+    TokenPrice,
+}
+
+impl Feed {
+    /// Gets the feed that serves a room; token mint rooms go to the token price socket
+    pub fn for_room(room: &str) -> Self {
+        match RoomKind::parse(room) {
+            RoomKind::TokenPrice(_) => Feed::TokenPrice,
+            _ => Feed::Cluster,
+        }
+    }
+}
+
+/// One pooled connection: a feed and a shard index within it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConnectionId {
+    pub feed: Feed,
+    pub shard: usize,
+}
+
+impl fmt::Display for ConnectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let feed = match self.feed {
+            Feed::Cluster => "cluster",
+            Feed::TokenPrice => "token_price",
+        };
+        write!(f, "{}#{}", feed, self.shard)
+    }
+}
+
+/// What happened on a pooled connection
+#[derive(Debug, Clone)]
+pub enum ConnectionEvent {
+    Message(WebSocketMessage),
+    Connected(String),
+    Disconnected(String),
+    Error(String),
+    Reconnected(u32),
+    ResubscribeFailed { room: String, error: String },
+}
+
+/// An event tagged with the connection it came from
+#[derive(Debug, Clone)]
+pub struct PoolEvent {
+    pub source: ConnectionId,
+    pub event: ConnectionEvent,
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Region for cluster connections
+    pub region: Region,
+    /// Rooms per connection before another shard is opened
+    pub max_rooms_per_connection: usize,
+    /// Shards per feed; once reached, rooms go to the least loaded shard
+    pub max_connections_per_feed: usize,
+    /// Capacity of the merged event stream; events are dropped and counted when it is full
+    pub event_buffer: usize,
+    /// WebSocket backend for every connection
+    pub transport: TransportKind,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            region: Region::Global,
+            max_rooms_per_connection: 100,
+            max_connections_per_feed: 8,
+            event_buffer: 1024,
+            transport: TransportKind::default(),
+        }
+    }
+}
+
+/// Assigns rooms to connections: by feed first, then to the least loaded shard
+#[derive(Debug, Default)]
+pub(crate) struct RoomRouter {
+    assignments: HashMap<String, ConnectionId>,
+    load: HashMap<ConnectionId, usize>,
+}
+
+impl RoomRouter {
+    /// Picks the connection for a room without assigning it
+    pub fn route(&self, room: &str, config: &PoolConfig) -> ConnectionId {
+        if let Some(id) = self.assignments.get(room) {
+            return *id;
+        }
+
+        let feed = Feed::for_room(room);
+        let mut shards: Vec<(ConnectionId, usize)> = self
+            .load
+            .iter()
+            .filter(|(id, _)| id.feed == feed)
+            .map(|(id, load)| (*id, *load))
+            .collect();
+        shards.sort();
+
+        let least_loaded = shards.iter().min_by_key(|(_, load)| *load).copied();
+        match least_loaded {
+            Some((id, load)) if load < config.max_rooms_per_connection.max(1) => id,
+            Some((id, _)) if shards.len() >= config.max_connections_per_feed.max(1) => id,
+            _ => ConnectionId {
+                feed,
+                shard: shards.len(),
+            },
+        }
+    }
+
+    pub fn assign(&mut self, room: &str, id: ConnectionId) {
+        if self.assignments.insert(room.to_string(), id).is_none() {
+            *self.load.entry(id).or_default() += 1;
+        }
+    }
+
+    pub fn release(&mut self, room: &str) -> Option<ConnectionId> {
+        let id = self.assignments.remove(room)?;
+        if let Some(load) = self.load.get_mut(&id) {
+            *load = load.saturating_sub(1);
+        }
+        Some(id)
+    }
+
+    pub fn rooms_on(&self, id: ConnectionId) -> usize {
+        self.load.get(&id).copied().unwrap_or(0)
+    }
+}
+
+/// Forwards a connection's callbacks into the pool's merged stream
+struct PoolHandler {
+    source: ConnectionId,
+    tx: mpsc::Sender<PoolEvent>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl PoolHandler {
+    fn forward(&self, event: ConnectionEvent) {
+        let event = PoolEvent {
+            source: self.source,
+            event,
+        };
+        // Never block a read loop on a slow consumer
+        if self.tx.try_send(event).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[async_trait]
+impl MessageHandler for PoolHandler {
+    async fn handle_message(&self, message: WebSocketMessage) {
+        self.forward(ConnectionEvent::Message(message));
+    }
+
+    async fn on_connected(&self, session_id: String) {
+        self.forward(ConnectionEvent::Connected(session_id));
+    }
+
+    async fn on_disconnected(&self, reason: String) {
+        self.forward(ConnectionEvent::Disconnected(reason));
+    }
+
+    async fn on_error(&self, error: String) {
+        self.forward(ConnectionEvent::Error(error));
+    }
+
+    async fn on_reconnected(&self, attempts: u32) {
+        self.forward(ConnectionEvent::Reconnected(attempts));
+    }
+
+    async fn on_resubscribe_failed(&self, room: String, error: String) {
+        self.forward(ConnectionEvent::ResubscribeFailed { room, error });
+    }
+}
+
+/// Several WebSocket connections behind one subscription API.
+///
+/// Token mint rooms go to the token price socket and every other room to the cluster feed.
+/// Each feed is sharded once a connection holds `max_rooms_per_connection` rooms. Connections
+/// open on first use, and every connection keeps its own reconnect and resubscribe handling.
+/// All connections share one auth client, so the session is refreshed once for the pool.
+/// Events from all of them arrive on one stream, tagged with their source.
+pub struct WebSocketPool {
+    config: PoolConfig,
+    router: RoomRouter,
+    connections: HashMap<ConnectionId, WebSocketClient>,
+    events_tx: mpsc::Sender<PoolEvent>,
+    events_rx: Option<mpsc::Receiver<PoolEvent>>,
+    dropped: Arc<AtomicU64>,
+    recorder: Option<Arc<SessionRecorder>>,
+    auth_client: Option<Arc<RwLock<AuthClient>>>,
+}
+
+impl WebSocketPool {
+    /// Creates an empty pool; connections open as rooms are joined
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - PoolConfig - Region, sharding limits and event buffer
+    ///
+    /// # Returns
+    ///
+    /// WebSocketPool - A new pool instance
+    pub fn new(config: PoolConfig) -> Self {
+        let (events_tx, events_rx) = mpsc::channel(config.event_buffer.max(1));
+        Self {
+            config,
+            router: RoomRouter::default(),
+            connections: HashMap::new(),
+            events_tx,
+            events_rx: Some(events_rx),
+            dropped: Arc::new(AtomicU64::new(0)),
+            recorder: None,
+            auth_client: None,
+        }
+    }
+
+    /// Authenticates connections opened afterwards with a shared auth client
+    ///
+    /// Without one, the pool shares the auth client of its first connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `auth_client` - Arc<RwLock<AuthClient>> - The session used by every connection
+    pub fn set_auth_client(&mut self, auth_client: Arc<RwLock<AuthClient>>) {
+        self.auth_client = Some(auth_client);
+    }
+
+    /// Records inbound frames of connections opened afterwards, tagged with their `ConnectionId`
+    ///
+    /// # Arguments
+    ///
+    /// * `recorder` - Arc<SessionRecorder> - Destination shared by all connections
+    pub fn set_recorder(&mut self, recorder: Arc<SessionRecorder>) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Takes the merged event stream; only the first call returns it
+    ///
+    /// # Returns
+    ///
+    /// Option<PoolEvents> - Events from every connection, tagged with their source
+    pub fn events(&mut self) -> Option<PoolEvents> {
+        self.events_rx.take().map(|rx| PoolEvents { rx })
+    }
+
+    /// Joins a room on the connection that serves it, opening the connection if needed
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - &str - The room to join
+    ///
+    /// # Returns
+    ///
+    /// Result<ConnectionId, WebSocketError> - The connection now carrying the room
+    pub async fn join(&mut self, room: &str) -> Result<ConnectionId, WebSocketError> {
+        let id = self.router.route(room, &self.config);
+        self.connection(id).await?.subscribe_room(room).await?;
+        self.router.assign(room, id);
+        Ok(id)
+    }
+
+    /// Leaves a room; connections stay open while they carry other rooms
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - &str - The room to leave
+    ///
+    /// # Returns
+    ///
+    /// Result<(), WebSocketError> - Ok if the room was left or never joined
+    pub async fn leave(&mut self, room: &str) -> Result<(), WebSocketError> {
+        let Some(id) = self.router.release(room) else {
+            return Ok(());
+        };
+        if let Some(client) = self.connections.get_mut(&id) {
+            client.unsubscribe_room(room).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn subscribe_new_pairs(&mut self) -> Result<ConnectionId, WebSocketError> {
+        self.join("new_pairs").await
+    }
+
+    pub async fn subscribe_token_price(&mut self, token_address: &str) -> Result<ConnectionId, WebSocketError> {
+        self.join(token_address).await
+    }
+
+    pub async fn subscribe_wallet_transactions(&mut self, wallet_address: &str) -> Result<ConnectionId, WebSocketError> {
+        self.join(&format!("v:{}", wallet_address)).await
+    }
+
+    /// Gets the open connections and how many rooms each carries
+    ///
+    /// # Returns
+    ///
+    /// Vec<(ConnectionId, usize)> - Connections in feed and shard order
+    pub fn connections(&self) -> Vec<(ConnectionId, usize)> {
+        let mut connections: Vec<_> = self
+            .connections
+            .keys()
+            .map(|id| (*id, self.router.rooms_on(*id)))
+            .collect();
+        connections.sort();
+        connections
+    }
+
+    /// Gets a pooled connection, e.g. for its health or stream subscriptions
+    pub fn client(&self, id: ConnectionId) -> Option<&WebSocketClient> {
+        self.connections.get(&id)
+    }
+
+    /// Events dropped because the merged stream was full
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Disconnects every connection and forgets all rooms
+    pub async fn disconnect_all(&mut self) {
+        for (_, mut client) in self.connections.drain() {
+            client.disconnect().await;
+        }
+        self.router = RoomRouter::default();
+    }
+
+    async fn connection(&mut self, id: ConnectionId) -> Result<&mut WebSocketClient, WebSocketError> {
+        if !self.connections.contains_key(&id) {
+            let handler = Arc::new(PoolHandler {
+                source: id,
+                tx: self.events_tx.clone(),
+                dropped: Arc::clone(&self.dropped),
+            });
+            let mut client = WebSocketClient::with_region(handler, self.config.region.clone())?;
+            // One session for every shard, so refreshes don't rotate tokens under each other
+            match &self.auth_client {
+                Some(auth_client) => client.set_auth_client(Arc::clone(auth_client)),
+                None => self.auth_client = Some(client.auth_client()),
+            }
+            client.set_transport(self.config.transport);
+            if let Some(recorder) = &self.recorder {
+                client.set_recorder(Arc::clone(recorder), id.to_string());
+            }
+            match id.feed {
+                Feed::Cluster => client.connect().await?,
+                Feed::TokenPrice => client.connect_token_price().await?,
+            }
+            self.connections.insert(id, client);
+        }
+        Ok(self.connections.get_mut(&id).expect("connection inserted above"))
+    }
+}
+
+/// Merged event stream of a `WebSocketPool`
+pub struct PoolEvents {
+    rx: mpsc::Receiver<PoolEvent>,
+}
+
+impl PoolEvents {
+    /// Waits for the next event from any connection
+    ///
+    /// # Returns
+    ///
+    /// Option<PoolEvent> - The next event, or None once the pool is dropped
+    pub async fn recv(&mut self) -> Option<PoolEvent> {
+        self.rx.recv().await
+    }
+}
+
+impl Stream for PoolEvents {
+    type Item = PoolEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<PoolEvent>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINTS: [&str; 3] = [
+        "So11111111111111111111111111111111111111112",
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263",
+    ];
+
+    fn id(feed: Feed, shard: usize) -> ConnectionId {
+        ConnectionId { feed, shard }
+    }
+
+    fn join(router: &mut RoomRouter, room: &str, config: &PoolConfig) -> ConnectionId {
+        let id = router.route(room, config);
+        router.assign(room, id);
+        id
+    }
+
+    #[test]
+    fn test_routes_by_feed_and_shards() {
+        let config = PoolConfig {
+            max_rooms_per_connection: 2,
+            max_connections_per_feed: 2,
+            ..Default::default()
+        };
+        let mut router = RoomRouter::default();
+
+        assert_eq!(join(&mut router, "new_pairs", &config), id(Feed::Cluster, 0));
+        assert_eq!(join(&mut router, "v:wallet", &config), id(Feed::Cluster, 0));
+        assert_eq!(join(&mut router, MINTS[0], &config), id(Feed::TokenPrice, 0));
+        assert_eq!(join(&mut router, MINTS[1], &config), id(Feed::TokenPrice, 0));
+        // Shard 0 is full, so the next mint opens shard 1
+        assert_eq!(join(&mut router, MINTS[2], &config), id(Feed::TokenPrice, 1));
+        // Rejoining keeps the existing assignment without adding load
+        assert_eq!(join(&mut router, MINTS[0], &config), id(Feed::TokenPrice, 0));
+        assert_eq!(router.rooms_on(id(Feed::TokenPrice, 0)), 2);
+
+        // Freed capacity is reused before opening more shards
+        assert_eq!(router.release(MINTS[1]), Some(id(Feed::TokenPrice, 0)));
+        assert_eq!(router.route("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", &config), id(Feed::TokenPrice, 0));
+    }
+
+    #[test]
+    fn test_spreads_over_full_shards_at_the_limit() {
+        let config = PoolConfig {
+            max_rooms_per_connection: 1,
+            max_connections_per_feed: 2,
+            ..Default::default()
+        };
+        let mut router = RoomRouter::default();
+
+        join(&mut router, "new_pairs", &config);
+        join(&mut router, "sol_price", &config);
+        assert_eq!(join(&mut router, "migrations", &config), id(Feed::Cluster, 0));
+        assert_eq!(join(&mut router, "v:wallet", &config), id(Feed::Cluster, 1));
+    }
+
+    #[tokio::test]
+    async fn test_handler_tags_and_counts_drops() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let handler = PoolHandler {
+            source: id(Feed::TokenPrice, 3),
+            tx,
+            dropped: Arc::clone(&dropped),
+        };
+
+        handler.on_reconnected(2).await;
+        handler.on_error("lost".to_string()).await;
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.source.to_string(), "token_price#3");
+        assert!(matches!(event.event, ConnectionEvent::Reconnected(2)));
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Backoff settings for automatic reconnection after the connection drops
+#[derive(Debug, Clone)]
+pub struct ReconnectConfig {
+    /// Reconnect automatically when the read task ends without a manual disconnect
+    pub enabled: bool,
+    /// Delay before the first attempt
+    pub initial_delay: Duration,
+    /// Upper bound for any delay, jitter included
+    pub max_delay: Duration,
+    /// Factor applied to the delay after each failed attempt
+    pub multiplier: f64,
+    /// Fraction of the delay randomly added or removed, in `0.0..=1.0`
+    pub jitter: f64,
+    /// Attempts before giving up; None retries forever
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectConfig {
+    /// Gets the randomized delay before an attempt
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - u32 - Zero-based attempt number
+    ///
+    /// # Returns
+    ///
+    /// Duration - Backoff delay with jitter applied
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.delay_with_sample(attempt, rand::thread_rng().gen_range(0.0..1.0))
+    }
+
+    /// Backoff delay for `attempt` with a jitter sample in `0.0..1.0`, where 0.5 means no jitter
+    pub(crate) fn delay_with_sample(&self, attempt: u32, sample: f64) -> Duration {
+        let max = self.max_delay.as_secs_f64();
+        let base = self.initial_delay.as_secs_f64() * self.multiplier.max(1.0).powi(attempt.min(64) as i32);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = 1.0 + jitter * (2.0 * sample.clamp(0.0, 1.0) - 1.0);
+        Duration::from_secs_f64((base.min(max) * factor).clamp(0.0, max))
+    }
+
+    /// Returns true if another attempt is allowed after `attempts` failures
+    pub(crate) fn allows(&self, attempts: u32) -> bool {
+        self.max_attempts.is_none_or(|max| attempts < max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Duration, expected_ms: u64) {
+        let diff = actual.as_secs_f64() - expected_ms as f64 / 1000.0;
+        assert!(diff.abs() < 1e-6, "{:?} != {}ms", actual, expected_ms);
+    }
+
+    #[test]
+    fn test_delay_grows_and_caps() {
+        let config = ReconnectConfig::default();
+        let delays: Vec<_> = (0..8).map(|attempt| config.delay_with_sample(attempt, 0.5)).collect();
+
+        assert_close(delays[0], 500);
+        assert_close(delays[1], 1_000);
+        assert_close(delays[3], 4_000);
+        assert_close(delays[7], 30_000);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let config = ReconnectConfig::default();
+
+        assert_close(config.delay_with_sample(2, 0.0), 1_600);
+        assert_close(config.delay_with_sample(2, 1.0), 2_400);
+        assert!(config.delay_with_sample(20, 1.0) <= config.max_delay);
+        for _ in 0..100 {
+            let delay = config.delay(1);
+            assert!(delay >= Duration::from_millis(799) && delay <= Duration::from_millis(1201));
+        }
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let config = ReconnectConfig { max_attempts: Some(3), ..Default::default() };
+        assert!(config.allows(2));
+        assert!(!config.allows(3));
+        assert!(ReconnectConfig::default().allows(u32::MAX));
+    }
+}
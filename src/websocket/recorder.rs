@@ -0,0 +1,401 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use crate::websocket::dispatch::FrameDispatcher;
+use crate::websocket::handler::MessageHandler;
+use crate::websocket::rooms::{DecodeMetrics, DecodeStats};
+use crate::websocket::stream::{RoomEvent, Subscription, SubscriptionRegistry};
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Invalid frame on line {line}: {error}")]
+    Parse { line: usize, error: serde_json::Error },
+}
+
+/// One inbound text frame as captured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Receive time, unix milliseconds
+    #[serde(rename = "t")]
+    pub received_at: i64,
+    /// Connection the frame arrived on
+    #[serde(rename = "c")]
+    pub connection: String,
+    /// The raw frame text
+    #[serde(rename = "f")]
+    pub frame: String,
+}
+
+/// How long recorded frames may sit in the writer's buffer
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+enum WriterCommand {
+    Frame(RecordedFrame),
+    Flush(SyncSender<io::Result<()>>),
+}
+
+/// Appends inbound frames to a JSONL file, one `{"t","c","f"}` object per line.
+///
+/// Frames are handed to a writer thread, so recording never blocks the read loop on disk IO.
+/// The writer flushes at least once a second; call `flush` to persist immediately. Dropping the
+/// recorder writes and flushes everything recorded so far.
+pub struct SessionRecorder {
+    commands: Option<Sender<WriterCommand>>,
+    writer: Option<JoinHandle<()>>,
+    /// First write error; the writer stops after it
+    failed: Arc<Mutex<Option<(io::ErrorKind, String)>>>,
+}
+
+impl SessionRecorder {
+    /// Opens a recording, appending if the file exists
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - impl AsRef<Path> - Destination file
+    ///
+    /// # Returns
+    ///
+    /// io::Result<SessionRecorder> - A recorder writing to the file
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (commands, receiver) = std::sync::mpsc::channel();
+        let failed = Arc::new(Mutex::new(None));
+        let writer = std::thread::Builder::new().name("session-recorder".to_string()).spawn({
+            let failed = Arc::clone(&failed);
+            move || {
+                // The receiver outlives the error being stored, so senders that see the channel
+                // closed also see why
+                if let Err(e) = run_writer(BufWriter::new(file), &receiver) {
+                    *failed.lock().expect("recorder lock poisoned") = Some((e.kind(), e.to_string()));
+                }
+            }
+        })?;
+        Ok(Self {
+            commands: Some(commands),
+            writer: Some(writer),
+            failed,
+        })
+    }
+
+    /// Records a frame received now
+    pub fn record(&self, connection: &str, frame: &str) -> io::Result<()> {
+        self.write(&RecordedFrame {
+            received_at: chrono::Utc::now().timestamp_millis(),
+            connection: connection.to_string(),
+            frame: frame.to_string(),
+        })
+    }
+
+    /// Queues a frame for the writer; fails once the writer has stopped on an IO error
+    pub fn write(&self, frame: &RecordedFrame) -> io::Result<()> {
+        self.send(WriterCommand::Frame(frame.clone()))
+    }
+
+    /// Writes and flushes everything recorded so far, blocking until the writer is done
+    pub fn flush(&self) -> io::Result<()> {
+        let (reply, done) = std::sync::mpsc::sync_channel(1);
+        self.send(WriterCommand::Flush(reply))?;
+        done.recv().unwrap_or_else(|_| Err(self.error()))
+    }
+
+    fn send(&self, command: WriterCommand) -> io::Result<()> {
+        self.commands
+            .as_ref()
+            .and_then(|commands| commands.send(command).ok())
+            .ok_or_else(|| self.error())
+    }
+
+    /// The error that stopped the writer
+    fn error(&self) -> io::Error {
+        match &*self.failed.lock().expect("recorder lock poisoned") {
+            Some((kind, message)) => io::Error::new(*kind, message.clone()),
+            None => io::Error::new(io::ErrorKind::BrokenPipe, "recorder writer stopped"),
+        }
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        // Closing the channel lets the writer drain, flush and exit
+        self.commands.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+/// Writes frames until every sender is gone, flushing when asked and after `FLUSH_INTERVAL`
+fn run_writer(mut writer: BufWriter<File>, commands: &Receiver<WriterCommand>) -> io::Result<()> {
+    let mut dirty = false;
+    loop {
+        match commands.recv_timeout(FLUSH_INTERVAL) {
+            Ok(WriterCommand::Frame(frame)) => {
+                serde_json::to_writer(&mut writer, &frame)?;
+                writer.write_all(b"\n")?;
+                dirty = true;
+            }
+            Ok(WriterCommand::Flush(reply)) => {
+                if let Err(e) = writer.flush() {
+                    let _ = reply.send(Err(io::Error::new(e.kind(), e.to_string())));
+                    return Err(e);
+                }
+                dirty = false;
+                let _ = reply.send(Ok(()));
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if dirty {
+                    writer.flush()?;
+                    dirty = false;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return writer.flush(),
+        }
+    }
+}
+
+/// Pace of a replay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Keep the recorded gaps between frames
+    RealTime,
+    /// Divide the recorded gaps by this factor
+    Accelerated(f64),
+    /// No delays
+    AsFastAsPossible,
+}
+
+impl ReplaySpeed {
+    fn scale(&self, gap: Duration) -> Option<Duration> {
+        match self {
+            ReplaySpeed::RealTime => Some(gap),
+            ReplaySpeed::Accelerated(factor) if *factor > 0.0 => Some(gap.div_f64(*factor)),
+            ReplaySpeed::Accelerated(_) | ReplaySpeed::AsFastAsPossible => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    pub frames: usize,
+    pub decode: DecodeStats,
+}
+
+/// Feeds a recorded session through the same decoding, stream and handler path as a live
+/// connection.
+///
+/// Stream subscriptions drop events their buffer cannot hold, exactly as they would live. For
+/// a lossless replay at `AsFastAsPossible`, use a buffer at least as large as the recording or
+/// consume through a handler.
+pub struct SessionReplay {
+    frames: Vec<RecordedFrame>,
+    handler: Option<Arc<dyn MessageHandler>>,
+    registry: Arc<SubscriptionRegistry>,
+    /// Joins and leaves from subscriptions land here and go nowhere
+    _outbound: mpsc::UnboundedReceiver<Message>,
+}
+
+impl SessionReplay {
+    /// Loads a recording
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - impl AsRef<Path> - A file written by `SessionRecorder`
+    ///
+    /// # Returns
+    ///
+    /// Result<SessionReplay, ReplayError> - The replay, frames in file order
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut frames = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = serde_json::from_str(&line).map_err(|error| ReplayError::Parse { line: index + 1, error })?;
+            frames.push(frame);
+        }
+        Ok(Self::from_frames(frames))
+    }
+
+    pub fn from_frames(frames: Vec<RecordedFrame>) -> Self {
+        let registry = Arc::new(SubscriptionRegistry::new());
+        let (outbound_tx, outbound_rx) = mpsc::unbounded_channel();
+        registry.set_outbound(Some(outbound_tx));
+        Self {
+            frames,
+            handler: None,
+            registry,
+            _outbound: outbound_rx,
+        }
+    }
+
+    /// Delivers replayed messages to a handler
+    pub fn with_handler(mut self, handler: Arc<dyn MessageHandler>) -> Self {
+        self.handler = Some(handler);
+        self
+    }
+
+    /// Keeps only frames from one connection
+    pub fn only_connection(mut self, connection: &str) -> Self {
+        self.frames.retain(|frame| frame.connection == connection);
+        self
+    }
+
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// Subscribes a typed stream to a room, as `WebSocketClient` does live
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - &str - The room to receive
+    /// * `buffer` - usize - Events buffered before new ones are dropped
+    ///
+    /// # Returns
+    ///
+    /// Subscription<T> - Stream of the room's events
+    pub fn subscribe<T: RoomEvent>(&self, room: &str, buffer: usize) -> Subscription<T> {
+        self.registry
+            .subscribe(room, buffer)
+            .expect("replay registry always has an outbound channel")
+    }
+
+    /// Replays every frame
+    ///
+    /// # Arguments
+    ///
+    /// * `speed` - ReplaySpeed - Pace relative to the recording
+    ///
+    /// # Returns
+    ///
+    /// ReplayStats - Frames replayed and how they decoded
+    pub async fn run(&self, speed: ReplaySpeed) -> ReplayStats {
+        let dispatcher = FrameDispatcher {
+            handler: self.handler.clone().unwrap_or_else(|| Arc::new(NoopHandler)),
+            registry: Arc::clone(&self.registry),
+            metrics: Arc::new(DecodeMetrics::new()),
+            // Awaiting the handler inline keeps replays deterministic
+            queue: None,
+        };
+
+        let started = Instant::now();
+        let first = self.frames.first().map(|frame| frame.received_at);
+        for frame in &self.frames {
+            let gap = Duration::from_millis((frame.received_at - first.unwrap_or(0)).max(0) as u64);
+            match speed.scale(gap) {
+                Some(offset) => tokio::time::sleep_until(started + offset).await,
+                // Let stream consumers on other tasks keep up
+                None => tokio::task::yield_now().await,
+            }
+            dispatcher.dispatch_text(&frame.frame).await;
+        }
+
+        ReplayStats {
+            frames: self.frames.len(),
+            decode: dispatcher.metrics.snapshot(),
+        }
+    }
+}
+
+struct NoopHandler;
+
+#[async_trait::async_trait]
+impl MessageHandler for NoopHandler {
+    async fn handle_message(&self, _message: crate::websocket::messages::WebSocketMessage) {}
+    async fn on_connected(&self, _session_id: String) {}
+    async fn on_disconnected(&self, _reason: String) {}
+    async fn on_error(&self, _error: String) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::handler::DefaultMessageHandler;
+    use crate::websocket::messages::TokenPriceTick;
+
+    const MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    fn frame(received_at: i64, text: &str) -> RecordedFrame {
+        RecordedFrame {
+            received_at,
+            connection: "cluster#0".to_string(),
+            frame: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_through_handler_and_streams() {
+        let path = std::env::temp_dir().join(format!("axiom_session_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let recorder = SessionRecorder::create(&path).unwrap();
+        recorder.record("cluster#0", r#"{"room":"new_pairs","content":{"token_address":"mint","protocol":"Pump V1"}}"#).unwrap();
+        recorder.record("token_price#0", &format!(r#"{{"room":"{}","content":0.25}}"#, MINT)).unwrap();
+        recorder.record("cluster#0", "not json").unwrap();
+        recorder.flush().unwrap();
+
+        let handler = Arc::new(DefaultMessageHandler::new());
+        let replay = SessionReplay::load(&path).unwrap().with_handler(handler.clone());
+        let mut ticks = replay.subscribe::<TokenPriceTick>(MINT, 8);
+        let stats = replay.run(ReplaySpeed::AsFastAsPossible).await;
+
+        assert_eq!(stats.frames, 3);
+        assert_eq!(stats.decode, DecodeStats { decoded: 2, raw: 0, failed: 0, unrecognized: 1 });
+        assert_eq!(handler.get_new_pairs().await[0].protocol, "Pump V1");
+        assert_eq!(ticks.recv().await.unwrap().price_usd, Some(0.25));
+
+        let only_prices = SessionReplay::load(&path).unwrap().only_connection("token_price#0");
+        assert_eq!(only_prices.frames().len(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_dropping_recorder_persists_frames() {
+        let path = std::env::temp_dir().join(format!("axiom_session_drop_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let recorder = SessionRecorder::create(&path).unwrap();
+        for i in 0..100 {
+            recorder.record("cluster#0", &format!(r#"{{"n":{}}}"#, i)).unwrap();
+        }
+        drop(recorder);
+
+        let replay = SessionReplay::load(&path).unwrap();
+        assert_eq!(replay.frames().len(), 100);
+        assert_eq!(replay.frames()[99].frame, r#"{"n":99}"#);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_replay_keeps_scaled_gaps() {
+        let frames = vec![frame(1_000, "{}"), frame(1_040, "{}"), frame(1_200, "{}")];
+
+        let started = Instant::now();
+        SessionReplay::from_frames(frames.clone()).run(ReplaySpeed::RealTime).await;
+        assert_eq!(started.elapsed(), Duration::from_millis(200));
+
+        let started = Instant::now();
+        SessionReplay::from_frames(frames.clone()).run(ReplaySpeed::Accelerated(4.0)).await;
+        assert_eq!(started.elapsed(), Duration::from_millis(50));
+
+        let started = Instant::now();
+        SessionReplay::from_frames(frames).run(ReplaySpeed::AsFastAsPossible).await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+}
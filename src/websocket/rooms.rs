@@ -0,0 +1,225 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::models::solana::Pubkey;
+use crate::websocket::messages::{
+    MigrationEvent, NewPairEvent, PairStatsUpdate, SolPriceUpdate, TokenPriceTick, WalletTransactionEvent,
+    WebSocketMessage,
+};
+
+pub const NEW_PAIRS_ROOM: &str = "new_pairs";
+pub const SOL_PRICE_ROOM: &str = "sol_price";
+pub const MIGRATIONS_ROOM: &str = "migrations";
+/// Prefix of wallet transaction rooms, `v:<wallet>`
+pub const WALLET_ROOM_PREFIX: &str = "v:";
+/// Prefix of pair stats rooms, `p:<pair>`
+pub const PAIR_STATS_ROOM_PREFIX: &str = "p:";
+
+/// The kind of an Axiom room, derived from its name
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RoomKind {
+    NewPairs,
+    SolPrice,
+    Migrations,
+    WalletTransactions(String),
+    PairStats(String),
+    /// A room named after a token mint
+    TokenPrice(String),
+    Unknown(String),
+}
+
+impl RoomKind {
+    pub fn parse(room: &str) -> Self {
+        match room {
+            NEW_PAIRS_ROOM => RoomKind::NewPairs,
+            SOL_PRICE_ROOM => RoomKind::SolPrice,
+            MIGRATIONS_ROOM => RoomKind::Migrations,
+            _ => {
+                if let Some(wallet) = room.strip_prefix(WALLET_ROOM_PREFIX) {
+                    RoomKind::WalletTransactions(wallet.to_string())
+                } else if let Some(pair) = room.strip_prefix(PAIR_STATS_ROOM_PREFIX) {
+                    RoomKind::PairStats(pair.to_string())
+                } else if room.parse::<Pubkey>().is_ok() {
+                    RoomKind::TokenPrice(room.to_string())
+                } else {
+                    RoomKind::Unknown(room.to_string())
+                }
+            }
+        }
+    }
+
+    /// Short name used in errors and metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            RoomKind::NewPairs => "new_pairs",
+            RoomKind::SolPrice => "sol_price",
+            RoomKind::Migrations => "migrations",
+            RoomKind::WalletTransactions(_) => "wallet_transactions",
+            RoomKind::PairStats(_) => "pair_stats",
+            RoomKind::TokenPrice(_) => "token_price",
+            RoomKind::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for RoomKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Invalid {kind} content in room {room}: {message}")]
+pub struct RoomDecodeError {
+    pub room: String,
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Decodes a room message into its typed `WebSocketMessage` variant
+///
+/// # Arguments
+///
+/// * `room` - &str - The room the message arrived on
+/// * `content` - &Value - The message content
+///
+/// # Returns
+///
+/// Result<WebSocketMessage, RoomDecodeError> - The typed message; unknown rooms give `RoomUpdate`
+pub fn decode_room_message(room: &str, content: &Value) -> Result<WebSocketMessage, RoomDecodeError> {
+    let kind = RoomKind::parse(room);
+    let error = |message: String| RoomDecodeError {
+        room: room.to_string(),
+        kind: kind.name(),
+        message,
+    };
+
+    match &kind {
+        RoomKind::NewPairs => NewPairEvent::from_content(content)
+            .map(|pair| WebSocketMessage::NewPair(Box::new(pair)))
+            .map_err(|e| error(e.to_string())),
+        RoomKind::SolPrice => SolPriceUpdate::from_content(content)
+            .map(WebSocketMessage::SolPrice)
+            .ok_or_else(|| error("no price".to_string())),
+        RoomKind::Migrations => MigrationEvent::from_content(content)
+            .map(|migration| WebSocketMessage::Migration(Box::new(migration)))
+            .map_err(|e| error(e.to_string())),
+        RoomKind::WalletTransactions(_) => WalletTransactionEvent::from_content(room, content)
+            .map(|tx| WebSocketMessage::WalletTransaction(Box::new(tx)))
+            .map_err(|e| error(e.to_string())),
+        RoomKind::PairStats(_) => PairStatsUpdate::from_content(room, content)
+            .map(|stats| WebSocketMessage::PairStats(Box::new(stats)))
+            .map_err(|e| error(e.to_string())),
+        RoomKind::TokenPrice(_) => TokenPriceTick::from_content(room, content)
+            .map(WebSocketMessage::TokenPrice)
+            .ok_or_else(|| error("no price".to_string())),
+        RoomKind::Unknown(_) => Ok(WebSocketMessage::RoomUpdate {
+            room: room.to_string(),
+            content: content.clone(),
+        }),
+    }
+}
+
+/// Counts of inbound frames by decode outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeStats {
+    /// Room messages decoded into a typed variant
+    pub decoded: u64,
+    /// Room messages from rooms without a decoder, delivered as `RoomUpdate`
+    pub raw: u64,
+    /// Room messages whose content did not match the room's type
+    pub failed: u64,
+    /// Text frames that were neither room messages nor known control messages
+    pub unrecognized: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DecodeMetrics {
+    decoded: AtomicU64,
+    raw: AtomicU64,
+    failed: AtomicU64,
+    unrecognized: AtomicU64,
+}
+
+impl DecodeMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, result: &Result<WebSocketMessage, RoomDecodeError>) {
+        let counter = match result {
+            Ok(WebSocketMessage::RoomUpdate { .. }) => &self.raw,
+            Ok(_) => &self.decoded,
+            Err(_) => &self.failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_unrecognized(&self) {
+        self.unrecognized.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> DecodeStats {
+        DecodeStats {
+            decoded: self.decoded.load(Ordering::Relaxed),
+            raw: self.raw.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            unrecognized: self.unrecognized.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    #[test]
+    fn test_room_kinds() {
+        assert_eq!(RoomKind::parse("new_pairs"), RoomKind::NewPairs);
+        assert_eq!(RoomKind::parse("v:wallet"), RoomKind::WalletTransactions("wallet".to_string()));
+        assert_eq!(RoomKind::parse("p:pair"), RoomKind::PairStats("pair".to_string()));
+        assert_eq!(RoomKind::parse(MINT), RoomKind::TokenPrice(MINT.to_string()));
+        assert_eq!(RoomKind::parse("surge-updates"), RoomKind::Unknown("surge-updates".to_string()));
+    }
+
+    #[test]
+    fn test_decodes_each_room() {
+        let metrics = DecodeMetrics::new();
+        let decode = |room: &str, content: Value| {
+            let result = decode_room_message(room, &content);
+            metrics.record(&result);
+            result
+        };
+
+        assert!(matches!(decode(MINT, json!("0.5")), Ok(WebSocketMessage::TokenPrice(t)) if t.price_usd == Some(0.5)));
+        assert!(matches!(decode("sol_price", json!(172.4)), Ok(WebSocketMessage::SolPrice(p)) if p.price_usd == 172.4));
+        assert!(matches!(
+            decode("p:pair", json!({ "buyCount": 12, "volumeSol": 3.5 })),
+            Ok(WebSocketMessage::PairStats(s)) if s.pair_address == "pair" && s.buy_count == Some(12)
+        ));
+        assert!(matches!(
+            decode("migrations", json!({ "tokenAddress": MINT, "pairAddress": "amm", "protocol": "Pump AMM" })),
+            Ok(WebSocketMessage::Migration(m)) if m.pair_address == "amm"
+        ));
+        assert!(matches!(
+            decode("v:wallet", json!({ "signature": "sig", "type": "sell" })),
+            Ok(WebSocketMessage::WalletTransaction(tx)) if tx.wallet_address == "wallet" && tx.transaction_type == "sell"
+        ));
+        assert!(matches!(
+            decode("surge-updates", json!({ "a": 1 })),
+            Ok(WebSocketMessage::RoomUpdate { room, content }) if room == "surge-updates" && content["a"] == 1
+        ));
+
+        let error = decode("migrations", json!({ "pairAddress": "amm" })).unwrap_err();
+        assert_eq!((error.room.as_str(), error.kind), ("migrations", "migrations"));
+        assert!(decode(MINT, json!({ "holders": 10 })).is_err());
+
+        assert_eq!(metrics.snapshot(), DecodeStats { decoded: 5, raw: 1, failed: 2, unrecognized: 0 });
+    }
+}
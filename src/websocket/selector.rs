@@ -0,0 +1,212 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use tokio_tungstenite::connect_async;
+
+use crate::websocket::client::{handshake_request, Region};
+
+/// Settings for ranking cluster hosts by handshake latency
+#[derive(Debug, Clone)]
+pub struct SelectorConfig {
+    /// Give up on a host whose handshake takes longer than this
+    pub probe_timeout: Duration,
+    /// Re-probe every host this often while connected; rankings older than this are refreshed
+    /// before the next connect
+    pub reevaluate_every: Duration,
+}
+
+impl Default for SelectorConfig {
+    fn default() -> Self {
+        Self {
+            probe_timeout: Duration::from_secs(3),
+            reevaluate_every: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Probe result for one host
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostLatency {
+    pub host: String,
+    /// Handshake round trip; None if the host could not be reached
+    pub latency: Option<Duration>,
+    /// Why the host was unreachable or last failed to connect
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct SelectorState {
+    ranking: Vec<HostLatency>,
+    probed_at: Option<Instant>,
+}
+
+/// Ranks a region's cluster hosts by handshake latency and hands out failover order.
+///
+/// A probe opens an unauthenticated WebSocket handshake. An HTTP rejection still counts as
+/// reachable, because the server answered and the round trip is what is being measured.
+pub struct RegionSelector {
+    hosts: Vec<&'static str>,
+    config: SelectorConfig,
+    state: Mutex<SelectorState>,
+}
+
+impl RegionSelector {
+    /// Creates a selector over the region's hosts; nothing is probed until first use
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - &Region - Region whose hosts are ranked; `Region::Auto` covers every host
+    /// * `config` - SelectorConfig - Probe timeout and re-evaluation interval
+    ///
+    /// # Returns
+    ///
+    /// RegionSelector - A new selector instance
+    pub fn new(region: &Region, config: SelectorConfig) -> Self {
+        Self {
+            hosts: region.get_urls(),
+            config,
+            state: Mutex::new(SelectorState::default()),
+        }
+    }
+
+    pub fn config(&self) -> &SelectorConfig {
+        &self.config
+    }
+
+    /// Probes every host concurrently and stores the ranking
+    ///
+    /// # Returns
+    ///
+    /// Vec<HostLatency> - Hosts ranked fastest first, unreachable hosts last
+    pub async fn probe(&self) -> Vec<HostLatency> {
+        let timeout = self.config.probe_timeout;
+        let probes = join_all(self.hosts.iter().map(|host| probe_host(host, timeout))).await;
+        let ranking = rank(probes);
+
+        let mut state = self.state.lock().expect("selector lock poisoned");
+        state.ranking = ranking.clone();
+        state.probed_at = Some(Instant::now());
+        ranking
+    }
+
+    /// Gets hosts in connection order, probing first if the ranking is missing or stale
+    ///
+    /// # Returns
+    ///
+    /// Vec<String> - Every host, best first
+    pub async fn candidates(&self) -> Vec<String> {
+        let fresh = {
+            let state = self.state.lock().expect("selector lock poisoned");
+            state
+                .probed_at
+                .is_some_and(|at| at.elapsed() < self.config.reevaluate_every)
+        };
+        let ranking = if fresh { self.rankings() } else { self.probe().await };
+        ranking.into_iter().map(|entry| entry.host).collect()
+    }
+
+    /// Gets the latest ranking without probing
+    pub fn rankings(&self) -> Vec<HostLatency> {
+        self.state.lock().expect("selector lock poisoned").ranking.clone()
+    }
+
+    /// Moves a host that failed to connect behind every other host until the next probe
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - &str - The host that failed
+    /// * `error` - String - The connection error
+    pub fn report_failure(&self, host: &str, error: String) {
+        let mut state = self.state.lock().expect("selector lock poisoned");
+        if let Some(index) = state.ranking.iter().position(|entry| entry.host == host) {
+            let mut entry = state.ranking.remove(index);
+            entry.error = Some(error);
+            state.ranking.push(entry);
+        }
+    }
+}
+
+/// Orders probe results fastest first; unreachable hosts keep their order at the end
+pub(crate) fn rank(mut probes: Vec<HostLatency>) -> Vec<HostLatency> {
+    probes.sort_by_key(|probe| probe.latency.unwrap_or(Duration::MAX));
+    probes
+}
+
+async fn probe_host(host: &'static str, timeout: Duration) -> HostLatency {
+    let started = Instant::now();
+    let outcome = match handshake_request(host, None) {
+        Ok(request) => match tokio::time::timeout(timeout, connect_async(request)).await {
+            Ok(Ok((mut stream, _))) => {
+                let _ = stream.close(None).await;
+                Ok(())
+            }
+            // The server answered the upgrade, just not with 101
+            Ok(Err(tungstenite::Error::Http(_))) => Ok(()),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("no handshake within {:?}", timeout)),
+        },
+        Err(e) => Err(e.to_string()),
+    };
+
+    match outcome {
+        Ok(()) => HostLatency {
+            host: host.to_string(),
+            latency: Some(started.elapsed()),
+            error: None,
+        },
+        Err(error) => HostLatency {
+            host: host.to_string(),
+            latency: None,
+            error: Some(error),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(host: &str, latency_ms: Option<u64>) -> HostLatency {
+        HostLatency {
+            host: host.to_string(),
+            latency: latency_ms.map(Duration::from_millis),
+            error: latency_ms.is_none().then(|| "unreachable".to_string()),
+        }
+    }
+
+    fn hosts(ranking: &[HostLatency]) -> Vec<&str> {
+        ranking.iter().map(|entry| entry.host.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rank_fastest_first_unreachable_last() {
+        let ranking = rank(vec![probe("a", None), probe("b", Some(80)), probe("c", Some(20)), probe("d", None)]);
+        assert_eq!(hosts(&ranking), vec!["c", "b", "a", "d"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_host_moves_to_back() {
+        let selector = RegionSelector::new(&Region::USWest, SelectorConfig::default());
+        {
+            let mut state = selector.state.lock().unwrap();
+            state.ranking = rank(vec![probe("fast", Some(10)), probe("slow", Some(90))]);
+            state.probed_at = Some(Instant::now());
+        }
+
+        selector.report_failure("fast", "refused".to_string());
+        assert_eq!(selector.candidates().await, vec!["slow", "fast"]);
+        assert_eq!(selector.rankings()[1].error.as_deref(), Some("refused"));
+    }
+
+    #[test]
+    fn test_auto_covers_every_host() {
+        let auto = Region::Auto.get_urls();
+        assert!(auto.contains(&"socket8.axiom.trade"));
+        assert!(auto.contains(&"cluster9.axiom.trade"));
+        let mut deduped = auto.clone();
+        deduped.sort();
+        deduped.dedup();
+        assert_eq!(auto.len(), deduped.len());
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use crate::websocket::client::WebSocketError;
+use crate::websocket::messages::{
+    MigrationEvent, NewPairEvent, PairStatsUpdate, SolPriceUpdate, TokenPriceTick, WalletTransactionEvent,
+};
+
+/// Default number of undelivered events buffered per subscription
+pub const DEFAULT_STREAM_BUFFER: usize = 256;
+
+/// An event type decoded from room content
+pub trait RoomEvent: Sized + Send + 'static {
+    /// Decodes room content; None skips the message for this subscriber
+    fn decode(room: &str, content: &Value) -> Option<Self>;
+}
+
+impl RoomEvent for NewPairEvent {
+    fn decode(_room: &str, content: &Value) -> Option<Self> {
+        NewPairEvent::from_content(content).ok()
+    }
+}
+
+impl RoomEvent for TokenPriceTick {
+    fn decode(room: &str, content: &Value) -> Option<Self> {
+        TokenPriceTick::from_content(room, content)
+    }
+}
+
+impl RoomEvent for WalletTransactionEvent {
+    fn decode(room: &str, content: &Value) -> Option<Self> {
+        WalletTransactionEvent::from_content(room, content).ok()
+    }
+}
+
+impl RoomEvent for PairStatsUpdate {
+    fn decode(room: &str, content: &Value) -> Option<Self> {
+        PairStatsUpdate::from_content(room, content).ok()
+    }
+}
+
+impl RoomEvent for MigrationEvent {
+    fn decode(_room: &str, content: &Value) -> Option<Self> {
+        MigrationEvent::from_content(content).ok()
+    }
+}
+
+impl RoomEvent for SolPriceUpdate {
+    fn decode(_room: &str, content: &Value) -> Option<Self> {
+        SolPriceUpdate::from_content(content)
+    }
+}
+
+/// Receives room content for one subscription
+trait RoomSink: Send + Sync {
+    /// Delivers content; returns false once the subscription's receiver is gone
+    fn deliver(&self, room: &str, content: &Value, stats: &StreamCounters) -> bool;
+}
+
+struct TypedSink<T> {
+    tx: mpsc::Sender<T>,
+}
+
+impl<T: RoomEvent> RoomSink for TypedSink<T> {
+    fn deliver(&self, room: &str, content: &Value, stats: &StreamCounters) -> bool {
+        let Some(event) = T::decode(room, content) else {
+            stats.undecodable.fetch_add(1, Ordering::Relaxed);
+            return true;
+        };
+        match self.tx.try_send(event) {
+            Ok(()) => {
+                stats.delivered.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                stats.dropped.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
+struct SinkEntry {
+    id: u64,
+    sink: Box<dyn RoomSink>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StreamCounters {
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    undecodable: AtomicU64,
+}
+
+/// Delivery counters across all stream subscriptions of a client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    pub delivered: u64,
+    /// Events dropped because a subscription's buffer was full
+    pub dropped: u64,
+    /// Room messages a subscription could not decode
+    pub undecodable: u64,
+}
+
+/// Routes room messages to stream subscriptions and joins/leaves rooms as subscriptions
+/// come and go. Also tracks rooms joined through the handler-based `subscribe_*` methods, so
+/// every room can be rejoined after a reconnect. Shared by the client, its tasks and every
+/// `Subscription`.
+pub(crate) struct SubscriptionRegistry {
+    rooms: Mutex<HashMap<String, Vec<SinkEntry>>>,
+    /// Rooms joined for the message handler rather than a stream
+    joined: Mutex<HashSet<String>>,
+    outbound: Mutex<Option<mpsc::UnboundedSender<Message>>>,
+    next_id: AtomicU64,
+    stats: StreamCounters,
+}
+
+// WebSocketError carries tungstenite's error inline; boxing it here alone would not help callers
+#[allow(clippy::result_large_err)]
+impl SubscriptionRegistry {
+    pub fn new() -> Self {
+        Self {
+            rooms: Mutex::new(HashMap::new()),
+            joined: Mutex::new(HashSet::new()),
+            outbound: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+            stats: StreamCounters::default(),
+        }
+    }
+
+    /// Sets the channel to the socket writer; None while disconnected
+    pub fn set_outbound(&self, outbound: Option<mpsc::UnboundedSender<Message>>) {
+        *self.outbound.lock().expect("outbound lock poisoned") = outbound;
+    }
+
+    /// Queues a message for the socket writer
+    pub fn send(&self, message: Message) -> Result<(), WebSocketError> {
+        let outbound = self.outbound.lock().expect("outbound lock poisoned");
+        let tx = outbound.as_ref().ok_or(WebSocketError::NotConnected)?;
+        tx.send(message).map_err(|e| WebSocketError::SendError(e.to_string()))
+    }
+
+    pub fn send_json(&self, message: &Value) -> Result<(), WebSocketError> {
+        let text = serde_json::to_string(message).map_err(|e| WebSocketError::SerializationError(e.to_string()))?;
+        self.send(Message::Text(text))
+    }
+
+    /// Every tracked room, joined for a stream or for the handler, sorted
+    pub fn rooms(&self) -> Vec<String> {
+        let mut rooms: BTreeSet<String> = self.rooms.lock().expect("rooms lock poisoned").keys().cloned().collect();
+        rooms.extend(self.joined.lock().expect("joined lock poisoned").iter().cloned());
+        rooms.into_iter().collect()
+    }
+
+    /// Joins a room for the message handler and tracks it for resubscription
+    pub fn join(&self, room: &str) -> Result<(), WebSocketError> {
+        self.send_json(&json!({ "action": "join", "room": room }))?;
+        self.joined.lock().expect("joined lock poisoned").insert(room.to_string());
+        Ok(())
+    }
+
+    /// Stops tracking a handler room, leaving it unless a stream still uses it
+    pub fn leave(&self, room: &str) -> Result<(), WebSocketError> {
+        let was_joined = self.joined.lock().expect("joined lock poisoned").remove(room);
+        let streamed = self.rooms.lock().expect("rooms lock poisoned").contains_key(room);
+        if !was_joined || streamed {
+            return Ok(());
+        }
+        match self.send_json(&json!({ "action": "leave", "room": room })) {
+            // Nothing to leave while disconnected; the room is no longer rejoined either way
+            Err(WebSocketError::NotConnected) => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Forgets handler rooms, e.g. after a manual disconnect. Stream rooms stay tracked for as
+    /// long as their subscriptions live.
+    pub fn forget_joined(&self) {
+        self.joined.lock().expect("joined lock poisoned").clear();
+    }
+
+    /// Drops every stream sink so subscriptions end, e.g. after a manual disconnect or once
+    /// reconnecting gives up. Buffered events are still delivered before `recv` returns None.
+    pub fn close_all(&self) {
+        self.rooms.lock().expect("rooms lock poisoned").clear();
+    }
+
+    /// Sends a join for every tracked room on the current connection
+    ///
+    /// # Returns
+    ///
+    /// Vec<(String, WebSocketError)> - Rooms that could not be rejoined
+    pub fn rejoin_all(&self) -> Vec<(String, WebSocketError)> {
+        self.rooms()
+            .into_iter()
+            .filter_map(|room| {
+                self.send_json(&json!({ "action": "join", "room": room }))
+                    .err()
+                    .map(|e| (room, e))
+            })
+            .collect()
+    }
+
+    fn is_joined(&self, room: &str) -> bool {
+        self.joined.lock().expect("joined lock poisoned").contains(room)
+    }
+
+    /// Delivers room content to every subscription of the room
+    pub fn dispatch(&self, room: &str, content: &Value) {
+        let mut rooms = self.rooms.lock().expect("rooms lock poisoned");
+        let Some(entries) = rooms.get_mut(room) else {
+            return;
+        };
+        // Receivers dropped without running Drop (e.g. leaked) are pruned here
+        entries.retain(|entry| entry.sink.deliver(room, content, &self.stats));
+    }
+
+    pub fn stats(&self) -> StreamStats {
+        StreamStats {
+            delivered: self.stats.delivered.load(Ordering::Relaxed),
+            dropped: self.stats.dropped.load(Ordering::Relaxed),
+            undecodable: self.stats.undecodable.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Registers a subscription, joining the room if it is the first one
+    pub fn subscribe<T: RoomEvent>(
+        self: &Arc<Self>,
+        room: &str,
+        buffer: usize,
+    ) -> Result<Subscription<T>, WebSocketError> {
+        let (tx, rx) = mpsc::channel(buffer.max(1));
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let first = {
+            let mut rooms = self.rooms.lock().expect("rooms lock poisoned");
+            let entries = rooms.entry(room.to_string()).or_default();
+            entries.push(SinkEntry { id, sink: Box::new(TypedSink { tx }) });
+            entries.len() == 1
+        };
+        if first
+            && !self.is_joined(room)
+            && let Err(e) = self.send_json(&json!({ "action": "join", "room": room }))
+        {
+            self.remove(room, id);
+            return Err(e);
+        }
+
+        Ok(Subscription {
+            room: room.to_string(),
+            id,
+            rx,
+            registry: Arc::clone(self),
+            _event: PhantomData,
+        })
+    }
+
+    /// Removes a subscription; returns true if the room should be left
+    fn remove(&self, room: &str, id: u64) -> bool {
+        let mut rooms = self.rooms.lock().expect("rooms lock poisoned");
+        let Some(entries) = rooms.get_mut(room) else {
+            return false;
+        };
+        entries.retain(|entry| entry.id != id);
+        if entries.is_empty() {
+            rooms.remove(room);
+            !self.is_joined(room)
+        } else {
+            false
+        }
+    }
+}
+
+/// A typed event stream for one room.
+///
+/// Events are buffered up to the subscription's capacity; when the buffer is full new events
+/// are dropped and counted in `StreamStats::dropped`. Dropping the subscription, or calling
+/// `unsubscribe`, leaves the room once no other subscription uses it. The stream ends when the
+/// client disconnects or stops reconnecting.
+pub struct Subscription<T> {
+    room: String,
+    id: u64,
+    rx: mpsc::Receiver<T>,
+    registry: Arc<SubscriptionRegistry>,
+    _event: PhantomData<fn() -> T>,
+}
+
+impl<T> Subscription<T> {
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    /// Waits for the next event
+    ///
+    /// # Returns
+    ///
+    /// Option<T> - The next event, or None once the client disconnected or gave up reconnecting
+    pub async fn recv(&mut self) -> Option<T> {
+        self.rx.recv().await
+    }
+
+    /// Unsubscribes now; equivalent to dropping the subscription
+    pub fn unsubscribe(self) {}
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        if self.registry.remove(&self.room, self.id) {
+            // Not connected means there is nothing to leave
+            let _ = self.registry.send_json(&json!({ "action": "leave", "room": self.room }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected_registry() -> (Arc<SubscriptionRegistry>, mpsc::UnboundedReceiver<Message>) {
+        let registry = Arc::new(SubscriptionRegistry::new());
+        let (tx, rx) = mpsc::unbounded_channel();
+        registry.set_outbound(Some(tx));
+        (registry, rx)
+    }
+
+    fn sent(rx: &mut mpsc::UnboundedReceiver<Message>) -> Vec<Value> {
+        let mut messages = Vec::new();
+        while let Ok(Message::Text(text)) = rx.try_recv() {
+            messages.push(serde_json::from_str(&text).unwrap());
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_join_once_and_leave_on_last_drop() {
+        let (registry, mut outbound) = connected_registry();
+        let mut first = registry.subscribe::<TokenPriceTick>("mint", 8).unwrap();
+        let second = registry.subscribe::<TokenPriceTick>("mint", 8).unwrap();
+        assert_eq!(sent(&mut outbound), vec![json!({ "action": "join", "room": "mint" })]);
+
+        registry.dispatch("mint", &json!({ "priceUsd": 1.5, "ts": 10 }));
+        registry.dispatch("other", &json!(2.0));
+        let tick = first.recv().await.unwrap();
+        assert_eq!((tick.token_address.as_str(), tick.price_usd, tick.timestamp), ("mint", Some(1.5), 10));
+
+        drop(second);
+        assert!(sent(&mut outbound).is_empty());
+        first.unsubscribe();
+        assert_eq!(sent(&mut outbound), vec![json!({ "action": "leave", "room": "mint" })]);
+        assert!(registry.rooms().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bounded_buffer_drops_and_counts() {
+        let (registry, _outbound) = connected_registry();
+        let mut wallet = registry.subscribe::<WalletTransactionEvent>("v:wallet", 2).unwrap();
+
+        for i in 0..4 {
+            registry.dispatch("v:wallet", &json!({ "signature": format!("sig{}", i), "type": "buy", "solAmount": 1.0 }));
+        }
+        registry.dispatch("v:wallet", &json!("not a transaction"));
+
+        assert_eq!(wallet.recv().await.unwrap().signature, "sig0");
+        assert_eq!(wallet.recv().await.unwrap().wallet_address, "wallet");
+        assert_eq!(registry.stats(), StreamStats { delivered: 2, dropped: 2, undecodable: 1 });
+    }
+
+    #[test]
+    fn test_rejoin_all_covers_stream_and_handler_rooms() {
+        let (registry, mut outbound) = connected_registry();
+        registry.join("v:wallet").unwrap();
+        registry.join("mint").unwrap();
+        let stream = registry.subscribe::<TokenPriceTick>("mint", 8).unwrap();
+        let _pairs = registry.subscribe::<NewPairEvent>("new_pairs", 8).unwrap();
+        sent(&mut outbound);
+
+        // The handler still uses the room, so dropping the stream does not leave it
+        drop(stream);
+        assert!(sent(&mut outbound).is_empty());
+        // ...and a stream keeps the room when the handler leaves
+        registry.join("new_pairs").unwrap();
+        registry.leave("new_pairs").unwrap();
+        assert_eq!(sent(&mut outbound), vec![json!({ "action": "join", "room": "new_pairs" })]);
+        assert_eq!(registry.rooms(), vec!["mint", "new_pairs", "v:wallet"]);
+
+        // A fresh connection gets a join per room
+        let (tx, mut reconnected) = mpsc::unbounded_channel();
+        registry.set_outbound(Some(tx));
+        assert!(registry.rejoin_all().is_empty());
+        let rooms: Vec<_> = sent(&mut reconnected).iter().map(|m| m["room"].as_str().unwrap().to_string()).collect();
+        assert_eq!(rooms, vec!["mint", "new_pairs", "v:wallet"]);
+
+        registry.forget_joined();
+        registry.set_outbound(None);
+        assert_eq!(registry.rooms(), vec!["new_pairs"]);
+        let failed = registry.rejoin_all();
+        assert_eq!(failed.len(), 1);
+        assert!(matches!(failed[0], (ref room, WebSocketError::NotConnected) if room == "new_pairs"));
+    }
+
+    #[tokio::test]
+    async fn test_close_all_ends_streams() {
+        let (registry, _outbound) = connected_registry();
+        let mut ticks = registry.subscribe::<TokenPriceTick>("mint", 8).unwrap();
+        registry.dispatch("mint", &json!({ "priceUsd": 1.5, "ts": 10 }));
+
+        registry.close_all();
+        assert!(registry.rooms().is_empty());
+        assert!(ticks.recv().await.is_some());
+        assert!(ticks.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_subscribe_requires_connection() {
+        let registry = Arc::new(SubscriptionRegistry::new());
+        assert!(matches!(
+            registry.subscribe::<NewPairEvent>("new_pairs", 8),
+            Err(WebSocketError::NotConnected)
+        ));
+        assert!(registry.rooms().is_empty());
+    }
+}
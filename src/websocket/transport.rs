@@ -0,0 +1,359 @@
+use std::sync::{Arc, OnceLock};
+
+use fastwebsockets::{FragmentCollectorRead, Frame, OpCode, Payload, WebSocketWrite};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{Future, SinkExt, StreamExt};
+use http_body_util::Empty;
+use hyper::body::Bytes;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+#[derive(Error, Debug)]
+pub enum TransportError {
+    #[error("Handshake failed: {0}")]
+    Handshake(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+
+    #[error("Connection closed")]
+    Closed,
+}
+
+impl From<tungstenite::Error> for TransportError {
+    fn from(error: tungstenite::Error) -> Self {
+        match error {
+            tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => TransportError::Closed,
+            tungstenite::Error::Io(e) => TransportError::Io(e),
+            e => TransportError::Protocol(e.to_string()),
+        }
+    }
+}
+
+impl From<fastwebsockets::WebSocketError> for TransportError {
+    fn from(error: fastwebsockets::WebSocketError) -> Self {
+        match error {
+            fastwebsockets::WebSocketError::ConnectionClosed | fastwebsockets::WebSocketError::UnexpectedEOF => {
+                TransportError::Closed
+            }
+            fastwebsockets::WebSocketError::IoError(e) => TransportError::Io(e),
+            e => TransportError::Protocol(e.to_string()),
+        }
+    }
+}
+
+/// An inbound frame, borrowed from the reader until the next read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboundFrame<'a> {
+    Text(&'a str),
+    Pong(&'a [u8]),
+    Close,
+    /// Binary, ping and other frames the client does not act on
+    Other,
+}
+
+/// Read half of a WebSocket connection
+#[async_trait::async_trait]
+pub trait FrameReader: Send {
+    /// Reads the next frame; pings are answered by the backend
+    async fn read_frame(&mut self) -> Result<InboundFrame<'_>, TransportError>;
+}
+
+/// Write half of a WebSocket connection
+#[async_trait::async_trait]
+pub trait FrameWriter: Send {
+    async fn send(&mut self, message: Message) -> Result<(), TransportError>;
+    async fn close(&mut self);
+}
+
+/// A connected socket's two halves
+pub type TransportHalves = (Box<dyn FrameReader>, Box<dyn FrameWriter>);
+
+/// The WebSocket implementation a connection runs on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TransportKind {
+    /// tokio-tungstenite; each text frame is copied into an owned `String`
+    #[default]
+    Tungstenite,
+    /// fastwebsockets over hyper; text frames are parsed straight from the read buffer
+    FastWebSockets,
+}
+
+impl TransportKind {
+    /// Opens a WebSocket with this backend
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - http::Request<()> - The upgrade request; `ws://` and `wss://` are supported
+    ///
+    /// # Returns
+    ///
+    /// Result<TransportHalves, TransportError> - Reader and writer for the connection
+    pub async fn connect(self, request: http::Request<()>) -> Result<TransportHalves, TransportError> {
+        match self {
+            TransportKind::Tungstenite => {
+                let (stream, _) = connect_async(request)
+                    .await
+                    .map_err(|e| TransportError::Handshake(e.to_string()))?;
+                let (write, read) = stream.split();
+                Ok((
+                    Box::new(TungsteniteReader { read, current: None }),
+                    Box::new(TungsteniteWriter { write }),
+                ))
+            }
+            TransportKind::FastWebSockets => connect_fast(request).await,
+        }
+    }
+}
+
+type TungsteniteStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+struct TungsteniteReader {
+    read: SplitStream<TungsteniteStream>,
+    /// The last message read, kept so the returned frame can borrow it
+    current: Option<Message>,
+}
+
+#[async_trait::async_trait]
+impl FrameReader for TungsteniteReader {
+    async fn read_frame(&mut self) -> Result<InboundFrame<'_>, TransportError> {
+        let message = self.read.next().await.ok_or(TransportError::Closed)??;
+        Ok(match self.current.insert(message) {
+            Message::Text(text) => InboundFrame::Text(text),
+            Message::Pong(payload) => InboundFrame::Pong(payload),
+            Message::Close(_) => InboundFrame::Close,
+            _ => InboundFrame::Other,
+        })
+    }
+}
+
+struct TungsteniteWriter {
+    write: SplitSink<TungsteniteStream, Message>,
+}
+
+#[async_trait::async_trait]
+impl FrameWriter for TungsteniteWriter {
+    async fn send(&mut self, message: Message) -> Result<(), TransportError> {
+        Ok(self.write.send(message).await?)
+    }
+
+    async fn close(&mut self) {
+        let _ = self.write.close().await;
+    }
+}
+
+/// Runs hyper's upgrade connection task on tokio
+struct SpawnExecutor;
+
+impl<Fut> hyper::rt::Executor<Fut> for SpawnExecutor
+where
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    fn execute(&self, fut: Fut) {
+        tokio::spawn(fut);
+    }
+}
+
+fn tls_connector() -> TlsConnector {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    let config = CONFIG.get_or_init(|| {
+        let mut roots = RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        Arc::new(ClientConfig::builder().with_root_certificates(roots).with_no_client_auth())
+    });
+    TlsConnector::from(Arc::clone(config))
+}
+
+async fn connect_fast(request: http::Request<()>) -> Result<TransportHalves, TransportError> {
+    let (mut parts, ()) = request.into_parts();
+    let host = parts
+        .uri
+        .host()
+        .ok_or_else(|| TransportError::Handshake("URL has no host".to_string()))?
+        .to_string();
+    let secure = parts.uri.scheme_str() == Some("wss");
+    let port = parts.uri.port_u16().unwrap_or(if secure { 443 } else { 80 });
+
+    // hyper writes the URI as given; the server expects origin form
+    let path = parts.uri.path_and_query().map_or("/", |p| p.as_str());
+    parts.uri = path
+        .parse()
+        .map_err(|e: http::uri::InvalidUri| TransportError::Handshake(e.to_string()))?;
+    let request = http::Request::from_parts(parts, Empty::<Bytes>::new());
+
+    let tcp = TcpStream::connect((host.as_str(), port)).await?;
+    tcp.set_nodelay(true)?;
+    if secure {
+        let domain = ServerName::try_from(host).map_err(|e| TransportError::Handshake(e.to_string()))?;
+        let tls = tls_connector().connect(domain, tcp).await?;
+        fast_handshake(request, tls).await
+    } else {
+        fast_handshake(request, tcp).await
+    }
+}
+
+async fn fast_handshake<S>(request: http::Request<Empty<Bytes>>, stream: S) -> Result<TransportHalves, TransportError>
+where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    let (ws, _) = fastwebsockets::handshake::client(&SpawnExecutor, request, stream)
+        .await
+        .map_err(|e| TransportError::Handshake(e.to_string()))?;
+    let (read, write) = ws.split(tokio::io::split);
+    let write = Arc::new(Mutex::new(write));
+    Ok((
+        Box::new(FastReader {
+            read: FragmentCollectorRead::new(read),
+            write: Arc::clone(&write),
+            current: Payload::Owned(Vec::new()),
+        }),
+        Box::new(FastWriter { write }),
+    ))
+}
+
+struct FastReader<R, W> {
+    read: FragmentCollectorRead<R>,
+    /// Shared with the writer so pongs and close replies go out on the same socket
+    write: Arc<Mutex<WebSocketWrite<W>>>,
+    /// Payload of the last frame; usually the read buffer's own bytes, never copied
+    current: Payload<'static>,
+}
+
+#[async_trait::async_trait]
+impl<R, W> FrameReader for FastReader<R, W>
+where
+    R: AsyncRead + Send + Unpin,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    async fn read_frame(&mut self) -> Result<InboundFrame<'_>, TransportError> {
+        let write = &self.write;
+        let frame = self
+            .read
+            .read_frame(&mut |obligated: Frame<'static>| async move { write.lock().await.write_frame(obligated).await })
+            .await?;
+        let opcode = frame.opcode;
+        self.current = match frame.payload {
+            Payload::Bytes(bytes) => Payload::Bytes(bytes),
+            Payload::Owned(owned) => Payload::Owned(owned),
+            borrowed => Payload::Owned(borrowed.to_vec()),
+        };
+
+        Ok(match opcode {
+            // The collector has already validated text as UTF-8
+            OpCode::Text => InboundFrame::Text(
+                std::str::from_utf8(&self.current).map_err(|e| TransportError::Protocol(e.to_string()))?,
+            ),
+            OpCode::Pong => InboundFrame::Pong(&self.current),
+            OpCode::Close => InboundFrame::Close,
+            _ => InboundFrame::Other,
+        })
+    }
+}
+
+struct FastWriter<W> {
+    write: Arc<Mutex<WebSocketWrite<W>>>,
+}
+
+#[async_trait::async_trait]
+impl<W> FrameWriter for FastWriter<W>
+where
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    async fn send(&mut self, message: Message) -> Result<(), TransportError> {
+        let frame = match message {
+            Message::Text(text) => Frame::text(Payload::Owned(text.into_bytes())),
+            Message::Binary(data) => Frame::binary(Payload::Owned(data)),
+            Message::Ping(data) => Frame::new(true, OpCode::Ping, None, Payload::Owned(data)),
+            Message::Pong(data) => Frame::pong(Payload::Owned(data)),
+            Message::Close(_) => Frame::close(1000, b""),
+            Message::Frame(_) => return Ok(()),
+        };
+        Ok(self.write.lock().await.write_frame(frame).await?)
+    }
+
+    async fn close(&mut self) {
+        let mut write = self.write.lock().await;
+        if !write.is_closed() {
+            let _ = write.write_frame(Frame::close(1000, b"")).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Echoes text frames and answers pings until the client closes
+    async fn echo_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                    while let Some(Ok(message)) = ws.next().await {
+                        if message.is_text() && ws.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        format!("ws://{}/", addr)
+    }
+
+    fn request(url: &str) -> http::Request<()> {
+        let host = url.trim_start_matches("ws://").trim_end_matches('/');
+        http::Request::builder()
+            .uri(url)
+            .header("Host", host)
+            .header("Connection", "Upgrade")
+            .header("Upgrade", "websocket")
+            .header("Sec-WebSocket-Version", "13")
+            .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key())
+            .body(())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_both_backends_echo_text_and_pong() {
+        let url = echo_server().await;
+        for kind in [TransportKind::Tungstenite, TransportKind::FastWebSockets] {
+            let (mut reader, mut writer) = kind.connect(request(&url)).await.unwrap();
+
+            writer.send(Message::Text(r#"{"room":"sol_price","content":172.4}"#.to_string())).await.unwrap();
+            assert_eq!(reader.read_frame().await.unwrap(), InboundFrame::Text(r#"{"room":"sol_price","content":172.4}"#));
+
+            writer.send(Message::Ping(vec![1, 2, 3])).await.unwrap();
+            assert_eq!(reader.read_frame().await.unwrap(), InboundFrame::Pong(&[1, 2, 3]), "{:?}", kind);
+
+            writer.close().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fast_reader_reports_closed_peer() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.close(None).await.unwrap();
+        });
+
+        let (mut reader, _writer) = TransportKind::FastWebSockets.connect(request(&url)).await.unwrap();
+        assert_eq!(reader.read_frame().await.unwrap(), InboundFrame::Close);
+    }
+}